@@ -0,0 +1,206 @@
+// Business Source License 1.1 (BSL 1.1)
+// Licensor: Bonsol Labs Inc.
+// Licensed Work: QCash
+// Change Date: 2030-12-31
+// Change License: Apache License 2.0
+// Use of this software is governed by the LICENSE file.
+
+// Canonical UTXO commitments, the only copy the guest, wasm and the node hash with.
+// Generic over the SHA-256 backend: sha2::Sha256 on the host, the risc0 accelerated
+// Sha256<Impl> in the guest. Needs neither std nor allocation.
+
+use alloc::vec::Vec;
+use core::fmt;
+use sha2::Digest;
+
+use crate::{DecryptedInput, EncryptedOutput, HASH, HASH_SIZE, PAYLOAD_VERSION, KYBER_CIPHERTEXT_SIZE, UTXOCommitmentHeader, UTXOEncryptedPayload, UTXOEncryptedPayloadV1, VersionedPayload};
+
+fn finalize<D: Digest>(hasher: D) -> HASH {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&hasher.finalize());
+    hash
+}
+
+impl UTXOEncryptedPayload{
+    // Domain tag of the payload commitment, keeps it apart from every other qcash hash
+    pub const COMMITMENT_DOMAIN:&'static [u8] = b"qcash:payload";
+
+    // The ciphertext_commitment of a UTXO
+    // H("qcash:payload" || version || amount (u64 LE) || is_return (1 byte) || receiver_vault || randomness || history_root)
+    // Every field is fixed size, so no two payloads share an encoding
+    pub fn commitment<D: Digest>(&self)->HASH{
+        let mut hasher = D::new();
+        hasher.update(Self::COMMITMENT_DOMAIN);
+        hasher.update([self.version]);
+        hasher.update(self.amount.to_le_bytes());
+        hasher.update([self.is_return as u8]);
+        hasher.update(self.receiver_vault);
+        hasher.update(self.randomness);
+        hasher.update(self.history_root);
+        finalize(hasher)
+    }
+}
+
+impl UTXOEncryptedPayloadV1{
+    // The ciphertext_commitment version 1 UTXOs were created with
+    // H(amount (u64 LE) || is_return (1 byte) || receiver_vault || randomness || utxo_spent_list)
+    pub fn commitment<D: Digest>(&self)->HASH{
+        let mut hasher = D::new();
+        hasher.update(self.amount.to_le_bytes());
+        hasher.update([self.is_return as u8]);
+        hasher.update(self.receiver_vault);
+        hasher.update(self.randomness);
+        for utxo_hash in &self.utxo_spent_list {
+            hasher.update(utxo_hash);
+        }
+        finalize(hasher)
+    }
+
+    // The spent list folded into a history root, as if each entry was an input with no history
+    pub fn history_root<D: Digest>(&self)->HASH{
+        self.utxo_spent_list
+            .iter()
+            .fold([0u8; HASH_SIZE], |acc, utxo_hash| fold_history::<D>(&acc, &[0u8; HASH_SIZE], utxo_hash))
+    }
+
+    // The current layout of this payload, version stays 1 so it is still committed as one
+    pub fn to_current<D: Digest>(&self)->UTXOEncryptedPayload{
+        UTXOEncryptedPayload{
+            amount: self.amount,
+            is_return: self.is_return,
+            receiver_vault: self.receiver_vault,
+            randomness: self.randomness,
+            history_root: self.history_root::<D>(),
+            version: Self::VERSION,
+        }
+    }
+}
+
+impl VersionedPayload{
+    // The payload in the current layout, a version 1 spent list is folded into its history root
+    pub fn to_current<D: Digest>(&self)->UTXOEncryptedPayload{
+        match self {
+            Self::V1(payload) => payload.to_current::<D>(),
+            Self::V2(payload) => payload.clone(),
+        }
+    }
+}
+
+impl UTXOCommitmentHeader{
+    // utxo_hash of a header: H(ciphertext_commitment || prev_utxo_hash || epoch (u32 LE))
+    pub fn compute_hash<D: Digest>(ciphertext_commitment:&HASH, prev_utxo_hash:&HASH, epoch:u32)->HASH{
+        let mut hasher = D::new();
+        hasher.update(ciphertext_commitment);
+        hasher.update(prev_utxo_hash);
+        hasher.update(epoch.to_le_bytes());
+        finalize(hasher)
+    }
+
+    // Header of a new output linking to prev_utxo_hash
+    // The ciphertext fields stay empty, they are only known once the output is encrypted
+    pub fn compute<D: Digest>(payload:&UTXOEncryptedPayload, prev_utxo_hash:HASH, epoch:u32)->Self{
+        let ciphertext_commitment = payload.commitment::<D>();
+        let utxo_hash = Self::compute_hash::<D>(&ciphertext_commitment, &prev_utxo_hash, epoch);
+
+        Self{
+            utxo_hash,
+            prev_utxo_hash,
+            ciphertext_commitment,
+            epoch,
+            kyber_ciphertext: [0u8; KYBER_CIPHERTEXT_SIZE],
+            nonce: [0u8; 12],
+            encrypted_payload: Vec::new(),
+        }
+    }
+}
+
+impl EncryptedOutput{
+    // Hash(kyber_ciphertext || nonce || encrypted_payload), the output_hash of its UTXO account
+    // The guest commits it, provers compare it with the hash the program stored
+    pub fn hash<D: Digest>(&self)->HASH{
+        let mut hasher = D::new();
+        hasher.update(self.kyber_ciphertext);
+        hasher.update(self.nonce);
+        hasher.update(&self.encrypted_payload);
+        finalize(hasher)
+    }
+}
+
+// Folds one spent input into the history root of the outputs spending it
+// H("qcash:history" || acc || input history_root || input utxo_hash), starting from zeros,
+// once per input in input order
+pub fn fold_history<D: Digest>(acc:&HASH, input_history_root:&HASH, input_utxo_hash:&HASH)->HASH{
+    let mut hasher = D::new();
+    hasher.update(b"qcash:history");
+    hasher.update(acc);
+    hasher.update(input_history_root);
+    hasher.update(input_utxo_hash);
+    finalize(hasher)
+}
+
+// Why a header doesn't belong to a payload
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum HeaderError{
+    // ciphertext_commitment is not the payload's commitment
+    CommitmentMismatch,
+    // utxo_hash doesn't hash the header's own fields
+    HashMismatch,
+    // The payload version has no commitment encoding, or a version 1 input lacks its spent list
+    UnsupportedVersion(u8),
+    // history_root of a version 1 input is not the fold of its spent list
+    HistoryMismatch,
+}
+
+impl fmt::Display for HeaderError{
+    fn fmt(&self, f:&mut fmt::Formatter<'_>)->fmt::Result{
+        match self {
+            Self::CommitmentMismatch => write!(f, "Payload does not match Commitment Header"),
+            Self::HashMismatch => write!(f, "UTXO hash does not match its header"),
+            Self::UnsupportedVersion(version) => write!(f, "Payload version {} can not be verified", version),
+            Self::HistoryMismatch => write!(f, "History root does not match the spent list"),
+        }
+    }
+}
+
+// Checks a header commits to the payload and its utxo_hash covers the header fields
+pub fn verify_header<D: Digest>(header:&UTXOCommitmentHeader, payload:&UTXOEncryptedPayload)->Result<(),HeaderError>{
+    verify_commitment::<D>(header, &payload.commitment::<D>())
+}
+
+impl DecryptedInput{
+    // Checks the header against the payload under the encoding its version was created with
+    // A version 1 payload is committed over its spent list, its history_root must be the fold of that list
+    pub fn verify<D: Digest>(&self)->Result<(),HeaderError>{
+        match (self.payload.version, &self.utxo_spent_list) {
+            (PAYLOAD_VERSION, None) => verify_header::<D>(&self.header, &self.payload),
+            (UTXOEncryptedPayloadV1::VERSION, Some(utxo_spent_list)) => {
+                let legacy = UTXOEncryptedPayloadV1{
+                    amount: self.payload.amount,
+                    is_return: self.payload.is_return,
+                    receiver_vault: self.payload.receiver_vault,
+                    randomness: self.payload.randomness,
+                    utxo_spent_list: utxo_spent_list.clone(),
+                    version: UTXOEncryptedPayloadV1::VERSION,
+                };
+                if legacy.history_root::<D>() != self.payload.history_root {
+                    return Err(HeaderError::HistoryMismatch);
+                }
+                verify_commitment::<D>(&self.header, &legacy.commitment::<D>())
+            }
+            (version, _) => Err(HeaderError::UnsupportedVersion(version)),
+        }
+    }
+}
+
+fn verify_commitment<D: Digest>(header:&UTXOCommitmentHeader, ciphertext_commitment:&HASH)->Result<(),HeaderError>{
+    if *ciphertext_commitment != header.ciphertext_commitment {
+        return Err(HeaderError::CommitmentMismatch);
+    }
+
+    let utxo_hash = UTXOCommitmentHeader::compute_hash::<D>(&header.ciphertext_commitment, &header.prev_utxo_hash, header.epoch);
+    if utxo_hash != header.utxo_hash {
+        return Err(HeaderError::HashMismatch);
+    }
+
+    Ok(())
+}
@@ -5,9 +5,17 @@
 // Change License: Apache License 2.0
 // Use of this software is governed by the LICENSE file.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
 use bip39::rand;
+#[cfg(feature = "std")]
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce, aead::Aead};
 use hmac::{Hmac, Mac};
-use pqc_kyber::{CryptoRng, Keypair, RngCore, keypair};
+use pqc_kyber::{CryptoRng, Keypair, RngCore, encapsulate, keypair};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 
@@ -16,11 +24,18 @@ pub const KYBER_CIPHERTEXT_SIZE:usize = 1088;
 pub const HASH_SIZE:usize = 32;
 
 pub type HASH = [u8;HASH_SIZE];
+
+// Version new payloads are written with
+// Version 2 replaced the ever growing utxo_spent_list with the constant size history_root
+pub const PAYLOAD_VERSION:u8 = 2;
 pub type KyberPubKey = [u8;KYBER_PUBKEY_SIZE];
 
 #[cfg(feature = "wallet")]
 pub mod wallet;
 
+pub mod commitment;
+pub use commitment::*;
+
 #[derive(Clone,Debug,Serialize,Deserialize,PartialEq)]
 pub struct UTXOCommitmentHeader{
     pub utxo_hash:HASH,
@@ -40,16 +55,75 @@ pub struct UTXOEncryptedPayload{
     #[serde(with = "serde_arrays")]
     pub receiver_vault: [u8;32],
     pub randomness:[u8;32],
+    // Rolling hash over the spent inputs and their own history (see fold_history), zeros for airdrops
+    // Stays 32 bytes however long the history gets, double spends are stopped by the nullifiers
+    pub history_root:HASH,
+    pub version:u8,
+}
+
+// Payload layout of version 1, UTXOs created before history_root are still on chain with it
+#[derive(Clone,Debug,Serialize,Deserialize,PartialEq)]
+pub struct UTXOEncryptedPayloadV1{
+    pub amount:u64,
+    pub is_return:bool,
+    #[serde(with = "serde_arrays")]
+    pub receiver_vault: [u8;32],
+    pub randomness:[u8;32],
+    // Every UTXO this one descends from, including the inputs that created it
     pub utxo_spent_list:Vec<HASH>,
     pub version:u8,
 }
 
+impl UTXOEncryptedPayloadV1{
+    pub const VERSION:u8 = 1;
+}
+
+// A decrypted payload in the layout it was written with
+#[derive(Clone,Debug,PartialEq)]
+pub enum VersionedPayload{
+    V1(UTXOEncryptedPayloadV1),
+    V2(UTXOEncryptedPayload),
+}
+
+impl VersionedPayload{
+    // Decodes bincode payload bytes, version is the last field of every layout so its byte picks the layout
+    #[cfg(feature = "std")]
+    pub fn decode(bytes:&[u8])->Result<Self,&'static str>{
+        let decoded = match bytes.last() {
+            Some(&UTXOEncryptedPayloadV1::VERSION) => bincode::deserialize(bytes).map(Self::V1),
+            Some(&PAYLOAD_VERSION) => bincode::deserialize(bytes).map(Self::V2),
+            _ => return Err("Unknown Payload Version"),
+        };
+        decoded.map_err(|_| "Deserialization Failed")
+    }
+
+    pub fn version(&self)->u8{
+        match self {
+            Self::V1(payload) => payload.version,
+            Self::V2(payload) => payload.version,
+        }
+    }
+}
+
+// Secret material that proves the sender actually holds the Kyber key behind sender_kyber_pubkey
+#[derive(Clone,Debug,Serialize,Deserialize)]
+pub enum SenderKeyWitness{
+    // The secret_entropy and kyber_version from WalletKeys, the guest re-derives the keypair with derive_kyber_key
+    // There is no secret key mode: a Kyber secret key carries its implicit-rejection value z, which anyone can
+    // set so decapsulation returns the secret they expect, so a round trip through the KEM proves nothing
+    SecretEntropy([u8;32], RngVersion),
+}
+
 #[derive(Clone,Debug,Serialize,Deserialize)]
 pub struct QSPVGuestInput{
     // Pass the pre-derived Kyber public key instead of seed
     // This avoids expensive key derivation inside the ZK circuit
     #[serde(with = "serde_arrays")]
     pub sender_kyber_pubkey: KyberPubKey,
+    // Proof of knowledge of the secret key behind sender_kyber_pubkey
+    // None still proves, but provers reject the receipt since anyone knowing the public key could build it
+    #[serde(default)]
+    pub sender_key_witness: Option<SenderKeyWitness>,
     // The list of all the notes we are spending
     pub input_utxos:Vec<DecryptedInput>, // must be ordered oldest to newest
     // We use serde_arrays because default serde struggles with array > 32 bytes
@@ -60,8 +134,22 @@ pub struct QSPVGuestInput{
     // The guest uses this to verify the encyrption matches.
     pub receiver_randomness:[u8;32],
     pub return_randomness:[u8;32],
-    // The latest hash on chain
+    // Seeds and nonces WASM used to encrypt the outputs.
+    // The guest re-runs the encryption with them and commits a hash of the result.
+    pub receiver_encapsulation_seed:[u8;32],
+    pub receiver_nonce:[u8;12],
+    pub return_encapsulation_seed:[u8;32],
+    pub return_nonce:[u8;12],
+    // A recent ledger tip, the receiver output links to it
     pub current_ledger_tip:HASH,
+    // Ledger epoch the outputs are created in, committed in both output headers
+    // The program refuses to finalize outputs once their epoch has expired
+    pub epoch:u32,
+    // Ledger tip right before the oldest input was finalized (ledger_prev_tip on its account)
+    pub ledger_path_start:HASH,
+    // Every finalized UTXO after the oldest input up to current_ledger_tip, ordered oldest to newest
+    // The guest re-hashes the running ledger tip over them to prove each input is on the ledger
+    pub ledger_path:Vec<LedgerLink>,
     pub solana_program_id:[u8;32],
     pub vault_bump:u8,
     pub receiver_vault:[u8;32],
@@ -72,8 +160,18 @@ pub struct QSPVGuestInput{
 pub struct DecryptedInput{
     // The public ledger data 
     pub header:UTXOCommitmentHeader,
-    // The Private Decrypted data
+    // The Private Decrypted data, a version 1 payload in its current layout (see VersionedPayload::to_current)
     pub payload:UTXOEncryptedPayload,
+    // The spent list of a version 1 payload, its commitment was hashed over it
+    #[serde(default)]
+    pub utxo_spent_list:Option<Vec<HASH>>,
+}
+
+// One finalized UTXO on the ledger, in the order the program sequenced it
+// The ledger tip after it is SHA256(previous tip || utxo_hash)
+#[derive(Clone,Debug,Serialize,Deserialize)]
+pub struct LedgerLink{
+    pub utxo_hash:HASH,
 }
 
 // Prover Output
@@ -81,36 +179,150 @@ pub struct DecryptedInput{
 pub struct QspvGuestOutput{
     pub receiver_commitment: UTXOCommitmentHeader,
     pub return_commitment: UTXOCommitmentHeader,
-    // output the encrypted payload also
+    // Hash(kyber_ciphertext || nonce || encrypted_payload) of each output as posted on chain
+    pub receiver_output_hash: HASH,
+    pub return_output_hash: HASH,
+    // true when the guest checked a SenderKeyWitness against the sender's public key
+    // Provers vote against receipts where it is false
+    pub sender_key_proven: bool,
+    // One nullifier per spent input, the program refuses to finalize if any is already on chain
+    pub nullifiers: Vec<HASH>,
 }
 
-// This will store a derived secret as the randomness for kyber
+// An output payload encrypted for its receiver, exactly as it is posted on chain
+#[derive(Clone,Debug,PartialEq)]
+pub struct EncryptedOutput{
+    pub kyber_ciphertext:[u8;KYBER_CIPHERTEXT_SIZE],
+    pub nonce:[u8;12],
+    pub encrypted_payload:Vec<u8>,
+}
+
+// Encrypts a payload for the receiver: Kyber encapsulation then ChaCha20-Poly1305.
+// Fully deterministic in (encapsulation_seed, nonce) so the guest can reproduce
+// the exact ciphertext WASM posts on chain.
+#[cfg(feature = "std")]
+pub fn encrypt_output(
+    receiver_pubkey:&KyberPubKey,
+    payload:&UTXOEncryptedPayload,
+    encapsulation_seed:&[u8;32],
+    nonce:&[u8;12],
+)->Result<EncryptedOutput,&'static str>{
+    let mut rng = DeterministicRng::new(encapsulation_seed, RngVersion::CURRENT);
+    let (kyber_ciphertext, shared_secret) = encapsulate(receiver_pubkey, &mut rng)
+        .map_err(|_| "Encapsulation Failed")?;
+
+    let payload_bytes = bincode::serialize(payload).map_err(|_| "Serialization Failed")?;
+
+    let cipher = <ChaCha20Poly1305 as chacha20poly1305::KeyInit>::new(Key::from_slice(&shared_secret));
+    let encrypted_payload = cipher
+        .encrypt(Nonce::from_slice(nonce), payload_bytes.as_ref())
+        .map_err(|_| "Symmetric Encryption Failed")?;
+
+    Ok(EncryptedOutput {
+        kyber_ciphertext,
+        nonce: *nonce,
+        encrypted_payload,
+    })
+}
+
+// Keystream layout of a DeterministicRng
+// Every Kyber key and ciphertext derived from a seed depends on it, so old layouts stay derivable
+#[repr(u8)]
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Serialize,Deserialize)]
+pub enum RngVersion{
+    // 8 blocks of HMAC-SHA256(seed, i), 256 bytes in total
+    // Only kept so wallets created with it re-derive the same Kyber keys
+    V1 = 1,
+    // HKDF-SHA256 (RFC 5869) keyed from the seed, Expand run in counter mode
+    V2 = 2,
+}
+
+impl RngVersion{
+    // Version new wallets and outputs are derived with
+    pub const CURRENT:Self = Self::V2;
+
+    // Bytes a DeterministicRng of this version can produce before it is exhausted
+    pub const fn max_bytes(self)->usize{
+        match self {
+            Self::V1 => 8 * HASH_SIZE,
+            Self::V2 => 255 * HASH_SIZE,
+        }
+    }
+}
+
+impl TryFrom<u8> for RngVersion{
+    type Error = &'static str;
+
+    fn try_from(version:u8)->Result<Self,Self::Error>{
+        match version {
+            1 => Ok(Self::V1),
+            2 => Ok(Self::V2),
+            _ => Err("Unknown RNG Version"),
+        }
+    }
+}
+
+// Deterministic randomness for Kyber, expanded from a derived secret
+// Once max_bytes are drawn it fails instead of repeating the keystream
 pub struct DeterministicRng{
-    seed_bytes:Vec<u8>,
-    cursor:usize,
+    version:RngVersion,
+    // V1: HMAC keyed with the seed, V2: HMAC keyed with the HKDF pseudorandom key
+    mac:Hmac<Sha256>,
+    block:HASH,
+    // Next unread byte of block
+    offset:usize,
+    // Blocks produced so far
+    counter:u8,
 }
 
 impl DeterministicRng{
-    pub fn new(seed:&[u8])->Self{
-        // Takes the input seed (32 bytes), expand it using HMAC-SHA256
-        // to create a long stream of bytes (256 bytes) and store it in the seed_bytes
-        let mut seed_bytes = Vec::new();
-
-        // kyber needs ~64 bytes.We generate 256 bytes(8 Block * 32 bytes) to be absolutely future proof
+    // HKDF salt and info of V2, they keep this keystream apart from any other use of the seed
+    pub const HKDF_SALT:&'static [u8] = b"qcash:drng";
+    pub const HKDF_INFO:&'static [u8] = b"qcash:drng:v2";
 
-        for i in 0..8{
+    pub fn new(seed:&[u8], version:RngVersion)->Self{
+        let mac = match version {
+            RngVersion::V1 => Hmac::<Sha256>::new_from_slice(seed).expect("HMAC should accept any key length"),
+            RngVersion::V2 => {
+                // HKDF-Extract: PRK = HMAC(salt, seed)
+                let mut extract = Hmac::<Sha256>::new_from_slice(Self::HKDF_SALT).expect("HMAC should accept any key length");
+                extract.update(seed);
+                let prk = extract.finalize().into_bytes();
+                Hmac::<Sha256>::new_from_slice(&prk).expect("HMAC should accept any key length")
+            }
+        };
 
-            // reinitialize the HMAC
-            let mut mac  = Hmac::<Sha256>::new_from_slice(&seed).expect("HMAC should accept any key length");
+        // Starts with an empty block, the first read produces block 1
+        Self { version, mac, block: [0u8; HASH_SIZE], offset: HASH_SIZE, counter: 0 }
+    }
 
-            mac.update(&[i as u8]);
+    pub fn version(&self)->RngVersion{
+        self.version
+    }
 
-            // Finalize produces the hash and RESET the mac to the initial state(key loaded)
-            let result = mac.finalize().into_bytes();
+    // Bytes left before the keystream is exhausted
+    pub fn remaining(&self)->usize{
+        self.version.max_bytes() - self.counter as usize * HASH_SIZE + (HASH_SIZE - self.offset)
+    }
 
-           seed_bytes.extend_from_slice(&result);
+    fn next_block(&mut self){
+        let mut mac = self.mac.clone();
+        match self.version {
+            // Block i = HMAC(seed, i), from 0
+            RngVersion::V1 => mac.update(&[self.counter]),
+            // T(i) = HMAC(PRK, T(i-1) || info || i), from 1 with T(0) empty
+            RngVersion::V2 => {
+                if self.counter > 0 {
+                    mac.update(&self.block);
+                }
+                mac.update(Self::HKDF_INFO);
+                mac.update(&[self.counter + 1]);
+            }
         }
-        Self { seed_bytes, cursor: 0 }
+
+        self.block.copy_from_slice(&mac.finalize().into_bytes());
+        self.offset = 0;
+        self.counter += 1;
     }
 }
 
@@ -129,29 +341,35 @@ impl RngCore for DeterministicRng{
         u64::from_le_bytes(buf)
     }
 
-    // copy bytes form from self.seed_bytes to dst
-    // we uses a ring buffer
+    // RngCore has no way to report an error here, callers that can handle one use try_fill_bytes
     fn fill_bytes(&mut self, dst: &mut [u8]) {
-       if self.seed_bytes.is_empty() {
-            panic!("DeterministicRng not initialized with seed bytes");
-       }
-
-       for i in 0..dst.len(){
-            let idx = (self.cursor) % self.seed_bytes.len();
-            dst[i] = self.seed_bytes[idx];
-
-            self.cursor +=1;
-
-       }
+        self.try_fill_bytes(dst).expect("DeterministicRng exhausted");
     }
 
+    // Fails without consuming anything when dest is longer than what is left
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
-        self.fill_bytes(dest);
+        if dest.len() > self.remaining() {
+            let code = core::num::NonZeroU32::new(rand::Error::CUSTOM_START).expect("non zero");
+            return Err(rand::Error::from(code));
+        }
+
+        let mut written = 0;
+        while written < dest.len() {
+            if self.offset == HASH_SIZE {
+                self.next_block();
+            }
+
+            let n = (dest.len() - written).min(HASH_SIZE - self.offset);
+            dest[written..written + n].copy_from_slice(&self.block[self.offset..self.offset + n]);
+            self.offset += n;
+            written += n;
+        }
         Ok(())
     }
 }
 
-pub fn derive_kyber_key(fragment: &[u8; 32]) -> Keypair {
-    let mut rng = DeterministicRng::new(fragment);
+// Kyber keypair of a wallet, version is the RngVersion the wallet was created with
+pub fn derive_kyber_key(fragment: &[u8; 32], version: RngVersion) -> Keypair {
+    let mut rng = DeterministicRng::new(fragment, version);
     keypair(&mut rng).expect("Kyber Keygen Failed")
 }
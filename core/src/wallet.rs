@@ -11,12 +11,14 @@ use ed25519_dalek_bip32::{ChildIndex, ExtendedSigningKey};
 use hmac::{Hmac, Mac};
 use pqc_kyber::{CryptoRng, Keypair, RngCore};
 use sha2::{Sha256};
-use crate::DeterministicRng;
+use crate::{DeterministicRng, RngVersion};
 
 pub struct WalletKeys{
     pub solana_key:SigningKey,
     pub kyber_key:Keypair,
     pub secret_entropy:[u8;32],
+    // RngVersion the Kyber key was expanded with, needed to re-derive it from secret_entropy
+    pub kyber_version:RngVersion,
     pub mnemonic:String,
 }
 
@@ -24,10 +26,17 @@ impl WalletKeys{
     pub fn new()->Self{
         let mnemonic = Mnemonic::generate_in(bip39::Language::English, 12).unwrap();
 
-        Self::from_mnemonic(mnemonic.to_string().as_str())
+        Self::from_mnemonic_with_version(mnemonic.to_string().as_str(), RngVersion::CURRENT)
     }
 
+    // Restores a wallet from its mnemonic alone, which doesn't say the RngVersion its Kyber key
+    // was derived with. Wallets created before V2 all used V1, so restores default to it
     pub fn from_mnemonic(phrase:&str)->Self{
+        Self::from_mnemonic_with_version(phrase, RngVersion::V1)
+    }
+
+    // Restores a wallet whose Kyber key was derived with a known RngVersion
+    pub fn from_mnemonic_with_version(phrase:&str, kyber_version:RngVersion)->Self{
         // Step A): Get Seed from Mnemonic
         let mnemonic = Mnemonic::parse(phrase).expect("Invalid Mnemonic");
         // Giving empty passphrase for now
@@ -57,7 +66,7 @@ impl WalletKeys{
         let vault_extended = root.derive(&vault_path).expect("Vault Derivation failed");
 
         let kyber_entropy = vault_extended.signing_key.to_bytes();
-        let mut drng = DeterministicRng::new(&kyber_entropy);
+        let mut drng = DeterministicRng::new(&kyber_entropy, kyber_version);
 
         let kyber_key = Keypair::generate(&mut drng).expect("kyber keygen failed");
 
@@ -65,6 +74,7 @@ impl WalletKeys{
             solana_key,
             kyber_key,
             secret_entropy: kyber_entropy,
+            kyber_version,
             mnemonic: phrase.to_string()
         }
     }
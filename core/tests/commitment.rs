@@ -0,0 +1,108 @@
+// Business Source License 1.1 (BSL 1.1)
+// Licensor: Bonsol Labs Inc.
+// Licensed Work: QCash
+// Change Date: 2030-12-31
+// Change License: Apache License 2.0
+// Use of this software is governed by the LICENSE file.
+
+// Test vectors for UTXOEncryptedPayload::commitment
+// Any client re-implementing the commitment (TypeScript, another prover) must reproduce these
+
+use qcash_core::{fold_history, HASH, PAYLOAD_VERSION, UTXOEncryptedPayload, UTXOEncryptedPayloadV1, VersionedPayload};
+use sha2::Sha256;
+
+fn payload(version: u8, amount: u64, is_return: bool, fill: [u8; 3]) -> UTXOEncryptedPayload {
+    UTXOEncryptedPayload {
+        amount,
+        is_return,
+        receiver_vault: [fill[0]; 32],
+        randomness: [fill[1]; 32],
+        history_root: [fill[2]; 32],
+        version,
+    }
+}
+
+fn hex(hash: &HASH) -> String {
+    hash.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn commitment_vectors() {
+    let vectors = [
+        (
+            payload(2, 0, false, [0, 0, 0]),
+            "ef6b7261b7b7f83a299e63753e8908632626cda74bd592601674f881ddac9a9e",
+        ),
+        (
+            payload(2, 100, true, [1, 2, 3]),
+            "6230c11b721f38fe2532b6f9b650c9c7d19a61a9a51f5fa3737a28a070d95146",
+        ),
+        (
+            payload(3, 100, true, [1, 2, 3]),
+            "664883fba1c5ca32ee8b2e0bbbbcb34015efb2788d3d4ba5c85812a9a81262ca",
+        ),
+    ];
+
+    for (payload, expected) in vectors {
+        assert_eq!(hex(&payload.commitment::<Sha256>()), expected, "{:?}", payload);
+    }
+}
+
+#[test]
+fn commitment_binds_every_field() {
+    let base = payload(PAYLOAD_VERSION, 100, true, [1, 2, 3]);
+    let variants = [
+        UTXOEncryptedPayload { version: PAYLOAD_VERSION + 1, ..base.clone() },
+        UTXOEncryptedPayload { amount: 101, ..base.clone() },
+        UTXOEncryptedPayload { is_return: false, ..base.clone() },
+        UTXOEncryptedPayload { receiver_vault: [9; 32], ..base.clone() },
+        UTXOEncryptedPayload { randomness: [9; 32], ..base.clone() },
+        UTXOEncryptedPayload { history_root: [9; 32], ..base.clone() },
+    ];
+
+    for variant in variants {
+        assert_ne!(variant.commitment::<Sha256>(), base.commitment::<Sha256>(), "{:?}", variant);
+    }
+}
+
+fn payload_v1(spent: Vec<HASH>) -> UTXOEncryptedPayloadV1 {
+    UTXOEncryptedPayloadV1 {
+        amount: 100,
+        is_return: true,
+        receiver_vault: [1; 32],
+        randomness: [2; 32],
+        utxo_spent_list: spent,
+        version: UTXOEncryptedPayloadV1::VERSION,
+    }
+}
+
+#[test]
+fn decode_picks_the_written_layout() {
+    for spent in [vec![], vec![[7; 32]], vec![[7; 32], [8; 32], [9; 32]]] {
+        let v1 = payload_v1(spent);
+        let bytes = bincode::serialize(&v1).unwrap();
+        assert_eq!(VersionedPayload::decode(&bytes), Ok(VersionedPayload::V1(v1)));
+    }
+
+    let v2 = payload(PAYLOAD_VERSION, 100, true, [1, 2, 3]);
+    let bytes = bincode::serialize(&v2).unwrap();
+    assert_eq!(VersionedPayload::decode(&bytes), Ok(VersionedPayload::V2(v2)));
+
+    let unknown = bincode::serialize(&payload(PAYLOAD_VERSION + 1, 100, true, [1, 2, 3])).unwrap();
+    assert!(VersionedPayload::decode(&unknown).is_err());
+    assert!(VersionedPayload::decode(&[]).is_err());
+}
+
+#[test]
+fn v1_spent_list_folds_into_history_root() {
+    let spent = vec![[7; 32], [8; 32]];
+    let expected = fold_history::<Sha256>(&fold_history::<Sha256>(&[0; 32], &[0; 32], &spent[0]), &[0; 32], &spent[1]);
+
+    let current = VersionedPayload::V1(payload_v1(spent)).to_current::<Sha256>();
+    assert_eq!(current.history_root, expected);
+    assert_eq!(current.version, UTXOEncryptedPayloadV1::VERSION);
+    assert_eq!((current.amount, current.is_return), (100, true));
+    assert_eq!((current.receiver_vault, current.randomness), ([1; 32], [2; 32]));
+
+    assert_eq!(payload_v1(vec![]).history_root::<Sha256>(), [0; 32]);
+}
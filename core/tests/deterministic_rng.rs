@@ -0,0 +1,182 @@
+// Business Source License 1.1 (BSL 1.1)
+// Licensor: Bonsol Labs Inc.
+// Licensed Work: QCash
+// Change Date: 2030-12-31
+// Change License: Apache License 2.0
+// Use of this software is governed by the LICENSE file.
+
+// DeterministicRng keystreams: V1 must keep reproducing the keys of existing wallets,
+// V2 must be plain HKDF-SHA256 and neither may ever repeat itself
+
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use pqc_kyber::{keypair, RngCore};
+use qcash_core::{derive_kyber_key, DeterministicRng, RngVersion};
+use sha2::{Digest, Sha256};
+
+const SEED: [u8; 32] = [42u8; 32];
+
+// The original ring buffer: HMAC-SHA256(seed, i) for i in 0..8
+fn v1_reference(seed: &[u8]) -> Vec<u8> {
+    (0..8u8)
+        .flat_map(|i| {
+            let mut mac = Hmac::<Sha256>::new_from_slice(seed).unwrap();
+            mac.update(&[i]);
+            mac.finalize().into_bytes().to_vec()
+        })
+        .collect()
+}
+
+fn v2_reference(seed: &[u8], len: usize) -> Vec<u8> {
+    let mut okm = vec![0u8; len];
+    Hkdf::<Sha256>::new(Some(DeterministicRng::HKDF_SALT), seed)
+        .expand(DeterministicRng::HKDF_INFO, &mut okm)
+        .unwrap();
+    okm
+}
+
+// Reads len bytes in uneven chunks, so block boundaries fall inside reads
+fn read(rng: &mut DeterministicRng, len: usize) -> Vec<u8> {
+    let mut out = vec![0u8; len];
+    let mut written = 0;
+    for chunk in [1, 7, 32, 33, 64].iter().cycle() {
+        if written == len {
+            break;
+        }
+        let n = (*chunk).min(len - written);
+        rng.try_fill_bytes(&mut out[written..written + n]).unwrap();
+        written += n;
+    }
+    out
+}
+
+#[test]
+fn v1_matches_the_original_keystream() {
+    let max = RngVersion::V1.max_bytes();
+    let mut rng = DeterministicRng::new(&SEED, RngVersion::V1);
+    assert_eq!(read(&mut rng, max), v1_reference(&SEED));
+}
+
+// SHA-256 of the Kyber keypair the original DeterministicRng derived from SEED
+const V1_PUBLIC_KEY_SHA256: &str = "0f6fdfd9cb57ecbaaf8d42ec69f069823e2f06da5f2b9db9fc3563e53ecfbc6f";
+const V1_SECRET_KEY_SHA256: &str = "ff46cd80e7c7a10391fa157fdb046298ae374d3c2caec9043c69c5a377f96774";
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn v1_derives_existing_wallet_keys() {
+    let derived = derive_kyber_key(&SEED, RngVersion::V1);
+    assert_eq!(sha256_hex(&derived.public), V1_PUBLIC_KEY_SHA256);
+    assert_eq!(sha256_hex(&derived.secret), V1_SECRET_KEY_SHA256);
+
+    // Kyber keygen fed the reference keystream directly lands on the same keys
+    let mut stream = v1_reference(&SEED).into_iter();
+    let expected = keypair(&mut ReplayRng(&mut stream)).unwrap();
+    assert_eq!(derived.public, expected.public);
+    assert_eq!(derived.secret, expected.secret);
+}
+
+// A mnemonic doesn't record its RngVersion, restoring one must land on the keys of the
+// wallets that existed before V2
+#[cfg(feature = "wallet")]
+#[test]
+fn restores_default_to_v1() {
+    use qcash_core::wallet::WalletKeys;
+
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let restored = WalletKeys::from_mnemonic(phrase);
+    let v1 = WalletKeys::from_mnemonic_with_version(phrase, RngVersion::V1);
+    let v2 = WalletKeys::from_mnemonic_with_version(phrase, RngVersion::V2);
+
+    assert_eq!(restored.kyber_version, RngVersion::V1);
+    assert_eq!(restored.kyber_key.public, v1.kyber_key.public);
+    assert_ne!(restored.kyber_key.public, v2.kyber_key.public);
+    assert_eq!(WalletKeys::new().kyber_version, RngVersion::CURRENT);
+}
+
+#[test]
+fn v2_is_hkdf_expand() {
+    let max = RngVersion::V2.max_bytes();
+    let mut rng = DeterministicRng::new(&SEED, RngVersion::V2);
+    assert_eq!(read(&mut rng, max), v2_reference(&SEED, max));
+}
+
+#[test]
+fn v2_does_not_repeat() {
+    let mut rng = DeterministicRng::new(&SEED, RngVersion::V2);
+    let stream = read(&mut rng, RngVersion::V2.max_bytes());
+
+    let blocks: Vec<&[u8]> = stream.chunks(32).collect();
+    for (i, block) in blocks.iter().enumerate() {
+        assert!(!blocks[i + 1..].contains(block), "block {} repeats", i);
+    }
+}
+
+#[test]
+fn versions_are_independent() {
+    let mut v1 = DeterministicRng::new(&SEED, RngVersion::V1);
+    let mut v2 = DeterministicRng::new(&SEED, RngVersion::V2);
+    assert_ne!(read(&mut v1, 64), read(&mut v2, 64));
+    assert_ne!(
+        derive_kyber_key(&SEED, RngVersion::V1).public,
+        derive_kyber_key(&SEED, RngVersion::V2).public
+    );
+}
+
+#[test]
+fn exhaustion_is_an_error() {
+    for version in [RngVersion::V1, RngVersion::V2] {
+        let max = version.max_bytes();
+        let mut rng = DeterministicRng::new(&SEED, version);
+        let head = read(&mut rng, max - 5);
+
+        // Asking for more than is left fails and consumes nothing
+        let mut buf = [0u8; 6];
+        assert!(rng.try_fill_bytes(&mut buf).is_err());
+        assert_eq!(rng.remaining(), 5);
+
+        rng.try_fill_bytes(&mut buf[..5]).unwrap();
+        assert_ne!(&buf[..5], &head[..5]);
+        assert_eq!(rng.remaining(), 0);
+        assert!(rng.try_fill_bytes(&mut buf[..1]).is_err());
+    }
+}
+
+#[test]
+fn version_round_trips_through_u8() {
+    for version in [RngVersion::V1, RngVersion::V2] {
+        assert_eq!(RngVersion::try_from(version as u8), Ok(version));
+    }
+    assert!(RngVersion::try_from(0).is_err());
+    assert!(RngVersion::try_from(3).is_err());
+}
+
+// Feeds a fixed byte stream to Kyber keygen
+struct ReplayRng<'a>(&'a mut std::vec::IntoIter<u8>);
+
+impl pqc_kyber::CryptoRng for ReplayRng<'_> {}
+
+impl RngCore for ReplayRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for byte in dest {
+            *byte = self.0.next().expect("reference stream exhausted");
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), bip39::rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
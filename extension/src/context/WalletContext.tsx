@@ -6,6 +6,7 @@ export interface StoredWallet{
     kyber_pubkey: string; // base58
     kyber_secret_key: number[];  // For background sync
     secret_entropy_hex: string;
+    kyber_version?: number; // RngVersion of the Kyber key, missing on wallets created before versioning (1)
 }
 
 interface WalletContextType{
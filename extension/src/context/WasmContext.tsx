@@ -4,7 +4,7 @@ import init, { generate_wallet, get_solana_secret, init_panic_hook, restore_wall
 interface WasmContextType {
     isReady: boolean;
     createIdentity: () => Promise<any>;
-    restoreIdentity: (mnemonic: string) => Promise<any>;
+    restoreIdentity: (mnemonic: string, kyberVersion?: number) => Promise<any>;
     getSolanaSecret: (mnemonic: string) => Uint8Array;
 }
 
@@ -30,8 +30,10 @@ export const WasmProvider = ({ children }: { children: ReactNode }) => {
         return result;
     };
 
-    const restoreIdentity = async (mnemonic: string) => {
-        const result = await restore_wallet(mnemonic);
+    // kyberVersion defaults to 1, the version of wallets created before RngVersion 2; pass the
+    // kyber_version createIdentity returned to restore a wallet created since
+    const restoreIdentity = async (mnemonic: string, kyberVersion?: number) => {
+        const result = await restore_wallet(mnemonic, kyberVersion);
         console.log("WASM restore_wallet result:", result);
         return result;
     };
@@ -10,6 +10,7 @@ export interface CryptoKeys {
   seed: number[];           // Converted from entropy hex
   kyberPublicKey: number[]; // Converted from Base58
   kyberSecretKey: number[]; // Passed through
+  kyberVersion: number;     // RngVersion the Kyber key was derived from the seed with
   vaultPda: string;         // Derived from Kyber Pubkey
 }
 
@@ -52,6 +53,8 @@ export function useKeyManager() {
           seed: Array.from(seedBytes),
           kyberPublicKey: Array.from(kyberPubBytes),
           kyberSecretKey: storedWallet.kyber_secret_key,
+          // Wallets created before versioning have no kyber_version, they used version 1
+          kyberVersion: storedWallet.kyber_version ?? 1,
           vaultPda: vaultPda.toString()
         };
 
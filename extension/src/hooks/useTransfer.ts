@@ -1,4 +1,4 @@
-import { PublicKey, Keypair, Transaction, SystemProgram } from "@solana/web3.js";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
 import { useSolana } from "./useSolana";
 import { useLedgerSync } from "./useLedgerSync";
 import { useCallback, useState } from "react";
@@ -7,6 +7,7 @@ import { useWasm } from "./useWasm";
 import { useWallet } from "../context/WalletContext";
 import * as wasm from '../wasm/qcash_wasm';
 import { Buffer } from "buffer";
+import { currentEpoch, fetchLedgerPath } from "../utils/syncUtils";
 
 const PROGRAM_ID = new PublicKey("QCashfSHwqptwFRSbqjBnxYH7GbDzbAfxVeDGXhL1fv");
 
@@ -100,11 +101,14 @@ export const useTransfer = () => {
         );
         const ledgerAccount = await program.account.ledger.fetch(ledgerPda);
 
-        const tip = new Uint8Array(ledgerAccount.lastValidUtxoHash);
+        // Read together so the path up to count re-hashes to tip
+        const tip = new Uint8Array(ledgerAccount.tipHash);
+        const count = Number(ledgerAccount.count.toString());
 
         return {
             tip,
-            epoch: 0,
+            count,
+            epoch: currentEpoch(Number(ledgerAccount.genesisTimestamp.toString())),
         }
     }
 
@@ -131,11 +135,19 @@ export const useTransfer = () => {
 
             console.log(`Selected ${inputs.length} UTXOs. Total: ${totalAmount}`);
 
-            const { tip: currentTip, epoch: currentEpoch } = await fetchLedgerState();
+            const { tip: currentTip, count: ledgerCount, epoch: currentEpoch } = await fetchLedgerState();
+
+            // Proves to the guest that every input is on the finalized ledger
+            const ledgerPath = await fetchLedgerPath(ledgerCount, new Uint8Array(inputs[0].header.utxoHash));
 
             // Encrypting Payload (WASM)
             setStatus('encrypting');
 
+            // The guest folds each input's history root and hash, in order, into both outputs' history root
+            const spentInputs = new Uint8Array(
+                inputs.flatMap(u => [...u.historyRoot, ...u.header.utxoHash])
+            );
+
             console.log("Receiver Vault PDA:", receiverVault);
             console.log("My Vault PDA:", myVaultPda.toBase58());
 
@@ -145,7 +157,8 @@ export const useTransfer = () => {
                 BigInt(amountToSend),
                 currentTip,
                 currentEpoch,
-                false
+                false,
+                spentInputs
             );
 
             // This hash will links to the receiver UTXO
@@ -158,7 +171,8 @@ export const useTransfer = () => {
                 BigInt(returnAmount),
                 prevHash,
                 currentEpoch,
-                true
+                true,
+                spentInputs
             )
 
             setStatus("proving");
@@ -168,6 +182,9 @@ export const useTransfer = () => {
             const proofInputs = {
                 // Pass pre-derived Kyber pubkey to avoid expensive key derivation in ZK circuit
                 sender_kyber_pubkey: Array.from(keys.kyberPublicKey),
+                // Proves we hold the secret key, not just the pubkey: the guest re-derives the key from the seed
+                // The daemon runs locally so the seed never leaves the machine
+                sender_key_witness: { SecretEntropy: [Array.from(keys.seed), keys.kyberVersion === 1 ? "V1" : "V2"] },
                 input_utxos: inputs.map(u => ({
                     header: {
                         utxo_hash: u.header.utxoHash,
@@ -183,12 +200,13 @@ export const useTransfer = () => {
                         is_return: u.isReturn,
                         receiver_vault: Array.from(new PublicKey(keys.vaultPda).toBuffer()),
                         randomness: u.randomness,
-                        // Convert Uint8Array[] to number[][]
-                        // // JSON.stringify handles number[] correctly as [1,2,3], 
-                        // but handles Uint8Array as {"0":1, "1":2} which breaks Rust hash logic.
-                        utxo_spent_list: u.utxoSpentList.map(hash => Array.from(hash)),
-                        version: 1
-                    }
+                        // Plain number[], JSON.stringify turns a Uint8Array into {"0":1, "1":2}
+                        // which breaks Rust hash logic.
+                        history_root: u.historyRoot,
+                        version: u.version
+                    },
+                    // Version 1 commitments hash the spent list, the guest checks it folds into history_root
+                    utxo_spent_list: u.version === 1 ? u.utxoSpentList : null
                 })),
                 solana_program_id: Array.from(PROGRAM_ID.toBuffer()),
                 vault_bump: myVaultBump,
@@ -196,7 +214,15 @@ export const useTransfer = () => {
                 receiver_pubkey: Array.from(receiverKey),
                 receiver_randomness: Array.from(receiverOutput.randomness),
                 return_randomness: Array.from(returnOutput.randomness),
+                // The guest re-runs both encryptions with these and commits the output hashes
+                receiver_encapsulation_seed: Array.from(receiverOutput.encapsulation_seed),
+                receiver_nonce: Array.from(receiverOutput.nonce),
+                return_encapsulation_seed: Array.from(returnOutput.encapsulation_seed),
+                return_nonce: Array.from(returnOutput.nonce),
                 current_ledger_tip: Array.from(currentTip),
+                epoch: currentEpoch,
+                ledger_path_start: ledgerPath.start,
+                ledger_path: ledgerPath.links,
                 receiver_vault: Array.from(new PublicKey(receiverVault).toBuffer()),
             };
 
@@ -234,8 +260,16 @@ export const useTransfer = () => {
                         console.log("Proof generated", proof);
 
                         try {
-                            // Submit to Solana
-                            await submitToSolana(proof, receiverOutput, returnOutput);
+                            // The guest commits one nullifier per input, same derivation as wasm
+                            const nullifiers = inputs.map(u =>
+                                Array.from(wasm.compute_nullifier(
+                                    new Uint8Array(u.header.utxoHash),
+                                    new Uint8Array(u.randomness)
+                                ))
+                            );
+
+                            // Submit to Solana, linked to the tip the proof was built against
+                            await submitToSolana(proof, receiverOutput, returnOutput, nullifiers, currentTip);
                             res(response.data);
                         } catch (submitErr) {
                             console.error("Submission failed:", submitErr);
@@ -262,7 +296,7 @@ export const useTransfer = () => {
     }
 
 
-    const submitToSolana = async (proof: string, receiverOutput: any, returnOutput: any) => {
+    const submitToSolana = async (proof: string, receiverOutput: any, returnOutput: any, nullifiers: number[][], prevUtxoHash: Uint8Array) => {
         setStatus("submitting");
 
         try {
@@ -288,34 +322,30 @@ export const useTransfer = () => {
             const zkProofKeypair = Keypair.generate();
 
             // Pre-create the ZK proof account directly (bypasses 10KB CPI limit)
-            // Space: 8 (discriminator) + 4 (total_len) + 4 (bytes_written) + proof_data
-            const zkProofSpace = 8 + 4 + 4 + proofBytes.length;
+            // Space: 8 (discriminator) + 4 (total_len) + 4 (bytes_written) + 32 (payer) + 32 (locked_by) + proof_data
+            const zkProofSpace = 8 + 4 + 4 + 32 + 32 + proofBytes.length;
             const lamports = await connection.getMinimumBalanceForRentExemption(zkProofSpace);
 
-            const createAccountTx = new Transaction().add(
-                SystemProgram.createAccount({
-                    fromPubkey: payer.publicKey,
-                    newAccountPubkey: zkProofKeypair.publicKey,
-                    lamports,
-                    space: zkProofSpace,
-                    programId: PROGRAM_ID,
-                })
-            );
-            createAccountTx.feePayer = payer.publicKey;
-            createAccountTx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
-            createAccountTx.partialSign(payer, zkProofKeypair);
-            const sig = await connection.sendRawTransaction(createAccountTx.serialize());
-            await connection.confirmTransaction(sig, "confirmed");
-            console.log("ZK Proof account created");
-
-            // Now init the ZK proof data structure
+            // Create and init in one transaction, init_zk_proof needs the proof account's signature
+            // so nobody else can initialize it and become its payer
             await program.methods
                 .initZkProof(proofBytes.length)
                 .accounts({
                     signer: payer.publicKey,
                     zkProof: zkProofKeypair.publicKey,
                 })
+                .preInstructions([
+                    SystemProgram.createAccount({
+                        fromPubkey: payer.publicKey,
+                        newAccountPubkey: zkProofKeypair.publicKey,
+                        lamports,
+                        space: zkProofSpace,
+                        programId: PROGRAM_ID,
+                    }),
+                ])
+                .signers([zkProofKeypair])
                 .rpc();
+            console.log("ZK Proof account created");
 
             // Write proof in chunks (max 900 bytes per tx to stay under limit)
             const CHUNK_SIZE = 900;
@@ -325,6 +355,7 @@ export const useTransfer = () => {
                 await program.methods
                     .writeZkProof(offset, Buffer.from(chunk))
                     .accounts({
+                        authority: payer.publicKey,
                         zkProof: zkProofKeypair.publicKey,
                     })
                     .rpc();
@@ -334,11 +365,9 @@ export const useTransfer = () => {
 
             console.log("ZK Proof uploaded:", zkProofKeypair.publicKey.toString());
 
-            // Creating Receiver UTXO 
-            await createUtxo(program, payer, receiverOutput, zkProofKeypair.publicKey);
-
-            // Creating Return UTXO 
-            await createUtxo(program, payer, returnOutput, zkProofKeypair.publicKey);
+            // Both outputs are created together and finalized in a single attestation round
+            // The receiver UTXO carries the nullifiers of the spent inputs
+            await createTransfer(program, payer, receiverOutput, returnOutput, zkProofKeypair.publicKey, nullifiers, prevUtxoHash);
 
             console.log("Transaction submitted successfully!");
             setStatus("success");
@@ -353,14 +382,13 @@ export const useTransfer = () => {
         }
     };
 
-    // Helper to create a single UTXO (handles ciphertext upload + UTXO creation)
-    // Returns the transaction signature for the createUtxo instruction
-    const createUtxo = async (
+    // Helper to upload one output's Kyber ciphertext into a fresh loader account
+    // Returns the loader pubkey
+    const uploadCiphertext = async (
         program: any,
         payer: any,
         output: any,
-        zkProofPubkey: PublicKey
-    ): Promise<string> => {
+    ): Promise<PublicKey> => {
         // Init Loader for ciphertext
         const loaderKeypair = Keypair.generate();
 
@@ -383,6 +411,7 @@ export const useTransfer = () => {
             await program.methods
                 .writeLoader(offset, Buffer.from(chunk))
                 .accounts({
+                    authority: payer.publicKey,
                     loader: loaderKeypair.publicKey,
                 })
                 .rpc();
@@ -390,39 +419,71 @@ export const useTransfer = () => {
 
         console.log("Ciphertext uploaded");
 
+        return loaderKeypair.publicKey;
+    };
+
+    // Converts a prepare_output result into the TransferOutput instruction arg
+    const toTransferOutput = (output: any) => ({
+        utxoHash: Array.from(new Uint8Array(output.utxo_hash)),
+        encryptedPayload: Buffer.from(new Uint8Array(output.encrypted_payload)),
+        nonce: Array.from(new Uint8Array(output.nonce)),
+        ciphertextCommitment: Array.from(new Uint8Array(output.ciphertext_commitment)),
+        epoch: output.epoch,
+        outputHash: Array.from(new Uint8Array(output.output_hash)),
+    });
+
+    // Helper to create both UTXOs of a transfer (handles ciphertext uploads + transfer instruction)
+    // Returns the transaction signature for the transfer instruction
+    const createTransfer = async (
+        program: any,
+        payer: any,
+        receiverOutput: any,
+        changeOutput: any,
+        zkProofPubkey: PublicKey,
+        nullifiers: number[][],
+        prevUtxoHash: Uint8Array
+    ): Promise<string> => {
+        const receiverLoader = await uploadCiphertext(program, payer, receiverOutput);
+        const changeLoader = await uploadCiphertext(program, payer, changeOutput);
+
         // Derive ledger PDA
         const [ledgerPda] = PublicKey.findProgramAddressSync(
             [Buffer.from("ledger")],
             PROGRAM_ID
         );
 
-        // Derive UTXO PDA
-        const utxoHash = new Uint8Array(output.utxo_hash);
-        const [utxoPda] = PublicKey.findProgramAddressSync(
-            [Buffer.from("utxo"), utxoHash],
+        // Derive UTXO PDAs
+        const [receiverUtxoPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("utxo"), new Uint8Array(receiverOutput.utxo_hash)],
+            PROGRAM_ID
+        );
+        const [changeUtxoPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("utxo"), new Uint8Array(changeOutput.utxo_hash)],
             PROGRAM_ID
         );
 
-        // Create UTXO
+        // Create both UTXOs
         const txSignature = await program.methods
-            .createUtxo(
-                Array.from(utxoHash),
-                Buffer.from(new Uint8Array(output.encrypted_payload)),
-                Array.from(new Uint8Array(output.nonce)),
-                Array.from(new Uint8Array(output.ciphertext_commitment)),
-                output.epoch
+            .transfer(
+                toTransferOutput(receiverOutput),
+                toTransferOutput(changeOutput),
+                nullifiers,
+                Array.from(prevUtxoHash)
             )
             .accounts({
                 signer: payer.publicKey,
                 ledger: ledgerPda,
-                utxo: utxoPda,
-                loader: loaderKeypair.publicKey,
+                receiverUtxo: receiverUtxoPda,
+                changeUtxo: changeUtxoPda,
+                receiverLoader,
+                changeLoader,
                 zkProof: zkProofPubkey,
             })
             .rpc();
 
-        console.log("UTXO created:", utxoPda.toString());
-        console.log("Create UTXO tx signature:", txSignature);
+        console.log("Receiver UTXO created:", receiverUtxoPda.toString());
+        console.log("Change UTXO created:", changeUtxoPda.toString());
+        console.log("Transfer tx signature:", txSignature);
 
         return txSignature;
     };
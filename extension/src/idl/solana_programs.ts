@@ -5,7 +5,7 @@
  * IDL can be found at `target/idl/solana_programs.json`.
  */
 export type SolanaPrograms = {
-  "address": "DMiW8pL1vuaRSG367zDRRkSmQM8z5kKUGU3eC9t7AFDT",
+  "address": "QCashfSHwqptwFRSbqjBnxYH7GbDzbAfxVeDGXhL1fv",
   "metadata": {
     "name": "solanaPrograms",
     "version": "0.1.0",
@@ -13,6 +13,62 @@ export type SolanaPrograms = {
     "description": "Created with Anchor"
   },
   "instructions": [
+    {
+      "name": "acceptAdmin",
+      "docs": [
+        "Accept a pending admin proposal"
+      ],
+      "discriminator": [
+        112,
+        42,
+        45,
+        90,
+        116,
+        181,
+        13,
+        170
+      ],
+      "accounts": [
+        {
+          "name": "newAdmin",
+          "docs": [
+            "Proposed admin taking over"
+          ],
+          "signer": true
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
     {
       "name": "airdrop",
       "docs": [
@@ -34,6 +90,103 @@ export type SolanaPrograms = {
           "writable": true,
           "signer": true
         },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration, holds the faucet authority and limits"
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "vault",
+          "docs": [
+            "Vault receiving the airdrop"
+          ]
+        },
+        {
+          "name": "airdropVaultLimit",
+          "docs": [
+            "Airdrops this vault received in the current epoch"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  105,
+                  114,
+                  100,
+                  114,
+                  111,
+                  112,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vault"
+              }
+            ]
+          }
+        },
+        {
+          "name": "airdropEpochLimit",
+          "docs": [
+            "Airdrops made in the current epoch across all vaults"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  105,
+                  114,
+                  100,
+                  114,
+                  111,
+                  112,
+                  95,
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              }
+            ]
+          }
+        },
         {
           "name": "ledger",
           "writable": true,
@@ -53,6 +206,14 @@ export type SolanaPrograms = {
             ]
           }
         },
+        {
+          "name": "ledgerPage",
+          "writable": true
+        },
+        {
+          "name": "nextLedgerPage",
+          "writable": true
+        },
         {
           "name": "utxo",
           "docs": [
@@ -81,7 +242,7 @@ export type SolanaPrograms = {
         {
           "name": "loader",
           "docs": [
-            "Loader account containing Kyber ciphertext (will be closed after use)"
+            "Loader account containing Kyber ciphertext, uploaded by the signer (will be closed after use)"
           ],
           "writable": true
         },
@@ -125,9 +286,124 @@ export type SolanaPrograms = {
         {
           "name": "epoch",
           "type": "u32"
+        },
+        {
+          "name": "prevUtxoHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "claimRewards",
+      "docs": [
+        "Claim the attestation fees a prover has earned (stake owner only)"
+      ],
+      "discriminator": [
+        4,
+        144,
+        132,
+        71,
+        116,
+        23,
+        151,
+        80
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "docs": [
+            "Stake owner, receives the rewards"
+          ],
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "proverStake"
+          ]
+        },
+        {
+          "name": "proverStake",
+          "docs": [
+            "Prover's stake, holds the earned attestation fees"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  118,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "uniqueId"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "uniqueId",
+          "type": "u64"
         }
       ]
     },
+    {
+      "name": "closeZkProof",
+      "docs": [
+        "Close a ZK proof once its UTXO is finalized or rejected, returning the rent to the payer"
+      ],
+      "discriminator": [
+        150,
+        76,
+        205,
+        197,
+        67,
+        57,
+        146,
+        237
+      ],
+      "accounts": [
+        {
+          "name": "zkProof",
+          "docs": [
+            "ZK proof account to close"
+          ],
+          "writable": true
+        },
+        {
+          "name": "utxo",
+          "docs": [
+            "UTXO the proof is locked to, must be finalized or rejected"
+          ]
+        },
+        {
+          "name": "payer",
+          "docs": [
+            "Payer recorded in the proof header, receives the lamports"
+          ],
+          "writable": true
+        }
+      ],
+      "args": []
+    },
     {
       "name": "completeVault",
       "docs": [
@@ -177,7 +453,7 @@ export type SolanaPrograms = {
         {
           "name": "ledger",
           "docs": [
-            "Ledger account to verify previous UTXO hash"
+            "Ledger account, the UTXO must link to one of its recent tips"
           ],
           "pda": {
             "seeds": [
@@ -196,24 +472,53 @@ export type SolanaPrograms = {
           }
         },
         {
-          "name": "utxo",
+          "name": "programConfig",
           "docs": [
-            "New UTXO account to be created",
-            "PDA: [\"utxo\", utxo_hash]"
+            "Program configuration, holds the attestation fee"
           ],
-          "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  117,
-                  116,
-                  120,
-                  111
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
                 ]
-              },
-              {
+              }
+            ]
+          }
+        },
+        {
+          "name": "utxo",
+          "docs": [
+            "New UTXO account to be created",
+            "PDA: [\"utxo\", utxo_hash]"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  116,
+                  120,
+                  111
+                ]
+              },
+              {
                 "kind": "arg",
                 "path": "utxoHash"
               }
@@ -223,15 +528,16 @@ export type SolanaPrograms = {
         {
           "name": "loader",
           "docs": [
-            "Loader account containing Kyber ciphertext (will be closed after use)"
+            "Loader account containing Kyber ciphertext, uploaded by the signer (will be closed after use)"
           ],
           "writable": true
         },
         {
           "name": "zkProof",
           "docs": [
-            "ZK Proof account reference"
-          ]
+            "ZK Proof account, locked to this UTXO"
+          ],
+          "writable": true
         },
         {
           "name": "systemProgram",
@@ -273,6 +579,35 @@ export type SolanaPrograms = {
         {
           "name": "epoch",
           "type": "u32"
+        },
+        {
+          "name": "nullifiers",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        },
+        {
+          "name": "outputHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "prevUtxoHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
         }
       ]
     },
@@ -294,6 +629,9 @@ export type SolanaPrograms = {
       "accounts": [
         {
           "name": "admin",
+          "docs": [
+            "Admin, or one of the admin signers with the others passed as remaining accounts"
+          ],
           "writable": true,
           "signer": true
         },
@@ -620,9 +958,12 @@ export type SolanaPrograms = {
         {
           "name": "zkProof",
           "docs": [
-            "We just initialize the data structure here."
+            "Account is pre-created by client via System Program to bypass 10KB CPI limit.",
+            "We just initialize the data structure here. It must sign, or anyone watching the",
+            "create transaction could initialize it first and become its payer."
           ],
-          "writable": true
+          "writable": true,
+          "signer": true
         }
       ],
       "args": [
@@ -633,30 +974,33 @@ export type SolanaPrograms = {
       ]
     },
     {
-      "name": "registerProver",
+      "name": "migrateLedger",
       "docs": [
-        "Register a new prover (admin only)"
+        "Grow a ledger created before the running tip, its UTXOs are replayed by migrate_utxo (admin only)"
       ],
       "discriminator": [
-        170,
-        190,
-        182,
-        150,
         76,
-        46,
-        227,
-        208
+        85,
+        118,
+        244,
+        156,
+        189,
+        103,
+        63
       ],
       "accounts": [
         {
           "name": "admin",
+          "docs": [
+            "Admin, pays for the extra space"
+          ],
           "writable": true,
           "signer": true
         },
         {
           "name": "programConfig",
           "docs": [
-            "Program configuration"
+            "Program configuration, holds the admin"
           ],
           "pda": {
             "seeds": [
@@ -683,9 +1027,10 @@ export type SolanaPrograms = {
           }
         },
         {
-          "name": "proverRegistry",
+          "name": "ledger",
           "docs": [
-            "Prover registry"
+            "Ledger, still at Ledger::LEGACY_SIZE",
+            "are checked here and the discriminator in the handler"
           ],
           "writable": true,
           "pda": {
@@ -693,101 +1038,138 @@ export type SolanaPrograms = {
               {
                 "kind": "const",
                 "value": [
-                  112,
-                  114,
-                  111,
-                  118,
-                  101,
-                  114,
-                  95,
-                  114,
+                  108,
                   101,
+                  100,
                   103,
-                  105,
-                  115,
-                  116,
-                  114,
-                  121
+                  101,
+                  114
                 ]
               }
             ]
           }
         },
         {
-          "name": "proverPubkey",
-          "docs": [
-            "Prover's public key to register"
-          ]
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "uniqueId",
-          "type": "u64"
-        },
-        {
-          "name": "nextKeyHash",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
+          "name": "genesisTimestamp",
+          "type": "i64"
         }
       ]
     },
     {
-      "name": "submitAttestation",
+      "name": "migrateProgramConfig",
       "docs": [
-        "Submit attestation vote on a UTXO"
+        "Grow a program config created before its appended fields and set them to defaults (admin only)"
       ],
       "discriminator": [
-        238,
-        220,
-        255,
-        105,
-        183,
-        211,
-        40,
-        83
+        214,
+        117,
+        160,
+        197,
+        134,
+        225,
+        159,
+        237
       ],
       "accounts": [
         {
-          "name": "proverOld",
+          "name": "admin",
           "docs": [
-            "The old prover account that will be used to pay for this IX.",
-            "Afterwards, all the remaining lamports will be transferred to the new prover account."
+            "Admin, pays for the extra space"
           ],
           "writable": true,
           "signer": true
         },
         {
-          "name": "prover",
+          "name": "programConfig",
           "docs": [
-            "The new prover submitting their vote",
-            "Their public key will be hashed and checked against the registry",
-            "This becomes the active prover account after key rotation"
+            "Program configuration, still at ProgramConfig::LEGACY_SIZE"
           ],
           "writable": true,
-          "signer": true
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
         },
         {
-          "name": "ledger",
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateUtxo",
+      "docs": [
+        "Grow a UTXO created before the fields appended after its bump (admin only)"
+      ],
+      "discriminator": [
+        60,
+        71,
+        156,
+        27,
+        85,
+        179,
+        50,
+        18
+      ],
+      "accounts": [
+        {
+          "name": "admin",
           "docs": [
-            "Ledger to verify and update"
+            "Admin, pays for the extra space"
           ],
           "writable": true,
+          "signer": true
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration, holds the admin"
+          ],
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  108,
-                  101,
-                  100,
+                  112,
+                  114,
+                  111,
                   103,
-                  101,
-                  114
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
                 ]
               }
             ]
@@ -796,7 +1178,8 @@ export type SolanaPrograms = {
         {
           "name": "utxo",
           "docs": [
-            "UTXO being attested"
+            "UTXO, still at the size the first release gave it",
+            "here and the discriminator and size in the handler"
           ],
           "writable": true,
           "pda": {
@@ -818,9 +1201,9 @@ export type SolanaPrograms = {
           }
         },
         {
-          "name": "proverRegistry",
+          "name": "ledger",
           "docs": [
-            "Prover registry"
+            "Ledger, already migrated, finalized legacy UTXOs are replayed onto its running tip"
           ],
           "writable": true,
           "pda": {
@@ -828,26 +1211,24 @@ export type SolanaPrograms = {
               {
                 "kind": "const",
                 "value": [
-                  112,
-                  114,
-                  111,
-                  118,
-                  101,
-                  114,
-                  95,
-                  114,
+                  108,
                   101,
+                  100,
                   103,
-                  105,
-                  115,
-                  116,
-                  114,
-                  121
+                  101,
+                  114
                 ]
               }
             ]
           }
         },
+        {
+          "name": "ledgerPage",
+          "docs": [
+            "Only written for a finalized UTXO, created if it lands on the first slot of the page"
+          ],
+          "writable": true
+        },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
@@ -862,470 +1243,3847 @@ export type SolanaPrograms = {
               32
             ]
           }
-        },
-        {
-          "name": "vote",
-          "type": "bool"
-        },
-        {
-          "name": "nextKeyHash",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
         }
       ]
     },
     {
-      "name": "writeLoader",
+      "name": "proposeAdmin",
       "docs": [
-        "Write chunk to loader"
+        "Propose a new admin, None cancels a pending proposal"
       ],
       "discriminator": [
-        50,
-        101,
-        230,
-        10,
-        73,
-        217,
-        42,
-        209
+        121,
+        214,
+        199,
+        212,
+        87,
+        39,
+        117,
+        234
       ],
       "accounts": [
         {
-          "name": "loader",
-          "writable": true
-        }
-      ],
-      "args": [
-        {
-          "name": "offset",
-          "type": "u32"
+          "name": "admin",
+          "docs": [
+            "Admin, or one of the admin signers with the others passed as remaining accounts"
+          ],
+          "signer": true
         },
         {
-          "name": "data",
-          "type": "bytes"
+          "name": "programConfig",
+          "docs": [
+            "Program configuration"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": {
+            "option": "pubkey"
+          }
         }
       ]
     },
     {
-      "name": "writeZkProof",
+      "name": "reactivateProver",
       "docs": [
-        "Write chunk to ZK proof"
+        "Reactivate a deactivated prover (admin only)"
       ],
       "discriminator": [
-        158,
-        236,
-        115,
-        161,
-        164,
-        17,
-        46,
-        22
+        188,
+        126,
+        65,
+        215,
+        179,
+        217,
+        205,
+        154
       ],
       "accounts": [
         {
-          "name": "zkProof",
-          "writable": true
+          "name": "admin",
+          "docs": [
+            "Admin, or one of the admin signers with the others passed as remaining accounts"
+          ],
+          "signer": true
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration"
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "proverRegistry",
+          "docs": [
+            "Prover registry"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  118,
+                  101,
+                  114,
+                  95,
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              }
+            ]
+          }
         }
       ],
       "args": [
         {
-          "name": "offset",
-          "type": "u32"
-        },
-        {
-          "name": "chunk",
-          "type": "bytes"
+          "name": "uniqueId",
+          "type": "u64"
         }
       ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "ledger",
-      "discriminator": [
-        43,
-        41,
-        21,
-        213,
-        180,
-        176,
-        95,
-        32
-      ]
     },
     {
-      "name": "loader",
+      "name": "reclaimRejectedUtxo",
+      "docs": [
+        "Close a rejected or expired UTXO and its proof, returning the rent to the payer"
+      ],
       "discriminator": [
-        107,
-        205,
-        249,
-        225,
-        119,
+        175,
+        202,
         75,
-        83,
-        248
-      ]
-    },
-    {
-      "name": "programConfig",
-      "discriminator": [
-        196,
-        210,
-        90,
-        231,
-        144,
-        149,
-        140,
-        63
-      ]
-    },
-    {
-      "name": "proverRegistry",
-      "discriminator": [
-        210,
-        201,
-        111,
-        119,
-        245,
-        85,
-        163,
-        11
-      ]
-    },
-    {
-      "name": "utxo",
-      "discriminator": [
-        210,
-        200,
-        228,
-        232,
+        120,
         85,
-        104,
-        216,
-        76
-      ]
-    },
-    {
-      "name": "vault",
-      "discriminator": [
-        211,
-        8,
-        232,
-        43,
-        2,
-        152,
-        117,
-        119
-      ]
-    }
-  ],
-  "events": [
-    {
-      "name": "airdropCompleted",
-      "discriminator": [
-        191,
-        220,
-        138,
-        226,
-        189,
-        53,
-        3,
-        235
-      ]
+        10,
+        175,
+        212
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "docs": [
+            "Payer recorded on the UTXO, receives the rent"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "utxo",
+          "docs": [
+            "Rejected or expired UTXO to close"
+          ],
+          "writable": true
+        },
+        {
+          "name": "changeUtxo",
+          "docs": [
+            "Change UTXO, required when `utxo` was created by `transfer`"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "zkProof",
+          "docs": [
+            "ZK proof referenced by the UTXO, closed if the payer also paid for it"
+          ],
+          "writable": true
+        }
+      ],
+      "args": []
     },
     {
-      "name": "attestationSubmitted",
+      "name": "registerProver",
+      "docs": [
+        "Register a new prover (admin only), locking its stake"
+      ],
       "discriminator": [
-        177,
-        213,
-        117,
-        225,
-        166,
-        11,
-        54,
-        218
-      ]
-    },
-    {
-      "name": "ledgerInitialized",
-      "discriminator": [
-        56,
-        38,
-        185,
-        200,
-        94,
-        92,
-        181,
-        202
+        170,
+        190,
+        182,
+        150,
+        76,
+        46,
+        227,
+        208
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "docs": [
+            "Admin, or one of the admin signers with the others passed as remaining accounts"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration"
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "proverRegistry",
+          "docs": [
+            "Prover registry"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  118,
+                  101,
+                  114,
+                  95,
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "proverPubkey",
+          "docs": [
+            "Prover's public key to register"
+          ]
+        },
+        {
+          "name": "staker",
+          "docs": [
+            "Account locking the stake, the only one allowed to withdraw it"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "proverStake",
+          "docs": [
+            "Prover's stake, kept when a removed prover is registered again under the same ID"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  118,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "uniqueId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "uniqueId",
+          "type": "u64"
+        },
+        {
+          "name": "nextKeyHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "stakeAmount",
+          "type": "u64"
+        }
       ]
     },
     {
-      "name": "loaderChunkWritten",
+      "name": "removeProver",
+      "docs": [
+        "Remove a deactivated prover and free its slot (admin only)"
+      ],
       "discriminator": [
-        100,
-        107,
-        219,
-        86,
-        6,
-        9,
-        103,
-        244
+        233,
+        169,
+        31,
+        202,
+        52,
+        70,
+        4,
+        252
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "docs": [
+            "Admin, or one of the admin signers with the others passed as remaining accounts"
+          ],
+          "signer": true
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration"
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "proverRegistry",
+          "docs": [
+            "Prover registry"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  118,
+                  101,
+                  114,
+                  95,
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "uniqueId",
+          "type": "u64"
+        }
       ]
     },
     {
-      "name": "loaderInitialized",
+      "name": "requestUnstake",
+      "docs": [
+        "Move part of a prover's stake into unbonding (stake owner only)"
+      ],
       "discriminator": [
-        232,
-        178,
-        234,
-        13,
-        122,
+        44,
+        154,
+        110,
         253,
-        41,
-        114
+        160,
+        202,
+        54,
+        34
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "docs": [
+            "Stake owner"
+          ],
+          "signer": true,
+          "relations": [
+            "proverStake"
+          ]
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration, holds the unbonding period"
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "proverStake",
+          "docs": [
+            "Prover's stake"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  118,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "uniqueId"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "uniqueId",
+          "type": "u64"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
       ]
     },
     {
-      "name": "programInitialized",
+      "name": "resetProverKey",
+      "docs": [
+        "Force-reset a prover's key hash after it lost its rotation keys (admin only)"
+      ],
       "discriminator": [
-        43,
-        70,
         110,
-        241,
-        199,
-        218,
-        221,
-        245
-      ]
-    },
-    {
-      "name": "proverDeactivated",
-      "discriminator": [
-        124,
-        129,
-        96,
-        166,
+        92,
+        29,
+        245,
+        72,
         67,
-        234,
-        34,
-        181
-      ]
-    },
-    {
-      "name": "proverRegistered",
-      "discriminator": [
-        243,
-        150,
-        169,
-        237,
-        21,
-        205,
-        121,
-        191
+        158,
+        255
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "docs": [
+            "Admin, or one of the admin signers with the others passed as remaining accounts"
+          ],
+          "signer": true
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration"
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "proverRegistry",
+          "docs": [
+            "Prover registry"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  118,
+                  101,
+                  114,
+                  95,
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "uniqueId",
+          "type": "u64"
+        },
+        {
+          "name": "newPubkeyHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
       ]
     },
     {
-      "name": "utxoCreated",
+      "name": "setAdminSigners",
+      "docs": [
+        "Hand admin rights to an M-of-N signer set"
+      ],
       "discriminator": [
+        240,
+        171,
         141,
-        33,
-        36,
-        147,
-        147,
-        155,
-        87,
-        98
+        105,
+        124,
+        2,
+        225,
+        188
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "docs": [
+            "Admin, or one of the admin signers with the others passed as remaining accounts"
+          ],
+          "signer": true
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "signers",
+          "type": {
+            "vec": "pubkey"
+          }
+        },
+        {
+          "name": "threshold",
+          "type": "u8"
+        }
       ]
     },
     {
-      "name": "vaultCompleted",
-      "discriminator": [
-        174,
-        72,
-        181,
-        184,
-        121,
-        48,
-        107,
-        227
-      ]
-    },
-    {
-      "name": "vaultInitialized",
+      "name": "slashProver",
+      "docs": [
+        "Slash a prover that voted against a UTXO's final outcome (anyone can call)"
+      ],
       "discriminator": [
-        180,
-        43,
-        207,
-        2,
-        18,
-        71,
-        3,
-        75
+        16,
+        104,
+        109,
+        192,
+        13,
+        0,
+        254,
+        161
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "docs": [
+            "Caller, pays nothing but the transaction fee"
+          ],
+          "signer": true
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration, holds the slash share and receives the slashed lamports"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "utxo",
+          "docs": [
+            "Finalized or rejected UTXO carrying the wrong vote"
+          ],
+          "writable": true
+        },
+        {
+          "name": "proverStake",
+          "docs": [
+            "Stake of the prover that cast the wrong vote"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  118,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "uniqueId"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "uniqueId",
+          "type": "u64"
+        }
       ]
     },
     {
-      "name": "zkProofChunkWritten",
+      "name": "submitAttestation",
+      "docs": [
+        "Submit attestation vote on a UTXO",
+        "Pass the UTXO's nullifier PDAs as remaining accounts"
+      ],
       "discriminator": [
-        86,
-        99,
-        62,
-        54,
-        246,
-        173,
-        54,
-        146
-      ]
+        238,
+        220,
+        255,
+        105,
+        183,
+        211,
+        40,
+        83
+      ],
+      "accounts": [
+        {
+          "name": "proverOld",
+          "docs": [
+            "The old prover account that will be used to pay for this IX.",
+            "Afterwards, all the remaining lamports will be transferred to the new prover account."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "prover",
+          "docs": [
+            "The new prover submitting their vote",
+            "Their public key will be hashed and checked against the registry",
+            "This becomes the active prover account after key rotation"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "ledger",
+          "docs": [
+            "Ledger to verify and update"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  108,
+                  101,
+                  100,
+                  103,
+                  101,
+                  114
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "utxo",
+          "docs": [
+            "UTXO being attested"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  116,
+                  120,
+                  111
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "utxoHash"
+              }
+            ]
+          }
+        },
+        {
+          "name": "changeUtxo",
+          "docs": [
+            "Change UTXO, required when `utxo` was created by `transfer`"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "proverRegistry",
+          "docs": [
+            "Prover registry"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  118,
+                  101,
+                  114,
+                  95,
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration, holds the attestation threshold"
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "proverStake",
+          "docs": [
+            "Voting prover's stake, matched to the prover by unique ID in the handler",
+            "(stake accounts are only created at their [\"prover_stake\", unique_id] PDA)"
+          ],
+          "writable": true
+        },
+        {
+          "name": "ledgerPage",
+          "docs": [
+            "Created by this vote if it finalizes the first UTXO of the page"
+          ],
+          "writable": true
+        },
+        {
+          "name": "nextLedgerPage",
+          "docs": [
+            "Needed when a transfer's change lands on the next page or the ledger moved on since",
+            "the prover read it"
+          ],
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "utxoHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "vote",
+          "type": "bool"
+        },
+        {
+          "name": "nextKeyHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "sweepSlashed",
+      "docs": [
+        "Move the slashed lamports held by the program config to `destination` (admin only)"
+      ],
+      "discriminator": [
+        48,
+        250,
+        251,
+        165,
+        194,
+        251,
+        6,
+        22
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "docs": [
+            "Admin, or one of the admin signers with the others passed as remaining accounts"
+          ],
+          "signer": true
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration, slash_prover moves slashed lamports here"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "destination",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "transfer",
+      "docs": [
+        "Create both UTXOs of a transfer, attested and finalized together"
+      ],
+      "discriminator": [
+        163,
+        52,
+        200,
+        231,
+        140,
+        3,
+        69,
+        186
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "ledger",
+          "docs": [
+            "Ledger account, the receiver UTXO links to one of its recent tips"
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  108,
+                  101,
+                  100,
+                  103,
+                  101,
+                  114
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration, holds the attestation fee"
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "receiverUtxo",
+          "docs": [
+            "Receiver UTXO, carries the votes and the nullifiers of the spent inputs",
+            "PDA: [\"utxo\", receiver.utxo_hash]"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  116,
+                  120,
+                  111
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "receiver.utxo_hash"
+              }
+            ]
+          }
+        },
+        {
+          "name": "changeUtxo",
+          "docs": [
+            "Change UTXO, finalized together with the receiver UTXO",
+            "PDA: [\"utxo\", change.utxo_hash]"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  116,
+                  120,
+                  111
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "change.utxo_hash"
+              }
+            ]
+          }
+        },
+        {
+          "name": "receiverLoader",
+          "docs": [
+            "Loader with the receiver's Kyber ciphertext, uploaded by the signer (closed after use)"
+          ],
+          "writable": true
+        },
+        {
+          "name": "changeLoader",
+          "docs": [
+            "Loader with the change Kyber ciphertext, uploaded by the signer (closed after use)"
+          ],
+          "writable": true
+        },
+        {
+          "name": "zkProof",
+          "docs": [
+            "ZK Proof account covering both outputs, locked to the receiver UTXO"
+          ],
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "receiver",
+          "type": {
+            "defined": {
+              "name": "transferOutput"
+            }
+          }
+        },
+        {
+          "name": "change",
+          "type": {
+            "defined": {
+              "name": "transferOutput"
+            }
+          }
+        },
+        {
+          "name": "nullifiers",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        },
+        {
+          "name": "prevUtxoHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateConfig",
+      "docs": [
+        "Update the program configuration (admin only)"
+      ],
+      "discriminator": [
+        29,
+        158,
+        252,
+        191,
+        10,
+        83,
+        219,
+        99
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "docs": [
+            "Admin, or one of the admin signers with the others passed as remaining accounts"
+          ],
+          "signer": true
+        },
+        {
+          "name": "programConfig",
+          "docs": [
+            "Program configuration"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "proverRegistry",
+          "docs": [
+            "Prover registry, bounds the attestation threshold"
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  118,
+                  101,
+                  114,
+                  95,
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "update",
+          "type": {
+            "defined": {
+              "name": "configUpdate"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "withdrawStake",
+      "docs": [
+        "Withdraw unbonded stake once the unbonding period is over (stake owner only)"
+      ],
+      "discriminator": [
+        153,
+        8,
+        22,
+        138,
+        105,
+        176,
+        87,
+        66
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "docs": [
+            "Stake owner, receives the unbonded lamports"
+          ],
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "proverStake"
+          ]
+        },
+        {
+          "name": "proverStake",
+          "docs": [
+            "Prover's stake"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  118,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "uniqueId"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "uniqueId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "writeLoader",
+      "docs": [
+        "Write chunk to loader"
+      ],
+      "discriminator": [
+        50,
+        101,
+        230,
+        10,
+        73,
+        217,
+        42,
+        209
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "loader"
+          ]
+        },
+        {
+          "name": "loader",
+          "writable": true
+        }
+      ],
+      "args": [
+        {
+          "name": "offset",
+          "type": "u32"
+        },
+        {
+          "name": "data",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "writeZkProof",
+      "docs": [
+        "Write chunk to ZK proof"
+      ],
+      "discriminator": [
+        158,
+        236,
+        115,
+        161,
+        164,
+        17,
+        46,
+        22
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "docs": [
+            "Account that initialized the proof"
+          ],
+          "signer": true
+        },
+        {
+          "name": "zkProof",
+          "writable": true
+        }
+      ],
+      "args": [
+        {
+          "name": "offset",
+          "type": "u32"
+        },
+        {
+          "name": "chunk",
+          "type": "bytes"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "airdropEpochLimit",
+      "discriminator": [
+        44,
+        6,
+        64,
+        34,
+        226,
+        117,
+        56,
+        149
+      ]
+    },
+    {
+      "name": "airdropVaultLimit",
+      "discriminator": [
+        176,
+        60,
+        168,
+        11,
+        251,
+        61,
+        85,
+        57
+      ]
+    },
+    {
+      "name": "ledger",
+      "discriminator": [
+        43,
+        41,
+        21,
+        213,
+        180,
+        176,
+        95,
+        32
+      ]
+    },
+    {
+      "name": "loader",
+      "discriminator": [
+        107,
+        205,
+        249,
+        225,
+        119,
+        75,
+        83,
+        248
+      ]
+    },
+    {
+      "name": "programConfig",
+      "discriminator": [
+        196,
+        210,
+        90,
+        231,
+        144,
+        149,
+        140,
+        63
+      ]
+    },
+    {
+      "name": "proverRegistry",
+      "discriminator": [
+        210,
+        201,
+        111,
+        119,
+        245,
+        85,
+        163,
+        11
+      ]
+    },
+    {
+      "name": "proverStake",
+      "discriminator": [
+        132,
+        221,
+        50,
+        66,
+        104,
+        219,
+        95,
+        2
+      ]
+    },
+    {
+      "name": "utxo",
+      "discriminator": [
+        210,
+        200,
+        228,
+        232,
+        85,
+        104,
+        216,
+        76
+      ]
+    },
+    {
+      "name": "vault",
+      "discriminator": [
+        211,
+        8,
+        232,
+        43,
+        2,
+        152,
+        117,
+        119
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "adminAccepted",
+      "discriminator": [
+        174,
+        12,
+        76,
+        139,
+        158,
+        99,
+        110,
+        254
+      ]
+    },
+    {
+      "name": "adminProposed",
+      "discriminator": [
+        129,
+        249,
+        226,
+        227,
+        199,
+        82,
+        110,
+        243
+      ]
+    },
+    {
+      "name": "adminSignersUpdated",
+      "discriminator": [
+        116,
+        117,
+        228,
+        166,
+        11,
+        253,
+        219,
+        179
+      ]
+    },
+    {
+      "name": "airdropCompleted",
+      "discriminator": [
+        191,
+        220,
+        138,
+        226,
+        189,
+        53,
+        3,
+        235
+      ]
+    },
+    {
+      "name": "attestationSubmitted",
+      "discriminator": [
+        177,
+        213,
+        117,
+        225,
+        166,
+        11,
+        54,
+        218
+      ]
+    },
+    {
+      "name": "configUpdated",
+      "discriminator": [
+        40,
+        241,
+        230,
+        122,
+        11,
+        19,
+        198,
+        194
+      ]
+    },
+    {
+      "name": "ledgerInitialized",
+      "discriminator": [
+        56,
+        38,
+        185,
+        200,
+        94,
+        92,
+        181,
+        202
+      ]
+    },
+    {
+      "name": "ledgerMigrated",
+      "discriminator": [
+        217,
+        210,
+        225,
+        104,
+        192,
+        191,
+        173,
+        76
+      ]
+    },
+    {
+      "name": "loaderChunkWritten",
+      "discriminator": [
+        100,
+        107,
+        219,
+        86,
+        6,
+        9,
+        103,
+        244
+      ]
+    },
+    {
+      "name": "loaderInitialized",
+      "discriminator": [
+        232,
+        178,
+        234,
+        13,
+        122,
+        253,
+        41,
+        114
+      ]
+    },
+    {
+      "name": "nullifierSpent",
+      "discriminator": [
+        166,
+        111,
+        130,
+        54,
+        212,
+        115,
+        152,
+        215
+      ]
+    },
+    {
+      "name": "programConfigMigrated",
+      "discriminator": [
+        115,
+        185,
+        78,
+        18,
+        158,
+        23,
+        114,
+        60
+      ]
+    },
+    {
+      "name": "programInitialized",
+      "discriminator": [
+        43,
+        70,
+        110,
+        241,
+        199,
+        218,
+        221,
+        245
+      ]
+    },
+    {
+      "name": "proverDeactivated",
+      "discriminator": [
+        124,
+        129,
+        96,
+        166,
+        67,
+        234,
+        34,
+        181
+      ]
+    },
+    {
+      "name": "proverKeyReset",
+      "discriminator": [
+        238,
+        241,
+        138,
+        206,
+        243,
+        23,
+        140,
+        195
+      ]
+    },
+    {
+      "name": "proverReactivated",
+      "discriminator": [
+        186,
+        225,
+        131,
+        150,
+        161,
+        211,
+        75,
+        197
+      ]
+    },
+    {
+      "name": "proverRegistered",
+      "discriminator": [
+        243,
+        150,
+        169,
+        237,
+        21,
+        205,
+        121,
+        191
+      ]
+    },
+    {
+      "name": "proverRemoved",
+      "discriminator": [
+        111,
+        49,
+        78,
+        156,
+        34,
+        203,
+        189,
+        118
+      ]
+    },
+    {
+      "name": "proverSlashed",
+      "discriminator": [
+        184,
+        210,
+        66,
+        52,
+        209,
+        138,
+        11,
+        2
+      ]
+    },
+    {
+      "name": "rewardsClaimed",
+      "discriminator": [
+        75,
+        98,
+        88,
+        18,
+        219,
+        112,
+        88,
+        121
+      ]
+    },
+    {
+      "name": "slashedSwept",
+      "discriminator": [
+        177,
+        174,
+        208,
+        62,
+        52,
+        11,
+        251,
+        208
+      ]
+    },
+    {
+      "name": "stakeWithdrawn",
+      "discriminator": [
+        33,
+        120,
+        159,
+        58,
+        140,
+        255,
+        174,
+        79
+      ]
+    },
+    {
+      "name": "transferCreated",
+      "discriminator": [
+        26,
+        150,
+        222,
+        191,
+        158,
+        158,
+        186,
+        179
+      ]
+    },
+    {
+      "name": "unstakeRequested",
+      "discriminator": [
+        21,
+        253,
+        177,
+        85,
+        129,
+        206,
+        42,
+        152
+      ]
+    },
+    {
+      "name": "utxoCreated",
+      "discriminator": [
+        141,
+        33,
+        36,
+        147,
+        147,
+        155,
+        87,
+        98
+      ]
+    },
+    {
+      "name": "utxoMigrated",
+      "discriminator": [
+        196,
+        75,
+        85,
+        70,
+        221,
+        43,
+        127,
+        59
+      ]
+    },
+    {
+      "name": "utxoReclaimed",
+      "discriminator": [
+        248,
+        190,
+        18,
+        133,
+        223,
+        35,
+        157,
+        76
+      ]
+    },
+    {
+      "name": "utxoRejected",
+      "discriminator": [
+        117,
+        136,
+        47,
+        116,
+        83,
+        65,
+        167,
+        149
+      ]
+    },
+    {
+      "name": "vaultCompleted",
+      "discriminator": [
+        174,
+        72,
+        181,
+        184,
+        121,
+        48,
+        107,
+        227
+      ]
+    },
+    {
+      "name": "vaultInitialized",
+      "discriminator": [
+        180,
+        43,
+        207,
+        2,
+        18,
+        71,
+        3,
+        75
+      ]
+    },
+    {
+      "name": "zkProofChunkWritten",
+      "discriminator": [
+        86,
+        99,
+        62,
+        54,
+        246,
+        173,
+        54,
+        146
+      ]
+    },
+    {
+      "name": "zkProofClosed",
+      "discriminator": [
+        247,
+        187,
+        102,
+        151,
+        236,
+        233,
+        108,
+        6
+      ]
+    },
+    {
+      "name": "zkProofInitialized",
+      "discriminator": [
+        41,
+        89,
+        183,
+        101,
+        104,
+        233,
+        152,
+        10
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "invalidKeyLength",
+      "msg": "Kyber Public key must be exactly 1184 bytes long"
+    },
+    {
+      "code": 6001,
+      "name": "chunkSizeExceeded",
+      "msg": "Chunk size exceeds maximum allowed length of 800 bytes"
+    },
+    {
+      "code": 6002,
+      "name": "hashMismatch",
+      "msg": "Hash Mismatch: Provided key does not match the expected hash"
+    },
+    {
+      "code": 6003,
+      "name": "invalidPreviousUtxoHash",
+      "msg": "Invalid previous UTXO hash provided"
+    },
+    {
+      "code": 6004,
+      "name": "payloadTooLarge",
+      "msg": "Payload too large"
+    },
+    {
+      "code": 6005,
+      "name": "arithmeticOverflow",
+      "msg": "Arithmetic overflow occurred"
+    },
+    {
+      "code": 6006,
+      "name": "invalidOffset",
+      "msg": "Invalid offset: must match current bytes_written"
+    },
+    {
+      "code": 6007,
+      "name": "programConfigAlreadyInitialized",
+      "msg": "Program config already initialized"
+    },
+    {
+      "code": 6008,
+      "name": "adminAuthorityNotSet",
+      "msg": "Admin authority not set"
+    },
+    {
+      "code": 6009,
+      "name": "unauthorizedAdmin",
+      "msg": "Unauthorized: Only admin can perform this action"
+    },
+    {
+      "code": 6010,
+      "name": "proverAlreadyRegistered",
+      "msg": "Prover already registered"
+    },
+    {
+      "code": 6011,
+      "name": "proverNotRegistered",
+      "msg": "Prover not registered"
+    },
+    {
+      "code": 6012,
+      "name": "proverNotActive",
+      "msg": "Prover not active"
+    },
+    {
+      "code": 6013,
+      "name": "proverAlreadyVoted",
+      "msg": "Prover already voted on this UTXO"
+    },
+    {
+      "code": 6014,
+      "name": "maxProversReached",
+      "msg": "Maximum provers reached"
+    },
+    {
+      "code": 6015,
+      "name": "maxVotesReached",
+      "msg": "Maximum votes reached for this UTXO"
+    },
+    {
+      "code": 6016,
+      "name": "invalidStakeAmount",
+      "msg": "Invalid stake amount"
+    },
+    {
+      "code": 6017,
+      "name": "proverUniqueIdAlreadyUsed",
+      "msg": "Prover unique ID already used"
+    },
+    {
+      "code": 6018,
+      "name": "invalidProverUniqueId",
+      "msg": "Invalid prover unique ID. Cannot be zero."
+    },
+    {
+      "code": 6019,
+      "name": "zkProofAccountNotProvided",
+      "msg": "ZK Proof account not provided"
+    },
+    {
+      "code": 6020,
+      "name": "utxoHashMismatch",
+      "msg": "UTXO hash mismatch with ledger"
+    },
+    {
+      "code": 6021,
+      "name": "minAttestationsNotMet",
+      "msg": "Min attestations not met"
+    },
+    {
+      "code": 6022,
+      "name": "tooManyNullifiers",
+      "msg": "Too many nullifiers for a single UTXO"
+    },
+    {
+      "code": 6023,
+      "name": "nullifierAccountsMismatch",
+      "msg": "Nullifier accounts do not match the UTXO nullifiers"
+    },
+    {
+      "code": 6024,
+      "name": "nullifierAlreadySpent",
+      "msg": "Nullifier already spent"
+    },
+    {
+      "code": 6025,
+      "name": "outputHashMismatch",
+      "msg": "Output hash does not match the loader ciphertext and payload"
+    },
+    {
+      "code": 6026,
+      "name": "duplicateLoader",
+      "msg": "Receiver and change outputs must use different loaders"
+    },
+    {
+      "code": 6027,
+      "name": "changeUtxoMismatch",
+      "msg": "Change UTXO does not belong to this transfer"
+    },
+    {
+      "code": 6028,
+      "name": "invalidMinAttestations",
+      "msg": "Min attestations must be between 1 and the number of active provers (at most MAX_VOTES_ALLOWED)"
+    },
+    {
+      "code": 6029,
+      "name": "invalidMinRejections",
+      "msg": "Min rejections must put min attestations out of reach and be at most the number of active provers (and MAX_VOTES_ALLOWED)"
+    },
+    {
+      "code": 6030,
+      "name": "utxoNotPending",
+      "msg": "UTXO is no longer pending"
+    },
+    {
+      "code": 6031,
+      "name": "utxoNotRejected",
+      "msg": "UTXO has not been rejected or expired"
+    },
+    {
+      "code": 6032,
+      "name": "unauthorizedPayer",
+      "msg": "Signer is not the payer of this UTXO"
+    },
+    {
+      "code": 6033,
+      "name": "zkProofMismatch",
+      "msg": "ZK proof account does not match the UTXO"
+    },
+    {
+      "code": 6034,
+      "name": "zkProofAlreadyInitialized",
+      "msg": "ZK proof account already initialized"
+    },
+    {
+      "code": 6035,
+      "name": "zkProofNotInitialized",
+      "msg": "ZK proof account not initialized"
+    },
+    {
+      "code": 6036,
+      "name": "utxoNotSettled",
+      "msg": "UTXO is still pending"
+    },
+    {
+      "code": 6037,
+      "name": "zkProofInvalidOwner",
+      "msg": "ZK proof account is not owned by the program"
+    },
+    {
+      "code": 6038,
+      "name": "zkProofIncomplete",
+      "msg": "ZK proof has not been fully written"
+    },
+    {
+      "code": 6039,
+      "name": "zkProofAlreadyLocked",
+      "msg": "ZK proof is already used by another UTXO"
+    },
+    {
+      "code": 6040,
+      "name": "unauthorizedWriter",
+      "msg": "Signer is not the authority of this upload account"
+    },
+    {
+      "code": 6041,
+      "name": "unauthorizedFaucet",
+      "msg": "Unauthorized: Only the faucet authority can airdrop"
+    },
+    {
+      "code": 6042,
+      "name": "airdropVaultLimitReached",
+      "msg": "Vault has reached its airdrop limit for this epoch"
+    },
+    {
+      "code": 6043,
+      "name": "airdropEpochLimitReached",
+      "msg": "Faucet has reached its airdrop limit for this epoch"
+    },
+    {
+      "code": 6044,
+      "name": "insufficientAdminSignatures",
+      "msg": "Not enough admin signers signed this instruction"
+    },
+    {
+      "code": 6045,
+      "name": "unauthorizedPendingAdmin",
+      "msg": "Signer is not the pending admin"
+    },
+    {
+      "code": 6046,
+      "name": "invalidAdminSigners",
+      "msg": "Invalid admin signer set or threshold"
+    },
+    {
+      "code": 6047,
+      "name": "proverAlreadyActive",
+      "msg": "Prover already active"
+    },
+    {
+      "code": 6048,
+      "name": "proverStillActive",
+      "msg": "Prover must be deactivated before it is removed"
+    },
+    {
+      "code": 6049,
+      "name": "invalidSlashBps",
+      "msg": "Slash share must be at most 10000 basis points"
+    },
+    {
+      "code": 6050,
+      "name": "invalidUnbondingPeriod",
+      "msg": "Unbonding period can't be negative"
+    },
+    {
+      "code": 6051,
+      "name": "unauthorizedStakeOwner",
+      "msg": "Stake account belongs to another owner"
+    },
+    {
+      "code": 6052,
+      "name": "stakeMismatch",
+      "msg": "Stake account does not belong to this prover"
+    },
+    {
+      "code": 6053,
+      "name": "insufficientStake",
+      "msg": "Prover stake is below the minimum"
+    },
+    {
+      "code": 6054,
+      "name": "unbondingNotEnded",
+      "msg": "Unbonding period has not ended"
+    },
+    {
+      "code": 6055,
+      "name": "noSlashableVote",
+      "msg": "Prover has no vote against the UTXO's outcome to slash"
+    },
+    {
+      "code": 6056,
+      "name": "unslashedVotes",
+      "msg": "Wrong votes on this UTXO must be slashed first"
+    },
+    {
+      "code": 6057,
+      "name": "rewardAccountsMismatch",
+      "msg": "Stake accounts don't match the provers that decided the UTXO"
+    },
+    {
+      "code": 6058,
+      "name": "noRewards",
+      "msg": "No attestation rewards to claim"
+    },
+    {
+      "code": 6059,
+      "name": "staleLedgerTip",
+      "msg": "UTXO links to a ledger tip that is no longer recent"
+    },
+    {
+      "code": 6060,
+      "name": "ledgerPageMismatch",
+      "msg": "Ledger page accounts don't match the pages being appended to"
+    },
+    {
+      "code": 6061,
+      "name": "staleEpoch",
+      "msg": "UTXO declares an epoch that is expired or hasn't started"
+    },
+    {
+      "code": 6062,
+      "name": "alreadyMigrated",
+      "msg": "Account already has its current layout"
+    },
+    {
+      "code": 6063,
+      "name": "nothingToSweep",
+      "msg": "No slashed lamports to sweep"
+    },
+    {
+      "code": 6064,
+      "name": "invalidGenesisTimestamp",
+      "msg": "Genesis timestamp is in the future"
+    },
+    {
+      "code": 6065,
+      "name": "legacyUtxoOutOfOrder",
+      "msg": "Finalized legacy UTXO does not link to the last one replayed"
+    }
+  ],
+  "types": [
+    {
+      "name": "adminAccepted",
+      "docs": [
+        "Event emitted when a proposed admin takes over"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "previousAdmin",
+            "docs": [
+              "Admin authority before the handoff"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "newAdmin",
+            "docs": [
+              "New admin authority"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when accepted"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "adminProposed",
+      "docs": [
+        "Event emitted when a new admin is proposed"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin who made the proposal"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingAdmin",
+            "docs": [
+              "Proposed admin, None when a proposal was cancelled"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when proposed"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "adminSignersUpdated",
+      "docs": [
+        "Event emitted when the admin signer set changes"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin signer who submitted the change"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "signers",
+            "docs": [
+              "New admin signer set"
+            ],
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "threshold",
+            "docs": [
+              "Admin signers required per instruction"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when updated"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "airdropCompleted",
+      "docs": [
+        "Event emitted when an airdrop is completed"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "utxo",
+            "docs": [
+              "UTXO account PDA"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "vault",
+            "docs": [
+              "Vault receiving the airdrop"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "utxoHash",
+            "docs": [
+              "UTXO hash"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "prevUtxoHash",
+            "docs": [
+              "Previous UTXO hash"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "epoch",
+            "docs": [
+              "Epoch number"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "payloadSize",
+            "docs": [
+              "Encrypted payload size"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "ciphertextCommitment",
+            "docs": [
+              "Ciphertext commitment"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "UTXO PDA bump"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "newLedgerCount",
+            "docs": [
+              "New ledger count after airdrop"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when created"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "name": "zkProofInitialized",
-      "discriminator": [
-        41,
-        89,
-        183,
-        101,
-        104,
-        233,
-        152,
-        10
-      ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "invalidKeyLength",
-      "msg": "Kyber Public key must be exactly 1184 bytes long"
+      "name": "airdropEpochLimit",
+      "docs": [
+        "Airdrops made program-wide in the current Solana epoch",
+        "PDA: [\"airdrop_epoch\"]"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "epoch",
+            "docs": [
+              "Solana epoch the count belongs to"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "count",
+            "docs": [
+              "Airdrops made in `epoch`"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump seed for PDA"
+            ],
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6001,
-      "name": "chunkSizeExceeded",
-      "msg": "Chunk size exceeds maximum allowed length of 800 bytes"
+      "name": "airdropVaultLimit",
+      "docs": [
+        "Airdrops received by one vault in the current Solana epoch",
+        "PDA: [\"airdrop_vault\", vault]"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "docs": [
+              "Vault receiving the airdrops"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "epoch",
+            "docs": [
+              "Solana epoch the count belongs to"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "count",
+            "docs": [
+              "Airdrops received in `epoch`"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump seed for PDA"
+            ],
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6002,
-      "name": "hashMismatch",
-      "msg": "Hash Mismatch: Provided key does not match the expected hash"
+      "name": "attestationSubmitted",
+      "docs": [
+        "Event emitted when an attestation is submitted"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "utxo",
+            "docs": [
+              "The UTXO being voted on"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "utxoHash",
+            "docs": [
+              "UTXO hash"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "proverOld",
+            "docs": [
+              "Old prover's public key (before rotation)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "prover",
+            "docs": [
+              "New prover's public key (after rotation)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "proverUniqueId",
+            "docs": [
+              "Prover's unique ID"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "vote",
+            "docs": [
+              "The vote (true=valid, false=invalid)"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "nextKeyHash",
+            "docs": [
+              "Hash of prover's next public key (for next rotation)"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "lamportsTransferred",
+            "docs": [
+              "Lamports transferred"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "validVotes",
+            "docs": [
+              "Current count of valid votes"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "invalidVotes",
+            "docs": [
+              "Current count of invalid votes"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "totalVotes",
+            "docs": [
+              "Total votes received"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "thresholdMet",
+            "docs": [
+              "Whether threshold was met"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "newLedgerTip",
+            "docs": [
+              "New ledger tip hash (if threshold met)"
+            ],
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          },
+          {
+            "name": "newLedgerCount",
+            "docs": [
+              "New ledger count (if threshold met)"
+            ],
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "feePaid",
+            "docs": [
+              "Escrowed attestation fee paid to the deciding provers (if this vote decided the UTXO)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when submitted"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6003,
-      "name": "invalidPreviousUtxoHash",
-      "msg": "Invalid previous UTXO hash provided"
+      "name": "configUpdate",
+      "docs": [
+        "Config fields to change, `None` leaves a field as it is"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minAttestations",
+            "docs": [
+              "Valid votes needed to finalize a UTXO"
+            ],
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "minRejections",
+            "docs": [
+              "Invalid votes that reject a UTXO"
+            ],
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "faucetAuthority",
+            "docs": [
+              "Only signer allowed to airdrop"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "maxAirdropsPerVault",
+            "docs": [
+              "Airdrops a single vault can receive per Solana epoch, 0 pauses the faucet"
+            ],
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "maxAirdropsPerEpoch",
+            "docs": [
+              "Airdrops the faucet can make per Solana epoch, 0 pauses the faucet"
+            ],
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "minProverStake",
+            "docs": [
+              "Lamports a prover must have staked to vote"
+            ],
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "slashBps",
+            "docs": [
+              "Share of a prover's stake slashed per wrong vote, in basis points"
+            ],
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "unbondingPeriod",
+            "docs": [
+              "Seconds unstaked lamports wait before they can be withdrawn"
+            ],
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "attestationFee",
+            "docs": [
+              "Lamports escrowed per UTXO for the provers that decide it"
+            ],
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6004,
-      "name": "payloadTooLarge",
-      "msg": "Payload too large"
+      "name": "configUpdated",
+      "docs": [
+        "Event emitted when the program config is updated"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin who updated the config"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "minAttestations",
+            "docs": [
+              "Minimum attestations required for UTXO validity"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "minRejections",
+            "docs": [
+              "Invalid votes required to reject a UTXO"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "faucetAuthority",
+            "docs": [
+              "Only signer allowed to airdrop"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "maxAirdropsPerVault",
+            "docs": [
+              "Airdrops a single vault can receive per Solana epoch"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "maxAirdropsPerEpoch",
+            "docs": [
+              "Airdrops the faucet can make per Solana epoch"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "minProverStake",
+            "docs": [
+              "Lamports a prover must have staked to vote"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "slashBps",
+            "docs": [
+              "Share of a prover's stake slashed per wrong vote, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "unbondingPeriod",
+            "docs": [
+              "Seconds unstaked lamports wait before they can be withdrawn"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "attestationFee",
+            "docs": [
+              "Lamports escrowed per UTXO for the provers that decide it"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when updated"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6005,
-      "name": "arithmeticOverflow",
-      "msg": "Arithmetic overflow occurred"
+      "name": "ledger",
+      "docs": [
+        "Ledger account that sequences finalized UTXOs",
+        "UTXOs are ordered when they are finalized, not when they are proven: a proof may link to",
+        "any of the last LEDGER_TIP_WINDOW tips, so transfers proven in parallel can all finalize."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "count",
+            "docs": [
+              "Total number of valid UTXOs"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastValidUtxoHash",
+            "docs": [
+              "Hash of the last valid UTXO (genesis is all zeros), where wallets start walking back"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump seed for PDA"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "tipHash",
+            "docs": [
+              "Running hash over every finalized UTXO hash in ledger order (genesis is all zeros)",
+              "tip = SHA256(previous tip || utxo_hash), proofs show their inputs lead up to one of these"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "recentTips",
+            "docs": [
+              "The last LEDGER_TIP_WINDOW tips, a new UTXO may link to any of them"
+            ],
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                64
+              ]
+            }
+          },
+          {
+            "name": "genesisTimestamp",
+            "docs": [
+              "Unix timestamp the ledger was initialized at, epoch 0 starts then"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "legacyRemaining",
+            "docs": [
+              "Finalized UTXOs of a migrated ledger not yet replayed onto the running tip"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "legacyCursor",
+            "docs": [
+              "Hash of the last legacy UTXO replayed, the next one must link to it (genesis is all zeros)"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6006,
-      "name": "invalidOffset",
-      "msg": "Invalid offset: must match current bytes_written"
+      "name": "ledgerInitialized",
+      "docs": [
+        "Event emitted when the ledger is initialized"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "count",
+            "docs": [
+              "Initial count (should be 0)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "genesisHash",
+            "docs": [
+              "Genesis hash (all zeros)"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Ledger PDA bump"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when initialized"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "programConfigAlreadyInitialized",
-      "msg": "Program config already initialized"
+      "name": "ledgerMigrated",
+      "docs": [
+        "Event emitted when an old ledger is migrated"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin who migrated the ledger"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "legacyCount",
+            "docs": [
+              "Finalized UTXOs left to replay onto the running tip with migrate_utxo"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "genesisTimestamp",
+            "docs": [
+              "Genesis timestamp kept from the original deployment"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "oldSize",
+            "docs": [
+              "Account size before the migration"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "newSize",
+            "docs": [
+              "Account size after the migration"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when migrated"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6008,
-      "name": "adminAuthorityNotSet",
-      "msg": "Admin authority not set"
+      "name": "loader",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "docs": [
+              "Account that created the loader, the only one allowed to write chunks"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "ciphertext",
+            "type": {
+              "array": [
+                "u8",
+                1088
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6009,
-      "name": "unauthorizedAdmin",
-      "msg": "Unauthorized: Only admin can perform this action"
+      "name": "loaderChunkWritten",
+      "docs": [
+        "Event emitted when a chunk is written to the loader"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "loader",
+            "docs": [
+              "Loader account address"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "chunkSize",
+            "docs": [
+              "Chunk size in bytes"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "offset",
+            "docs": [
+              "Offset where chunk was written"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "end",
+            "docs": [
+              "End position after write"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when written"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6010,
-      "name": "proverAlreadyRegistered",
-      "msg": "Prover already registered"
+      "name": "loaderInitialized",
+      "docs": [
+        "Event emitted when a loader is initialized"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "loader",
+            "docs": [
+              "Loader account address"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "size",
+            "docs": [
+              "Size of the loader account"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when initialized"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6011,
-      "name": "proverNotRegistered",
-      "msg": "Prover not registered"
+      "name": "nullifierSpent",
+      "docs": [
+        "Event emitted when a nullifier is spent"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nullifier",
+            "docs": [
+              "The nullifier committed by the guest"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "nullifierAccount",
+            "docs": [
+              "Nullifier account PDA"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "utxoHash",
+            "docs": [
+              "Hash of the UTXO that spent it"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when spent"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6012,
-      "name": "proverNotActive",
-      "msg": "Prover not active"
+      "name": "programConfig",
+      "docs": [
+        "Program configuration account",
+        "Fields after `bump` were appended to the original layout, configs created before them",
+        "are brought up to SIZE by migrate_program_config"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "adminAuthority",
+            "docs": [
+              "Admin authority who can manage provers",
+              "Ignored while an admin signer set is configured"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "minAttestations",
+            "docs": [
+              "Minimum attestations required for UTXO validity"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump seed for PDA"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "minRejections",
+            "docs": [
+              "Invalid votes that reject a UTXO, raised to the safe minimum by rejection_quorum",
+              "0 on configs created before the field existed"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "faucetAuthority",
+            "docs": [
+              "Only signer allowed to call airdrop"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "maxAirdropsPerVault",
+            "docs": [
+              "Airdrops a single vault can receive per Solana epoch"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "maxAirdropsPerEpoch",
+            "docs": [
+              "Airdrops the faucet can make per Solana epoch across all vaults"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "minProverStake",
+            "docs": [
+              "Lamports a prover must have staked to vote"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "slashBps",
+            "docs": [
+              "Share of a prover's stake slashed per wrong vote, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "unbondingPeriod",
+            "docs": [
+              "Seconds unstaked lamports wait before they can be withdrawn"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "attestationFee",
+            "docs": [
+              "Lamports escrowed by create_utxo and transfer, split among the provers that decide the UTXO"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "pendingAdmin",
+            "docs": [
+              "Admin proposed with propose_admin, takes over once it calls accept_admin"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "adminSigners",
+            "docs": [
+              "M-of-N admin signer set, empty when a single admin_authority is in charge"
+            ],
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "adminThreshold",
+            "docs": [
+              "Admin signers that must sign an admin instruction"
+            ],
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6013,
-      "name": "proverAlreadyVoted",
-      "msg": "Prover already voted on this UTXO"
+      "name": "programConfigMigrated",
+      "docs": [
+        "Event emitted when an old program config is migrated"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin who migrated the config"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "oldSize",
+            "docs": [
+              "Account size before the migration"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "newSize",
+            "docs": [
+              "Account size after the migration"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when migrated"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6014,
-      "name": "maxProversReached",
-      "msg": "Maximum provers reached"
+      "name": "programInitialized",
+      "docs": [
+        "Event emitted when the program is initialized"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin authority who initialized the program"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "minAttestations",
+            "docs": [
+              "Minimum attestations required for UTXO validity"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "minRejections",
+            "docs": [
+              "Invalid votes required to reject a UTXO"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "configBump",
+            "docs": [
+              "Program config PDA bump"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "registryBump",
+            "docs": [
+              "Prover registry PDA bump"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when initialized"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6015,
-      "name": "maxVotesReached",
-      "msg": "Maximum votes reached for this UTXO"
+      "name": "proverDeactivated",
+      "docs": [
+        "Event emitted when a prover is deactivated"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin who deactivated the prover"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "proverPubkey",
+            "docs": [
+              "Prover's public key that was deactivated"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "proverPubkeyHash",
+            "docs": [
+              "Hash of prover's public key"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when deactivated"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6016,
-      "name": "invalidStakeAmount",
-      "msg": "Invalid stake amount"
+      "name": "proverInfo",
+      "docs": [
+        "Information about a single prover"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "uniqueId",
+            "docs": [
+              "Unique identifier for the prover"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "pubkeyHash",
+            "docs": [
+              "Hash of prover's current public key"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "isActive",
+            "docs": [
+              "Whether the prover is currently active"
+            ],
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "code": 6017,
-      "name": "proverUniqueIdAlreadyUsed",
-      "msg": "Prover unique ID already used"
+      "name": "proverKeyReset",
+      "docs": [
+        "Event emitted when a prover's key is force-reset"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin who reset the key"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "uniqueId",
+            "docs": [
+              "Unique identifier for the prover"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "newPubkeyHash",
+            "docs": [
+              "Hash of the key the prover signs with next"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when reset"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6018,
-      "name": "invalidProverUniqueId",
-      "msg": "Invalid prover unique ID. Cannot be zero."
+      "name": "proverReactivated",
+      "docs": [
+        "Event emitted when a prover is reactivated"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin who reactivated the prover"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "uniqueId",
+            "docs": [
+              "Unique identifier for the prover"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "activeProvers",
+            "docs": [
+              "Number of active provers after reactivation"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when reactivated"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6019,
-      "name": "zkProofAccountNotProvided",
-      "msg": "ZK Proof account not provided"
+      "name": "proverRegistered",
+      "docs": [
+        "Event emitted when a new prover is registered"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin who registered the prover"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "uniqueId",
+            "docs": [
+              "Unique identifier for the prover"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "proverPubkey",
+            "docs": [
+              "Prover's public key"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "proverPubkeyHash",
+            "docs": [
+              "Hash of prover's public key"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "totalProvers",
+            "docs": [
+              "Total number of provers after registration"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "staker",
+            "docs": [
+              "Account that locked the stake"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "stakeAmount",
+            "docs": [
+              "Lamports staked after registration"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when registered"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6020,
-      "name": "utxoHashMismatch",
-      "msg": "UTXO hash mismatch with ledger"
+      "name": "proverRegistry",
+      "docs": [
+        "Prover registry account"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "proverCount",
+            "docs": [
+              "Number of registered provers"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump seed for PDA derivation"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "provers",
+            "docs": [
+              "Array of prover information"
+            ],
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "proverInfo"
+                }
+              }
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6021,
-      "name": "minAttestationsNotMet",
-      "msg": "Min attestations not met"
-    }
-  ],
-  "types": [
-    {
-      "name": "airdropCompleted",
+      "name": "proverRemoved",
       "docs": [
-        "Event emitted when an airdrop is completed"
+        "Event emitted when a prover is removed"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "utxo",
+            "name": "admin",
             "docs": [
-              "UTXO account PDA"
+              "Admin who removed the prover"
             ],
             "type": "pubkey"
           },
           {
-            "name": "utxoHash",
+            "name": "uniqueId",
             "docs": [
-              "UTXO hash"
+              "Unique identifier of the removed prover"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "proverPubkeyHash",
+            "docs": [
+              "Key hash the prover had when removed"
             ],
             "type": {
               "array": [
@@ -1335,9 +5093,55 @@ export type SolanaPrograms = {
             }
           },
           {
-            "name": "prevUtxoHash",
+            "name": "totalProvers",
             "docs": [
-              "Previous UTXO hash"
+              "Total number of provers after removal"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when removed"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "proverSlashed",
+      "docs": [
+        "Event emitted when a prover's stake is slashed"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "docs": [
+              "Account that submitted the slash"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "uniqueId",
+            "docs": [
+              "Unique identifier of the slashed prover"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "utxo",
+            "docs": [
+              "UTXO account carrying the wrong vote"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "utxoHash",
+            "docs": [
+              "Hash of the UTXO"
             ],
             "type": {
               "array": [
@@ -1347,196 +5151,249 @@ export type SolanaPrograms = {
             }
           },
           {
-            "name": "epoch",
+            "name": "amount",
             "docs": [
-              "Epoch number"
+              "Lamports slashed"
             ],
-            "type": "u32"
+            "type": "u64"
           },
           {
-            "name": "payloadSize",
+            "name": "remainingStake",
             "docs": [
-              "Encrypted payload size"
+              "Lamports still staked"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "remainingUnbonding",
+            "docs": [
+              "Lamports still unbonding"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when slashed"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "proverStake",
+      "docs": [
+        "SOL a prover locks to back its votes",
+        "PDA: [\"prover_stake\", unique_id]. Keyed by unique ID so it survives key rotation.",
+        "The account keeps rent + amount + unbonding_amount + rewards lamports and is never closed,",
+        "so a prover's wrong votes stay slashable after it withdraws."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "uniqueId",
+            "docs": [
+              "Unique ID of the prover in the registry"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "owner",
+            "docs": [
+              "Account that funded the stake, the only one allowed to withdraw it"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Staked lamports, counted towards the minimum stake needed to vote"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "unbondingAmount",
+            "docs": [
+              "Lamports waiting out the unbonding period, still slashable"
             ],
-            "type": "u32"
+            "type": "u64"
           },
           {
-            "name": "ciphertextCommitment",
+            "name": "unbondingEndsAt",
             "docs": [
-              "Ciphertext commitment"
+              "Unix timestamp when unbonding_amount can be withdrawn"
             ],
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "type": "i64"
           },
           {
-            "name": "bump",
+            "name": "slashedAmount",
             "docs": [
-              "UTXO PDA bump"
+              "Lamports slashed so far"
             ],
-            "type": "u8"
+            "type": "u64"
           },
           {
-            "name": "newLedgerCount",
+            "name": "rewards",
             "docs": [
-              "New ledger count after airdrop"
+              "Attestation fees earned and not yet claimed, never slashed"
             ],
             "type": "u64"
           },
           {
-            "name": "timestamp",
+            "name": "bump",
             "docs": [
-              "Timestamp when created"
+              "Bump seed for PDA"
             ],
-            "type": "i64"
+            "type": "u8"
           }
         ]
       }
     },
     {
-      "name": "attestationSubmitted",
+      "name": "proverVote",
       "docs": [
-        "Event emitted when an attestation is submitted"
+        "Vote record for a prover on a UTXO"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "utxo",
+            "name": "proverId",
             "docs": [
-              "The UTXO being voted on"
+              "Unique ID of the prover"
             ],
-            "type": "pubkey"
+            "type": "u64"
           },
           {
-            "name": "utxoHash",
+            "name": "isValid",
             "docs": [
-              "UTXO hash"
+              "Vote value: true = valid, false = invalid"
             ],
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardsClaimed",
+      "docs": [
+        "Event emitted when attestation rewards are claimed"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "proverOld",
+            "name": "owner",
             "docs": [
-              "Old prover's public key (before rotation)"
+              "Stake owner who received the rewards"
             ],
             "type": "pubkey"
           },
           {
-            "name": "proverOldHash",
-            "docs": [
-              "Old prover's hash"
-            ],
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
-          {
-            "name": "proverNew",
+            "name": "uniqueId",
             "docs": [
-              "New prover's public key (after rotation)"
+              "Unique identifier of the prover"
             ],
-            "type": "pubkey"
+            "type": "u64"
           },
           {
-            "name": "proverUniqueId",
+            "name": "amount",
             "docs": [
-              "Prover's unique ID"
+              "Lamports claimed"
             ],
             "type": "u64"
           },
           {
-            "name": "vote",
+            "name": "timestamp",
             "docs": [
-              "The vote (true=valid, false=invalid)"
+              "Timestamp when claimed"
             ],
-            "type": "bool"
-          },
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "slashedSwept",
+      "docs": [
+        "Event emitted when slashed lamports are swept"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "nextKeyHash",
+            "name": "admin",
             "docs": [
-              "Hash of prover's next public key (for next rotation)"
+              "Admin signer who swept the lamports"
             ],
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "type": "pubkey"
           },
           {
-            "name": "lamportsTransferred",
+            "name": "destination",
             "docs": [
-              "Lamports transferred"
+              "Account that received them"
             ],
-            "type": "u64"
+            "type": "pubkey"
           },
           {
-            "name": "validVotes",
+            "name": "amount",
             "docs": [
-              "Current count of valid votes"
+              "Lamports swept"
             ],
-            "type": "u16"
+            "type": "u64"
           },
           {
-            "name": "invalidVotes",
+            "name": "timestamp",
             "docs": [
-              "Current count of invalid votes"
+              "Timestamp when swept"
             ],
-            "type": "u16"
-          },
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "stakeWithdrawn",
+      "docs": [
+        "Event emitted when unbonded stake is withdrawn"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "totalVotes",
+            "name": "owner",
             "docs": [
-              "Total votes received"
+              "Stake owner who received the lamports"
             ],
-            "type": "u16"
+            "type": "pubkey"
           },
           {
-            "name": "thresholdMet",
+            "name": "uniqueId",
             "docs": [
-              "Whether threshold was met"
+              "Unique identifier of the prover"
             ],
-            "type": "bool"
+            "type": "u64"
           },
           {
-            "name": "newLedgerTip",
+            "name": "amount",
             "docs": [
-              "New ledger tip hash (if threshold met)"
+              "Lamports withdrawn"
             ],
-            "type": {
-              "option": {
-                "array": [
-                  "u8",
-                  32
-                ]
-              }
-            }
+            "type": "u64"
           },
           {
-            "name": "newLedgerCount",
+            "name": "remainingStake",
             "docs": [
-              "New ledger count (if threshold met)"
+              "Lamports still staked"
             ],
-            "type": {
-              "option": "u64"
-            }
+            "type": "u64"
           },
           {
             "name": "timestamp",
             "docs": [
-              "Timestamp when submitted"
+              "Timestamp when withdrawn"
             ],
             "type": "i64"
           }
@@ -1544,24 +5401,24 @@ export type SolanaPrograms = {
       }
     },
     {
-      "name": "ledger",
+      "name": "transferCreated",
       "docs": [
-        "Ledger account that tracks the last valid UTXO hash"
+        "Event emitted when a transfer is created"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "count",
+            "name": "receiverUtxo",
             "docs": [
-              "Total number of valid UTXOs"
+              "Receiver UTXO account PDA, the one provers vote on"
             ],
-            "type": "u64"
+            "type": "pubkey"
           },
           {
-            "name": "lastValidUtxoHash",
+            "name": "receiverUtxoHash",
             "docs": [
-              "Hash of the last valid UTXO (genesis is all zeros)"
+              "Receiver UTXO hash"
             ],
             "type": {
               "array": [
@@ -1571,34 +5428,28 @@ export type SolanaPrograms = {
             }
           },
           {
-            "name": "bump",
+            "name": "changeUtxo",
             "docs": [
-              "Bump seed for PDA"
+              "Change UTXO account PDA, finalized with the receiver UTXO"
             ],
-            "type": "u8"
-          }
-        ]
-      }
-    },
-    {
-      "name": "ledgerInitialized",
-      "docs": [
-        "Event emitted when the ledger is initialized"
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
+            "type": "pubkey"
+          },
           {
-            "name": "count",
+            "name": "changeUtxoHash",
             "docs": [
-              "Initial count (should be 0)"
+              "Change UTXO hash"
             ],
-            "type": "u64"
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
           },
           {
-            "name": "genesisHash",
+            "name": "prevUtxoHash",
             "docs": [
-              "Genesis hash (all zeros)"
+              "Ledger tip the receiver UTXO links to"
             ],
             "type": {
               "array": [
@@ -1608,16 +5459,23 @@ export type SolanaPrograms = {
             }
           },
           {
-            "name": "bump",
+            "name": "zkProof",
             "docs": [
-              "Ledger PDA bump"
+              "ZK proof reference"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "nullifierCount",
+            "docs": [
+              "Number of nullifiers spent when the transfer is finalized"
             ],
             "type": "u8"
           },
           {
             "name": "timestamp",
             "docs": [
-              "Timestamp when initialized"
+              "Timestamp when created"
             ],
             "type": "i64"
           }
@@ -1625,16 +5483,54 @@ export type SolanaPrograms = {
       }
     },
     {
-      "name": "loader",
+      "name": "transferOutput",
+      "docs": [
+        "One output of a transfer, as produced by prepare_output"
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "ciphertext",
+            "name": "utxoHash",
             "type": {
               "array": [
                 "u8",
-                1088
+                32
+              ]
+            }
+          },
+          {
+            "name": "encryptedPayload",
+            "type": "bytes"
+          },
+          {
+            "name": "nonce",
+            "type": {
+              "array": [
+                "u8",
+                12
+              ]
+            }
+          },
+          {
+            "name": "ciphertextCommitment",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "epoch",
+            "type": "u32"
+          },
+          {
+            "name": "outputHash",
+            "type": {
+              "array": [
+                "u8",
+                32
               ]
             }
           }
@@ -1642,45 +5538,59 @@ export type SolanaPrograms = {
       }
     },
     {
-      "name": "loaderChunkWritten",
+      "name": "unstakeRequested",
       "docs": [
-        "Event emitted when a chunk is written to the loader"
+        "Event emitted when part of a prover's stake starts unbonding"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "loader",
+            "name": "owner",
             "docs": [
-              "Loader account address"
+              "Stake owner"
             ],
             "type": "pubkey"
           },
           {
-            "name": "chunkSize",
+            "name": "uniqueId",
+            "docs": [
+              "Unique identifier of the prover"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Lamports moved into unbonding"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "remainingStake",
             "docs": [
-              "Chunk size in bytes"
+              "Lamports still staked"
             ],
-            "type": "u32"
+            "type": "u64"
           },
           {
-            "name": "offset",
+            "name": "unbondingAmount",
             "docs": [
-              "Offset where chunk was written"
+              "Total lamports unbonding"
             ],
-            "type": "u32"
+            "type": "u64"
           },
           {
-            "name": "end",
+            "name": "unbondingEndsAt",
             "docs": [
-              "End position after write"
+              "Unix timestamp when the unbonding lamports can be withdrawn"
             ],
-            "type": "u32"
+            "type": "i64"
           },
           {
             "name": "timestamp",
             "docs": [
-              "Timestamp when written"
+              "Timestamp when requested"
             ],
             "type": "i64"
           }
@@ -1688,58 +5598,116 @@ export type SolanaPrograms = {
       }
     },
     {
-      "name": "loaderInitialized",
+      "name": "utxo",
       "docs": [
-        "Event emitted when a loader is initialized"
+        "UTXO account structure",
+        "Used for both regular UTXOs (with voting) and airdrop UTXOs (without voting)",
+        "Fields added after the first release are appended after `bump`, accounts created before",
+        "them are brought up to date by migrate_utxo"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "loader",
+            "name": "epoch",
             "docs": [
-              "Loader account address"
+              "Epoch number"
             ],
-            "type": "pubkey"
+            "type": "u32"
           },
           {
-            "name": "size",
+            "name": "utxoHash",
             "docs": [
-              "Size of the loader account"
+              "Hash of this UTXO"
             ],
-            "type": "u32"
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
           },
           {
-            "name": "timestamp",
+            "name": "prevUtxoHash",
             "docs": [
-              "Timestamp when initialized"
+              "Ledger tip the proof linked this UTXO to, any recent tip at creation time"
             ],
-            "type": "i64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "programConfig",
-      "docs": [
-        "Program configuration account"
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
           {
-            "name": "adminAuthority",
+            "name": "ciphertextCommitment",
             "docs": [
-              "Admin authority who can manage provers"
+              "SHA256 commitment of ciphertext+payload+nonce"
             ],
-            "type": "pubkey"
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
           },
           {
-            "name": "minAttestations",
+            "name": "nonce",
             "docs": [
-              "Minimum attestations required for UTXO validity"
+              "ChaCha20 nonce for decryption"
             ],
-            "type": "u16"
+            "type": {
+              "array": [
+                "u8",
+                12
+              ]
+            }
+          },
+          {
+            "name": "encryptedPayload",
+            "docs": [
+              "Encrypted payload data"
+            ],
+            "type": "bytes"
+          },
+          {
+            "name": "kyberCiphertext",
+            "docs": [
+              "Kyber ciphertext for shared secret"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                1088
+              ]
+            }
+          },
+          {
+            "name": "zkProofPubkey",
+            "docs": [
+              "Reference to ZK proof account (None for airdrop UTXOs)"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "votes",
+            "docs": [
+              "Map of prover votes (None for airdrop UTXOs - immediately finalized)"
+            ],
+            "type": {
+              "option": {
+                "array": [
+                  {
+                    "defined": {
+                      "name": "proverVote"
+                    }
+                  },
+                  10
+                ]
+              }
+            }
           },
           {
             "name": "bump",
@@ -1747,82 +5715,83 @@ export type SolanaPrograms = {
               "Bump seed for PDA"
             ],
             "type": "u8"
-          }
-        ]
-      }
-    },
-    {
-      "name": "programInitialized",
-      "docs": [
-        "Event emitted when the program is initialized"
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
+          },
           {
-            "name": "admin",
+            "name": "outputHash",
             "docs": [
-              "Admin authority who initialized the program"
+              "SHA256(kyber_ciphertext || nonce || encrypted_payload), the guest commits the same hash"
             ],
-            "type": "pubkey"
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
           },
           {
-            "name": "minAttestations",
+            "name": "nullifiers",
             "docs": [
-              "Minimum attestations required for UTXO validity"
+              "Nullifiers of the inputs spent by this UTXO (empty for airdrop UTXOs)"
             ],
-            "type": "u16"
+            "type": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
           },
           {
-            "name": "configBump",
+            "name": "changeUtxo",
             "docs": [
-              "Program config PDA bump"
+              "Change UTXO of the same transfer, finalized together with this one"
             ],
-            "type": "u8"
+            "type": {
+              "option": "pubkey"
+            }
           },
           {
-            "name": "registryBump",
+            "name": "payer",
             "docs": [
-              "Prover registry PDA bump"
+              "Account that paid the rent, refunded if the UTXO is rejected"
             ],
-            "type": "u8"
+            "type": "pubkey"
           },
           {
-            "name": "timestamp",
+            "name": "status",
             "docs": [
-              "Timestamp when initialized"
+              "Pending until the attestation or rejection threshold is reached"
             ],
-            "type": "i64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "proverDeactivated",
-      "docs": [
-        "Event emitted when a prover is deactivated"
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
+            "type": {
+              "defined": {
+                "name": "utxoStatus"
+              }
+            }
+          },
           {
-            "name": "admin",
+            "name": "attestationFee",
             "docs": [
-              "Admin who deactivated the prover"
+              "Escrowed lamports paid to the deciding provers, 0 once paid or for unvoted UTXOs"
             ],
-            "type": "pubkey"
+            "type": "u64"
           },
           {
-            "name": "proverPubkey",
+            "name": "ledgerPrevUtxoHash",
             "docs": [
-              "Prover's public key that was deactivated"
+              "UTXO finalized right before this one, zeros until finalized or for the first UTXO"
             ],
-            "type": "pubkey"
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
           },
           {
-            "name": "proverPubkeyHash",
+            "name": "ledgerPrevTip",
             "docs": [
-              "Hash of prover's public key"
+              "Ledger tip right before this UTXO was appended, zeros until finalized"
             ],
             "type": {
               "array": [
@@ -1832,34 +5801,48 @@ export type SolanaPrograms = {
             }
           },
           {
-            "name": "timestamp",
+            "name": "ledgerIndex",
             "docs": [
-              "Timestamp when deactivated"
+              "Position on the ledger (0-based), locates the UTXO in the ledger index pages",
+              "0 until finalized, check `status` before trusting it"
             ],
-            "type": "i64"
+            "type": "u64"
+          },
+          {
+            "name": "slashedVotes",
+            "docs": [
+              "Set for each vote slot once the prover was slashed for that vote",
+              "Kept out of ProverVote so the votes keep the layout existing accounts were written with"
+            ],
+            "type": {
+              "array": [
+                "bool",
+                10
+              ]
+            }
           }
         ]
       }
     },
     {
-      "name": "proverInfo",
+      "name": "utxoCreated",
       "docs": [
-        "Information about a single prover"
+        "Event emitted when a UTXO is created"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "uniqueId",
+            "name": "utxo",
             "docs": [
-              "Unique identifier for the prover"
+              "UTXO account PDA"
             ],
-            "type": "u64"
+            "type": "pubkey"
           },
           {
-            "name": "pubkeyHash",
+            "name": "utxoHash",
             "docs": [
-              "Hash of prover's current public key"
+              "UTXO hash"
             ],
             "type": {
               "array": [
@@ -1869,48 +5852,42 @@ export type SolanaPrograms = {
             }
           },
           {
-            "name": "isActive",
+            "name": "prevUtxoHash",
             "docs": [
-              "Whether the prover is currently active"
+              "Previous UTXO hash"
             ],
-            "type": "bool"
-          }
-        ]
-      }
-    },
-    {
-      "name": "proverRegistered",
-      "docs": [
-        "Event emitted when a new prover is registered"
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
           {
-            "name": "admin",
+            "name": "epoch",
             "docs": [
-              "Admin who registered the prover"
+              "Epoch number"
             ],
-            "type": "pubkey"
+            "type": "u32"
           },
           {
-            "name": "uniqueId",
+            "name": "payloadSize",
             "docs": [
-              "Unique identifier for the prover"
+              "Encrypted payload size"
             ],
-            "type": "u64"
+            "type": "u32"
           },
           {
-            "name": "proverPubkey",
+            "name": "zkProof",
             "docs": [
-              "Prover's public key"
+              "ZK proof reference"
             ],
             "type": "pubkey"
           },
           {
-            "name": "proverPubkeyHash",
+            "name": "ciphertextCommitment",
             "docs": [
-              "Hash of prover's public key"
+              "Ciphertext commitment"
             ],
             "type": {
               "array": [
@@ -1920,16 +5897,16 @@ export type SolanaPrograms = {
             }
           },
           {
-            "name": "totalProvers",
+            "name": "bump",
             "docs": [
-              "Total number of provers after registration"
+              "UTXO PDA bump"
             ],
-            "type": "u32"
+            "type": "u8"
           },
           {
             "name": "timestamp",
             "docs": [
-              "Timestamp when registered"
+              "Timestamp when created"
             ],
             "type": "i64"
           }
@@ -1937,112 +5914,100 @@ export type SolanaPrograms = {
       }
     },
     {
-      "name": "proverRegistry",
+      "name": "utxoMigrated",
       "docs": [
-        "Prover registry account"
+        "Event emitted when an old UTXO is migrated"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "proverCount",
+            "name": "admin",
             "docs": [
-              "Number of registered provers"
+              "Admin who migrated the UTXO"
             ],
-            "type": "u32"
+            "type": "pubkey"
           },
           {
-            "name": "bump",
+            "name": "utxo",
             "docs": [
-              "Bump seed for PDA derivation"
+              "UTXO account"
             ],
-            "type": "u8"
+            "type": "pubkey"
           },
           {
-            "name": "provers",
+            "name": "utxoHash",
             "docs": [
-              "Array of prover information"
+              "Hash of the UTXO"
             ],
             "type": {
-              "vec": {
-                "defined": {
-                  "name": "proverInfo"
-                }
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "status",
+            "docs": [
+              "Status rebuilt from its votes"
+            ],
+            "type": {
+              "defined": {
+                "name": "utxoStatus"
               }
             }
-          }
-        ]
-      }
-    },
-    {
-      "name": "proverVote",
-      "docs": [
-        "Vote record for a prover on a UTXO"
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
+          },
           {
-            "name": "proverId",
+            "name": "ledgerIndex",
             "docs": [
-              "Unique ID of the prover"
+              "Position it was replayed at on the ledger, 0 unless finalized"
             ],
             "type": "u64"
           },
           {
-            "name": "isValid",
+            "name": "oldSize",
             "docs": [
-              "Vote value: true = valid, false = invalid"
+              "Account size before the migration"
             ],
-            "type": "bool"
+            "type": "u32"
+          },
+          {
+            "name": "newSize",
+            "docs": [
+              "Account size after the migration"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when migrated"
+            ],
+            "type": "i64"
           }
         ]
       }
     },
     {
-      "name": "utxo",
+      "name": "utxoReclaimed",
       "docs": [
-        "UTXO account structure",
-        "Used for both regular UTXOs (with voting) and airdrop UTXOs (without voting)"
+        "Event emitted when a rejected UTXO is reclaimed"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "epoch",
+            "name": "utxo",
             "docs": [
-              "Epoch number"
+              "UTXO account PDA that was closed"
             ],
-            "type": "u32"
+            "type": "pubkey"
           },
           {
             "name": "utxoHash",
             "docs": [
-              "Hash of this UTXO"
-            ],
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
-          {
-            "name": "prevUtxoHash",
-            "docs": [
-              "Hash of previous UTXO"
-            ],
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
-          {
-            "name": "ciphertextCommitment",
-            "docs": [
-              "SHA256 commitment of ciphertext+payload+nonce"
+              "UTXO hash"
             ],
             "type": {
               "array": [
@@ -2052,77 +6017,42 @@ export type SolanaPrograms = {
             }
           },
           {
-            "name": "nonce",
-            "docs": [
-              "ChaCha20 nonce for decryption"
-            ],
-            "type": {
-              "array": [
-                "u8",
-                12
-              ]
-            }
-          },
-          {
-            "name": "encryptedPayload",
-            "docs": [
-              "Encrypted payload data"
-            ],
-            "type": "bytes"
-          },
-          {
-            "name": "kyberCiphertext",
+            "name": "changeUtxo",
             "docs": [
-              "Kyber ciphertext for shared secret"
+              "Change UTXO closed along with it, if created by transfer"
             ],
             "type": {
-              "array": [
-                "u8",
-                1088
-              ]
+              "option": "pubkey"
             }
           },
           {
-            "name": "zkProofPubkey",
+            "name": "payer",
             "docs": [
-              "Reference to ZK proof account (None for airdrop UTXOs)"
+              "Payer who received the rent"
             ],
-            "type": {
-              "option": "pubkey"
-            }
+            "type": "pubkey"
           },
           {
-            "name": "votes",
+            "name": "lamportsReclaimed",
             "docs": [
-              "Map of prover votes (None for airdrop UTXOs - immediately finalized)"
+              "Total lamports returned to the payer"
             ],
-            "type": {
-              "option": {
-                "array": [
-                  {
-                    "defined": {
-                      "name": "proverVote"
-                    }
-                  },
-                  10
-                ]
-              }
-            }
+            "type": "u64"
           },
           {
-            "name": "bump",
+            "name": "timestamp",
             "docs": [
-              "Bump seed for PDA"
+              "Timestamp when reclaimed"
             ],
-            "type": "u8"
+            "type": "i64"
           }
         ]
       }
     },
     {
-      "name": "utxoCreated",
+      "name": "utxoRejected",
       "docs": [
-        "Event emitted when a UTXO is created"
+        "Event emitted when a UTXO is rejected"
       ],
       "type": {
         "kind": "struct",
@@ -2147,67 +6077,75 @@ export type SolanaPrograms = {
             }
           },
           {
-            "name": "prevUtxoHash",
+            "name": "changeUtxo",
             "docs": [
-              "Previous UTXO hash"
+              "Change UTXO rejected along with it, if created by transfer"
             ],
             "type": {
-              "array": [
-                "u8",
-                32
-              ]
+              "option": "pubkey"
             }
           },
           {
-            "name": "epoch",
-            "docs": [
-              "Epoch number"
-            ],
-            "type": "u32"
-          },
-          {
-            "name": "payloadSize",
+            "name": "payer",
             "docs": [
-              "Encrypted payload size"
+              "Account that can reclaim the rent"
             ],
-            "type": "u32"
+            "type": "pubkey"
           },
           {
-            "name": "zkProof",
+            "name": "validVotes",
             "docs": [
-              "ZK proof reference"
+              "Number of valid votes"
             ],
-            "type": "pubkey"
+            "type": "u16"
           },
           {
-            "name": "ciphertextCommitment",
+            "name": "invalidVotes",
             "docs": [
-              "Ciphertext commitment"
+              "Number of invalid votes"
             ],
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "type": "u16"
           },
           {
-            "name": "bump",
+            "name": "expired",
             "docs": [
-              "UTXO PDA bump"
+              "Settled as UtxoStatus::Expired, no vote on it is slashable"
             ],
-            "type": "u8"
+            "type": "bool"
           },
           {
             "name": "timestamp",
             "docs": [
-              "Timestamp when created"
+              "Timestamp when rejected"
             ],
             "type": "i64"
           }
         ]
       }
     },
+    {
+      "name": "utxoStatus",
+      "docs": [
+        "Lifecycle of a UTXO"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "pending"
+          },
+          {
+            "name": "finalized"
+          },
+          {
+            "name": "rejected"
+          },
+          {
+            "name": "expired"
+          }
+        ]
+      }
+    },
     {
       "name": "vault",
       "type": {
@@ -2371,6 +6309,52 @@ export type SolanaPrograms = {
         ]
       }
     },
+    {
+      "name": "zkProofClosed",
+      "docs": [
+        "Event emitted when a ZK proof account is closed"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "zkProof",
+            "docs": [
+              "ZK proof account that was closed"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "utxo",
+            "docs": [
+              "Settled UTXO that referenced the proof"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "payer",
+            "docs": [
+              "Payer who received the lamports"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "lamports",
+            "docs": [
+              "Lamports returned to the payer"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp when closed"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
     {
       "name": "zkProofInitialized",
       "docs": [
@@ -7,6 +7,17 @@ import { Buffer } from 'buffer';
 const RPC_URL = "http://127.0.0.1:8899";
 const PROGRAM_ID = new PublicKey("QCashfSHwqptwFRSbqjBnxYH7GbDzbAfxVeDGXhL1fv");
 
+// Ledger index page layout, must match LedgerPage in the Solana program
+const LEDGER_PAGE_SIZE = 256;
+const LEDGER_PAGE_LEN_OFFSET = 16;
+const LEDGER_PAGE_HEADER_SIZE = 20;
+
+// Length of a ledger epoch in seconds, must match EPOCH_DURATION in the Solana program
+const EPOCH_DURATION = 24 * 60 * 60;
+
+// Most accounts a single getMultipleAccounts call returns
+const MAX_MULTIPLE_ACCOUNTS = 100;
+
 // UTXO Status based on voting
 export type UtxoStatus = 'finalized' | 'pending' | 'voted';
 
@@ -24,6 +35,10 @@ export interface DecryptedUtxo {
     amount: number;
     isReturn: boolean;
     randomness: number[];
+    // Rolling hash over the inputs this UTXO spent, needed to spend it
+    historyRoot: number[];
+    // Payload version, version 1 UTXOs are spent with the spent list their commitment covers
+    version: number;
     utxoSpentList: number[][];
     // Public Data
     header: UtxoHeader;
@@ -35,8 +50,21 @@ export interface DecryptedUtxo {
     index: number;
 }
 
+// One finalized UTXO on the path from a spent input to the ledger tip
+export interface LedgerLink {
+    utxo_hash: number[];
+}
+
+// Membership witness for the guest: the tip before the oldest input, then every UTXO after it
+export interface LedgerPath {
+    start: number[];
+    links: LedgerLink[];
+}
+
 export interface SyncResult {
     utxos: DecryptedUtxo[];
+    // Last ledger page scanned, pass it as fromPage to resume from there
+    lastPage: number;
     confirmedBalance: number;
     pendingBalance: number;
     totalBalance: number;
@@ -72,10 +100,6 @@ function determineUtxoStatus(votes: any): { status: UtxoStatus; voteCount: numbe
     }
 }
 
-function isGenesisHash(hash: number[] | Uint8Array): boolean {
-    return Array.from(hash).every(byte => byte === 0);
-}
-
 function deriveUtxoPda(utxoHash: Uint8Array): PublicKey {
     const [pda] = PublicKey.findProgramAddressSync(
         [Buffer.from("utxo"), utxoHash],
@@ -84,6 +108,16 @@ function deriveUtxoPda(utxoHash: Uint8Array): PublicKey {
     return pda;
 }
 
+function deriveLedgerPagePda(page: number): PublicKey {
+    const pageBytes = Buffer.alloc(8);
+    pageBytes.writeBigUInt64LE(BigInt(page));
+    const [pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("ledger_page"), pageBytes],
+        PROGRAM_ID
+    );
+    return pda;
+}
+
 function deriveLedgerPda(): PublicKey {
     const [pda] = PublicKey.findProgramAddressSync(
         [Buffer.from("ledger")],
@@ -92,6 +126,58 @@ function deriveLedgerPda(): PublicKey {
     return pda;
 }
 
+function chunk<T>(items: T[], size: number): T[][] {
+    const chunks: T[][] = [];
+    for (let i = 0; i < items.length; i += size) {
+        chunks.push(items.slice(i, i + size));
+    }
+    return chunks;
+}
+
+// Fetches the finalized UTXO hashes stored in the given ledger index pages, in page order
+async function fetchLedgerPageHashes(connection: Connection, pages: number[]): Promise<Uint8Array[]> {
+    const hashes: Uint8Array[] = [];
+
+    for (const batch of chunk(pages, MAX_MULTIPLE_ACCOUNTS)) {
+        const accounts = await connection.getMultipleAccountsInfo(batch.map(deriveLedgerPagePda));
+
+        accounts.forEach((account, i) => {
+            if (!account) {
+                throw new Error(`Ledger page ${batch[i]} not found`);
+            }
+            const data = Buffer.from(account.data);
+            const len = data.readUInt32LE(LEDGER_PAGE_LEN_OFFSET);
+            for (let slot = 0; slot < len; slot++) {
+                const start = LEDGER_PAGE_HEADER_SIZE + slot * 32;
+                hashes.push(new Uint8Array(data.subarray(start, start + 32)));
+            }
+        });
+    }
+
+    return hashes;
+}
+
+// Finalized UTXO hashes from ledger index `from` (inclusive) to `to` (exclusive)
+async function fetchLedgerRange(connection: Connection, from: number, to: number): Promise<Uint8Array[]> {
+    if (from >= to) {
+        return [];
+    }
+
+    const firstPage = Math.floor(from / LEDGER_PAGE_SIZE);
+    const lastPage = Math.floor((to - 1) / LEDGER_PAGE_SIZE);
+    const pages = Array.from({ length: lastPage - firstPage + 1 }, (_, i) => firstPage + i);
+    const hashes = await fetchLedgerPageHashes(connection, pages);
+
+    const offset = from - firstPage * LEDGER_PAGE_SIZE;
+    return hashes.slice(offset, offset + (to - from));
+}
+
+// Ledger epoch new UTXOs declare, counted from the ledger's genesis timestamp
+// Outputs may still finalize during the next epoch, so small clock skew is harmless
+export function currentEpoch(genesisTimestamp: number, nowSeconds: number = Date.now() / 1000): number {
+    return Math.max(0, Math.floor((nowSeconds - genesisTimestamp) / EPOCH_DURATION));
+}
+
 // Create a read-only provider for syncing
 function createReadOnlyProvider(connection: Connection): anchor.AnchorProvider {
     return new anchor.AnchorProvider(connection, {} as any, {
@@ -100,109 +186,115 @@ function createReadOnlyProvider(connection: Connection): anchor.AnchorProvider {
 }
 
 // Shared sync function that can be used by both frontend hook and background
-// Uses linked list traversal: starts from ledger head, follows prevUtxoHash
-// Stops when finding a finalized return UTXO or reaching genesis
+// Reads the ledger index pages newest first, batching UTXO fetches with getMultipleAccounts
+// Stops when finding a finalized return UTXO or reaching fromPage
 export async function syncUtxos(
     secretKey: Uint8Array,
-    tryDecryptUtxo: (secretKey: Uint8Array, ciphertext: Uint8Array, nonce: Uint8Array, payload: Uint8Array, index: number) => any
+    tryDecryptUtxo: (secretKey: Uint8Array, ciphertext: Uint8Array, nonce: Uint8Array, payload: Uint8Array, index: number) => any,
+    fromPage: number = 0
 ): Promise<SyncResult> {
     const connection = new Connection(RPC_URL, "confirmed");
     const provider = createReadOnlyProvider(connection);
     const program = new anchor.Program<SolanaPrograms>(idl as SolanaPrograms, provider);
 
-    // Fetch Ledger to get chain head
+    // Fetch Ledger to get the number of finalized UTXOs
     const ledgerPda = deriveLedgerPda();
     console.log("Sync: Fetching ledger");
 
     const ledger = await program.account.ledger.fetch(ledgerPda);
-    const headHash = new Uint8Array(ledger.lastValidUtxoHash);
+    const count = Number(ledger.count.toString());
 
-    console.log(`Sync: Ledger tip hash: ${Buffer.from(headHash).toString('hex').slice(0, 16)}...`);
+    console.log(`Sync: Ledger has ${count} finalized UTXOs`);
 
     // Check if ledger is empty (genesis state)
-    if (isGenesisHash(headHash)) {
+    if (count === 0) {
         console.log("Sync: Ledger is empty (genesis state)");
         return {
             utxos: [],
+            lastPage: 0,
             confirmedBalance: 0,
             pendingBalance: 0,
             totalBalance: 0
         };
     }
 
+    const lastPage = Math.floor((count - 1) / LEDGER_PAGE_SIZE);
     const myUtxos: DecryptedUtxo[] = [];
-    let currentHash = headHash;
     let index = 0;
     let shouldStop = false;
 
-    // Traverse linked list from head (newest) to tail (oldest)
-    while (!isGenesisHash(currentHash) && !shouldStop) {
-        const utxoPda = deriveUtxoPda(currentHash);
+    // Walk the pages from the newest to fromPage, MAX_MULTIPLE_ACCOUNTS pages at a time
+    for (let pageEnd = lastPage; pageEnd >= fromPage && !shouldStop; pageEnd -= MAX_MULTIPLE_ACCOUNTS) {
+        const pageStart = Math.max(fromPage, pageEnd - MAX_MULTIPLE_ACCOUNTS + 1);
+        const pages = Array.from({ length: pageEnd - pageStart + 1 }, (_, i) => pageStart + i);
 
-        try {
-            const rawUtxo = await program.account.utxo.fetch(utxoPda);
+        // Newest first
+        const hashes = (await fetchLedgerPageHashes(connection, pages)).reverse();
 
-            // Try to decrypt this UTXO
-            try {
-                const ciphertext = Uint8Array.from(rawUtxo.kyberCiphertext);
-                const nonce = Uint8Array.from(rawUtxo.nonce);
-                const payload = Uint8Array.from(rawUtxo.encryptedPayload);
-
-                const decrypted = tryDecryptUtxo(secretKey, ciphertext, nonce, payload, index);
-
-                // Reconstruct spent list
-                const flatList = Array.from(decrypted.utxo_spent_list) as number[];
-                const reconstructedList: number[][] = [];
-                for (let j = 0; j < decrypted.spent_list_len; j++) {
-                    const start = j * 32;
-                    const end = start + 32;
-                    reconstructedList.push(flatList.slice(start, end));
+        for (const batch of chunk(hashes, MAX_MULTIPLE_ACCOUNTS)) {
+            if (shouldStop) {
+                break;
+            }
+            const accounts = await connection.getMultipleAccountsInfo(batch.map(deriveUtxoPda));
+
+            for (let i = 0; i < accounts.length && !shouldStop; i++) {
+                const account = accounts[i];
+                if (!account) {
+                    // Indexed UTXOs are never closed, this shouldn't happen in a valid ledger
+                    console.error(`Sync: Failed to fetch UTXO at hash ${Buffer.from(batch[i]).toString('hex').slice(0, 16)}...`);
+                    index++;
+                    continue;
                 }
-
-                // Determine status from votes
-                const { status, voteCount } = determineUtxoStatus(rawUtxo.votes);
-                const utxoHashHex = Buffer.from(rawUtxo.utxoHash).toString('hex');
-
-                const decryptedUtxo: DecryptedUtxo = {
-                    amount: Number(decrypted.amount),
-                    isReturn: decrypted.is_return,
-                    randomness: Array.from(decrypted.randomness),
-                    index,
-                    utxoHashHex,
-                    utxoSpentList: reconstructedList,
-                    status,
-                    voteCount,
-                    header: {
-                        utxoHash: Array.from(rawUtxo.utxoHash),
-                        prevUtxoHash: Array.from(rawUtxo.prevUtxoHash),
-                        ciphertextCommitment: Array.from(rawUtxo.ciphertextCommitment),
-                        epoch: rawUtxo.epoch,
-                        kyberCiphertext: Array.from(rawUtxo.kyberCiphertext),
-                        nonce: Array.from(rawUtxo.nonce)
+                const rawUtxo = program.coder.accounts.decode("utxo", account.data);
+
+                // Try to decrypt this UTXO
+                try {
+                    const ciphertext = Uint8Array.from(rawUtxo.kyberCiphertext);
+                    const nonce = Uint8Array.from(rawUtxo.nonce);
+                    const payload = Uint8Array.from(rawUtxo.encryptedPayload);
+
+                    const decrypted = tryDecryptUtxo(secretKey, ciphertext, nonce, payload, index);
+
+                    // Determine status from votes
+                    const { status, voteCount } = determineUtxoStatus(rawUtxo.votes);
+                    const utxoHashHex = Buffer.from(rawUtxo.utxoHash).toString('hex');
+
+                    const decryptedUtxo: DecryptedUtxo = {
+                        amount: Number(decrypted.amount),
+                        isReturn: decrypted.is_return,
+                        randomness: Array.from(decrypted.randomness),
+                        index,
+                        utxoHashHex,
+                        historyRoot: Array.from(decrypted.history_root) as number[],
+                        version: decrypted.version,
+                        utxoSpentList: chunk(Array.from(decrypted.utxo_spent_list) as number[], 32),
+                        status,
+                        voteCount,
+                        header: {
+                            utxoHash: Array.from(rawUtxo.utxoHash),
+                            prevUtxoHash: Array.from(rawUtxo.prevUtxoHash),
+                            ciphertextCommitment: Array.from(rawUtxo.ciphertextCommitment),
+                            epoch: rawUtxo.epoch,
+                            kyberCiphertext: Array.from(rawUtxo.kyberCiphertext),
+                            nonce: Array.from(rawUtxo.nonce)
+                        }
+                    };
+
+                    myUtxos.push(decryptedUtxo);
+
+                    // Stop condition: finalized (votes=null) AND is_return=true
+                    // This means we've found our last confirmed return UTXO
+                    if (status === 'finalized' && decrypted.is_return) {
+                        console.log(`Sync: Found finalized return UTXO at index ${index}, stopping traversal`);
+                        shouldStop = true;
                     }
-                };
-
-                myUtxos.push(decryptedUtxo);
 
-                // Stop condition: finalized (votes=null) AND is_return=true
-                // This means we've found our last confirmed return UTXO
-                if (status === 'finalized' && decrypted.is_return) {
-                    console.log(`Sync: Found finalized return UTXO at index ${index}, stopping traversal`);
-                    shouldStop = true;
+                } catch (e) {
+                    // Decryption failed = Not our UTXO, continue traversal
                 }
 
-            } catch (e) {
-                // Decryption failed = Not our UTXO, continue traversal
+                index++;
             }
-
-            // Move to previous UTXO in chain
-            currentHash = new Uint8Array(rawUtxo.prevUtxoHash);
-            index++;
-
-        } catch (e) {
-            // UTXO account not found, this shouldn't happen in a valid chain
-            console.error(`Sync: Failed to fetch UTXO at hash ${Buffer.from(currentHash).toString('hex').slice(0, 16)}...`);
-            break;
         }
     }
 
@@ -224,8 +316,36 @@ export async function syncUtxos(
 
     return {
         utxos: myUtxos,
+        lastPage,
         confirmedBalance,
         pendingBalance,
         totalBalance: confirmedBalance + pendingBalance
     };
 }
+
+// Builds the ledger membership witness for the guest
+// Reads every UTXO finalized after the oldest input from the ledger index pages (oldest first),
+// up to ledgerCount so the path re-hashes to the tip read together with it
+export async function fetchLedgerPath(
+    ledgerCount: number,
+    oldestInputHash: Uint8Array
+): Promise<LedgerPath> {
+    const connection = new Connection(RPC_URL, "confirmed");
+    const provider = createReadOnlyProvider(connection);
+    const program = new anchor.Program<SolanaPrograms>(idl as SolanaPrograms, provider);
+
+    const oldestInput = await program.account.utxo.fetch(deriveUtxoPda(oldestInputHash));
+    const oldestIndex = Number(oldestInput.ledgerIndex.toString());
+    const hashes = await fetchLedgerRange(connection, oldestIndex, ledgerCount);
+
+    // Pending UTXOs aren't indexed yet, their ledger index still points at slot 0
+    if (hashes.length === 0 || Buffer.compare(Buffer.from(hashes[0]), Buffer.from(oldestInputHash)) !== 0) {
+        throw new Error("Input UTXO is not on the ledger chain");
+    }
+
+    return {
+        // The guest starts re-hashing the ledger tip from right before the oldest input
+        start: Array.from(oldestInput.ledgerPrevTip),
+        links: hashes.map(hash => ({ utxo_hash: Array.from(hash) })),
+    };
+}
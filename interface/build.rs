@@ -71,6 +71,12 @@ struct TypeDef {
 struct TypeDefType {
     kind: String,
     fields: Option<Vec<TypeDefField>>,
+    variants: Option<Vec<EnumVariant>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnumVariant {
+    name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -168,6 +174,18 @@ fn generate_types_mod(types: &[TypeDef]) -> String {
                 }
             }
 
+            code.push_str("    }\n\n");
+        } else if ty.ty.kind == "enum" {
+            // Only unit variants are used by the program
+            code.push_str("    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]\n");
+            code.push_str(&format!("    pub enum {} {{\n", ty.name));
+
+            if let Some(variants) = &ty.ty.variants {
+                for variant in variants {
+                    code.push_str(&format!("        {},\n", variant.name));
+                }
+            }
+
             code.push_str("    }\n\n");
         }
     }
@@ -240,12 +258,15 @@ fn generate_accounts_mod(instructions: &[Instruction], program_id: &str) -> Stri
                         "arg" => {
                             if let Some(path) = &seed.path {
                                 let param_name = path.replace(".", "_");
-                                let arg_ty = arg_type_map
-                                    .get(path.as_str())
-                                    .expect("PDA seed arg not found in instruction args");
+                                // Nested paths (e.g. `receiver.utxo_hash`) point into a struct arg,
+                                // those seeds are taken as raw bytes
+                                let arg_ty = arg_type_map.get(path.as_str()).copied();
+                                if arg_ty.is_none() && !path.contains('.') {
+                                    panic!("PDA seed arg not found in instruction args");
+                                }
 
                                 match arg_ty {
-                                    ArgType::Simple(s)
+                                    Some(ArgType::Simple(s))
                                         if s.starts_with('u') || s.starts_with('i') =>
                                     {
                                         seed_params.push(format!("{}: {}", param_name, s));
@@ -254,21 +275,21 @@ fn generate_accounts_mod(instructions: &[Instruction], program_id: &str) -> Stri
                                             param_name
                                         ));
                                     }
-                                    ArgType::Simple(s) if s == "string" => {
+                                    Some(ArgType::Simple(s)) if s == "string" => {
                                         seed_params.push(format!("{}: &str", param_name));
                                         seed_code
                                             .push(format!("            {}.as_bytes()", param_name));
                                     }
-                                    ArgType::Simple(s) if s == "bytes" => {
+                                    Some(ArgType::Simple(s)) if s == "bytes" => {
                                         seed_params.push(format!("{}: &[u8]", param_name));
                                         seed_code.push(format!("            {}", param_name));
                                     }
-                                    ArgType::Simple(s) if s == "publicKey" || s == "pubkey" => {
+                                    Some(ArgType::Simple(s)) if s == "publicKey" || s == "pubkey" => {
                                         seed_params.push(format!("{}: Pubkey", param_name));
                                         seed_code
                                             .push(format!("            {}.as_ref()", param_name));
                                     }
-                                    ArgType::Array { .. } => {
+                                    Some(ArgType::Array { .. }) => {
                                         seed_params.push(format!("{}: &[u8]", param_name));
                                         seed_code.push(format!("            {}", param_name));
                                     }
@@ -430,6 +451,7 @@ fn main() {
     let out_dir = env::var("OUT_DIR").unwrap();
 
     println!("cargo:rerun-if-changed={}", idl_path);
+    println!("cargo:rerun-if-env-changed=IDL_PATH");
 
     let idl_content = fs::read_to_string(&idl_path)
         .unwrap_or_else(|_| panic!("Failed to read IDL file at {}", idl_path));
@@ -0,0 +1,109 @@
+// Business Source License 1.1 (BSL 1.1)
+// Licensor: Bonsol Labs Inc.
+// Licensed Work: QCash
+// Change Date: 2030-12-31
+// Change License: Apache License 2.0
+// Use of this software is governed by the LICENSE file.
+
+// Compares guest cycle counts for each sender key mode.
+// Only executes the guest, no proving, so it runs in seconds.
+//
+//   cargo bench -p methods --bench guest_cycles
+
+use methods::GUEST_ELF;
+use qcash_core::wallet::WalletKeys;
+use qcash_core::{
+    DecryptedInput, QSPVGuestInput, SenderKeyWitness, UTXOCommitmentHeader, UTXOEncryptedPayload,
+    HASH, PAYLOAD_VERSION,
+};
+use risc0_zkvm::{default_executor, ExecutorEnv};
+use sha2::{Digest, Sha256};
+
+// The guest never checks the PDA is off-curve, so any program id and bump will do
+const PROGRAM_ID: [u8; 32] = [7u8; 32];
+const VAULT_BUMP: u8 = 255;
+
+fn main() {
+    let sender = WalletKeys::new();
+    let receiver = WalletKeys::new();
+
+    let sender_vault = vault_pda(&sender.kyber_key.public);
+    let receiver_vault = vault_pda(&receiver.kyber_key.public);
+
+    let modes = [
+        ("public key only", None),
+        ("secret entropy", Some(SenderKeyWitness::SecretEntropy(sender.secret_entropy, sender.kyber_version))),
+    ];
+
+    println!("{:<16} {:>12} {:>9}", "mode", "cycles", "segments");
+
+    for (label, witness) in modes {
+        // The input is the first UTXO on the ledger, so the membership path is empty
+        let input = input_utxo(sender_vault, 100);
+        let ledger_path_start = [0u8; 32];
+        let current_ledger_tip: [u8; 32] =
+            Sha256::new().chain_update(ledger_path_start).chain_update(input.header.utxo_hash).finalize().into();
+
+        let inputs = QSPVGuestInput {
+            sender_kyber_pubkey: sender.kyber_key.public,
+            sender_key_witness: witness,
+            input_utxos: vec![input],
+            receiver_pubkey: receiver.kyber_key.public,
+            amount_to_send: 40,
+            receiver_randomness: [1u8; 32],
+            return_randomness: [2u8; 32],
+            receiver_encapsulation_seed: [4u8; 32],
+            receiver_nonce: [5u8; 12],
+            return_encapsulation_seed: [6u8; 32],
+            return_nonce: [7u8; 12],
+            current_ledger_tip,
+            epoch: 0,
+            ledger_path_start,
+            ledger_path: vec![],
+            solana_program_id: PROGRAM_ID,
+            vault_bump: VAULT_BUMP,
+            receiver_vault,
+        };
+
+        let env = ExecutorEnv::builder()
+            .write(&inputs)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let session = default_executor()
+            .execute(env, GUEST_ELF)
+            .expect("guest execution failed");
+
+        println!("{:<16} {:>12} {:>9}", label, session.cycles(), session.segments.len());
+    }
+}
+
+// Same derivation as the guest: sha256("vault" || sha256(pubkey) || bump || program_id || marker)
+fn vault_pda(kyber_pubkey: &[u8]) -> [u8; 32] {
+    let vault_hash: HASH = Sha256::digest(kyber_pubkey).into();
+    let mut hasher = Sha256::new();
+    hasher.update(b"vault");
+    hasher.update(vault_hash);
+    hasher.update([VAULT_BUMP]);
+    hasher.update(PROGRAM_ID);
+    hasher.update(b"ProgramDerivedAddress");
+    hasher.finalize().into()
+}
+
+fn input_utxo(receiver_vault: [u8; 32], amount: u64) -> DecryptedInput {
+    let payload = UTXOEncryptedPayload {
+        amount,
+        is_return: false,
+        receiver_vault,
+        randomness: [3u8; 32],
+        history_root: [0u8; 32],
+        version: PAYLOAD_VERSION,
+    };
+
+    DecryptedInput {
+        header: UTXOCommitmentHeader::compute::<Sha256>(&payload, [0u8; 32], 0),
+        payload,
+        utxo_spent_list: None,
+    }
+}
@@ -8,7 +8,7 @@
 #![no_main]
 
 use risc0_zkvm::{guest::{env, sha::rust_crypto::{Digest, Sha256}}, sha::Impl};
-use qcash_core::{QSPVGuestInput, QspvGuestOutput, UTXOEncryptedPayload, UTXOCommitmentHeader, HASH};
+use qcash_core::{derive_kyber_key, encrypt_output, fold_history, QSPVGuestInput, QspvGuestOutput, SenderKeyWitness, UTXOEncryptedPayload, UTXOCommitmentHeader, HASH, PAYLOAD_VERSION};
 
 risc0_zkvm::guest::entry!(main);
 
@@ -18,48 +18,94 @@ fn main(){
     // Use the pre-derived Kyber pubkey passed from frontend
     // This avoids expensive key derivation inside the ZK circuit
     let my_pubkey = inputs.sender_kyber_pubkey;
+
+    // Prove we hold the secret behind that pubkey, when a witness is provided
+    let sender_key_proven = match &inputs.sender_key_witness {
+        Some(SenderKeyWitness::SecretEntropy(entropy, version)) => {
+            let derived = derive_kyber_key(entropy, *version);
+            if derived.public != my_pubkey {
+                panic!("Key Error: Secret entropy does not derive the sender public key");
+            }
+            true
+        }
+        None => false,
+    };
     
     // Hash the Kyber pubkey (1184 bytes) 
     let my_vault_hash = hash_pubkey(&my_pubkey);
 
+    // Derive the sender's vault PDA: ["vault", sha256(kyber_pubkey)] under the qcash program
+    let my_vault_pda = derive_vault_pda(&my_vault_hash, &inputs.solana_program_id, inputs.vault_bump);
+
 
     let mut total_in_amount:u64 = 0;
 
-    let mut propagated_history:Vec<HASH> = Vec::new();
+    // Constant size spend history of the outputs, folded over the inputs in order
+    let mut history_root:HASH = [0u8; 32];
+
+    let mut spent_inputs:Vec<HASH> = Vec::new();
+
+    let mut nullifiers:Vec<HASH> = Vec::new();
+
+    // Ledger membership
+    // Re-hash the running ledger tip from the oldest input forward, every UTXO appended on the way is on the ledger
+    let oldest_input = match inputs.input_utxos.first() {
+        Some(utxo) => utxo.header.utxo_hash,
+        None => panic!("No Inputs: Nothing to spend"),
+    };
+    let mut ledger_tip = hash_ledger_tip(&inputs.ledger_path_start, &oldest_input);
+    let mut ledger_members:Vec<HASH> = vec![oldest_input];
+    for link in &inputs.ledger_path {
+        ledger_tip = hash_ledger_tip(&ledger_tip, &link.utxo_hash);
+        ledger_members.push(link.utxo_hash);
+    }
+
+    if ledger_tip != inputs.current_ledger_tip {
+        panic!("Ledger Error: Path from the oldest input does not reach the ledger tip");
+    }
 
 
     for utxo in &inputs.input_utxos{
-        
-        // Integrity Check (commitment == Hash(payload))
+
+        // Integrity Check (commitment == Hash(payload), utxo_hash == Hash(commitment || prev || epoch))
+        // The commitment is hashed with the encoding of the payload's version, version 1 inputs stay spendable
         // This proves: "The data I am showing matches the encrypted data "
-        let calculated_payload_hash = hash_payload(&utxo.payload);
+        if let Err(e) = utxo.verify::<Sha256<Impl>>() {
+            panic!("Integrity Error: {}", e);
+        }
 
-        if calculated_payload_hash != utxo.header.ciphertext_commitment {
-            panic!("Integrity Error: Payload does not match Commitment Header");
+        // Epoch Check
+        // Inputs come from the ledger, so none can be newer than the epoch we create outputs in
+        if utxo.header.epoch > inputs.epoch {
+            panic!("Epoch Error: Input epoch {} is after the current epoch {}", utxo.header.epoch, inputs.epoch);
+        }
+
+        // Verify Membership
+        // This proves: "The note I am spending was finalized on the ledger"
+        if !ledger_members.contains(&utxo.header.utxo_hash) {
+            panic!("Ledger Error: UTXO {:?} is not on the path to the ledger tip", utxo.header.utxo_hash);
         }
 
         // Verify Ownership
-        // if utxo.payload.receiver_vault != my_vault_pda {
-        //    panic!("Ownership Error: This UTXO belongs to address {:?}, but I derived {:?}", utxo.payload.receiver_vault, my_vault_pda);
-        // }
-
-        // Spend list propogation
-        // Union of all prev history
-        for past_hash in &utxo.payload.utxo_spent_list{
-            if propagated_history.contains(past_hash) {
-                // If a past hash is ALREADY in our current history, it means
-                // two inputs share the same ancestor. This is a double spend.
-                panic!("Double Spend Detected: Ancestor collision");
-            }
-            propagated_history.push(*past_hash);
+        // This proves: "Every note I am spending was addressed to my vault"
+        if utxo.payload.receiver_vault != my_vault_pda {
+            panic!("Ownership Error: This UTXO belongs to address {:?}, but I derived {:?}", utxo.payload.receiver_vault, my_vault_pda);
         }
 
-        // mark this input as spent
-        // Append current UTXO hash
-        if propagated_history.contains(&utxo.header.utxo_hash){
-            panic!("Double Spend Detected: UTXO is already in history (Cycle)");
+        // An input listed twice would be counted twice
+        // Inputs spent by earlier transfers are caught on chain by their nullifiers
+        if spent_inputs.contains(&utxo.header.utxo_hash){
+            panic!("Double Spend Detected: UTXO is spent twice in this transfer");
         }
-        propagated_history.push(utxo.header.utxo_hash);
+        spent_inputs.push(utxo.header.utxo_hash);
+
+        // History propagation
+        // Fold this input and its own history into the outputs' history root
+        history_root = fold_history::<Sha256<Impl>>(&history_root, &utxo.payload.history_root, &utxo.header.utxo_hash);
+
+        // Publish a nullifier so the chain can see this note is spent
+        // Only someone who knows the private randomness can link it back to the utxo_hash
+        nullifiers.push(hash_nullifier(&utxo.header.utxo_hash, &utxo.payload.randomness));
 
         total_in_amount += utxo.payload.amount;
     }
@@ -79,34 +125,55 @@ fn main(){
         // Todo: It is being calculated by the frontend.
         receiver_vault: inputs.receiver_vault,
         randomness: inputs.receiver_randomness,
-        utxo_spent_list: propagated_history.clone(),
-        version: 1,
+        history_root,
+        version: PAYLOAD_VERSION,
     };
 
     // The new utxo links to the global tip
-    let receiver_header = create_header(&receiver_payload,inputs.current_ledger_tip,1);
+    let receiver_header = UTXOCommitmentHeader::compute::<Sha256<Impl>>(&receiver_payload, inputs.current_ledger_tip, inputs.epoch);
 
     // Output B: Return UTXO
     let return_payload = UTXOEncryptedPayload{
         amount:return_amount,
         is_return:true,
-        receiver_vault: my_vault_hash,
+        // Change goes back to our own vault so it passes the ownership check when spent
+        receiver_vault: my_vault_pda,
         randomness: inputs.return_randomness,
-        utxo_spent_list: propagated_history,
-        version: 1,
+        history_root,
+        version: PAYLOAD_VERSION,
     };
 
     // The second output links to the first output we just created
-    let return_header = create_header(
+    let return_header = UTXOCommitmentHeader::compute::<Sha256<Impl>>(
         &return_payload,
         receiver_header.utxo_hash,
-        1 // epoch
+        inputs.epoch
     );
 
+    // Re-run the encryption WASM performed, from the same seeds and nonces.
+    // This proves: "The ciphertext posted on chain decrypts to the payload committed above"
+    let receiver_encrypted = encrypt_output(
+        &inputs.receiver_pubkey,
+        &receiver_payload,
+        &inputs.receiver_encapsulation_seed,
+        &inputs.receiver_nonce,
+    ).unwrap_or_else(|e| panic!("Encryption Error: Receiver output: {}", e));
+
+    let return_encrypted = encrypt_output(
+        &my_pubkey,
+        &return_payload,
+        &inputs.return_encapsulation_seed,
+        &inputs.return_nonce,
+    ).unwrap_or_else(|e| panic!("Encryption Error: Return output: {}", e));
+
     // Commit
     let output = QspvGuestOutput{
         receiver_commitment: receiver_header,
         return_commitment: return_header,
+        receiver_output_hash: receiver_encrypted.hash::<Sha256<Impl>>(),
+        return_output_hash: return_encrypted.hash::<Sha256<Impl>>(),
+        sender_key_proven,
+        nullifiers,
     };
 
     env::commit(&output);
@@ -120,31 +187,33 @@ fn hash_pubkey(pubkey: &[u8; 1184]) -> [u8; 32] {
     hasher.finalize().into()
 }
 
-fn hash_payload(payload: &UTXOEncryptedPayload) -> HASH {
+/// Re-derive a Solana PDA for `["vault", vault_hash]` with a known bump.
+/// Mirrors `Pubkey::create_program_address`. The off-curve check is skipped: the
+/// result only has to equal an address the sender already committed to.
+fn derive_vault_pda(vault_hash: &[u8; 32], program_id: &[u8; 32], bump: u8) -> [u8; 32] {
     let mut hasher = Sha256::<Impl>::new();
-    hasher.update(payload.amount.to_le_bytes());
-    hasher.update(&[payload.is_return as u8]);
-    hasher.update(payload.receiver_vault);
-    hasher.update(payload.randomness);
-    for h in &payload.utxo_spent_list { hasher.update(h); }
+    hasher.update(b"vault");
+    hasher.update(vault_hash);
+    hasher.update([bump]);
+    hasher.update(program_id);
+    hasher.update(b"ProgramDerivedAddress");
     hasher.finalize().into()
 }
 
-fn create_header(payload: &UTXOEncryptedPayload, prev_hash: HASH, epoch: u32) -> UTXOCommitmentHeader {
-   let c_commitment = hash_payload(payload);
+/// Nullifier of a spent UTXO: H("qcash:nullifier" || utxo_hash || randomness)
+fn hash_nullifier(utxo_hash: &HASH, randomness: &[u8; 32]) -> HASH {
     let mut hasher = Sha256::<Impl>::new();
-    hasher.update(c_commitment);
-    hasher.update(prev_hash);
-    hasher.update(epoch.to_le_bytes());
-    let utxo_hash = hasher.finalize().into();
-
-    UTXOCommitmentHeader {
-        utxo_hash,
-        prev_utxo_hash: prev_hash,
-        ciphertext_commitment: c_commitment,
-        epoch,
-        kyber_ciphertext: [0u8; 1088], 
-        nonce: [0u8; 12],              
-        encrypted_payload: vec![],     
-    }
+    hasher.update(b"qcash:nullifier");
+    hasher.update(utxo_hash);
+    hasher.update(randomness);
+    hasher.finalize().into()
 }
+
+/// Ledger tip after the program appends utxo_hash: H(prev_tip || utxo_hash)
+fn hash_ledger_tip(prev_tip: &HASH, utxo_hash: &HASH) -> HASH {
+    let mut hasher = Sha256::<Impl>::new();
+    hasher.update(prev_tip);
+    hasher.update(utxo_hash);
+    hasher.finalize().into()
+}
+
@@ -0,0 +1,163 @@
+// Business Source License 1.1 (BSL 1.1)
+// Licensor: Bonsol Labs Inc.
+// Licensed Work: QCash
+// Change Date: 2030-12-31
+// Change License: Apache License 2.0
+// Use of this software is governed by the LICENSE file.
+
+// The guest hashes with the risc0 Sha256<Impl>, wasm and the node with sha2::Sha256.
+// Both go through the same qcash_core builders, these properties check they agree.
+
+use proptest::prelude::*;
+use qcash_core::{derive_kyber_key, fold_history, verify_header, DecryptedInput, HeaderError, UTXOCommitmentHeader, UTXOEncryptedPayload, UTXOEncryptedPayloadV1, HASH, PAYLOAD_VERSION, RngVersion};
+use qcash_wasm::build_output;
+use sha2::Sha256;
+
+// The risc0 rust_crypto wrapper the guest hashes through, host side
+type Guest = risc0_zkvm::sha::rust_crypto::Sha256;
+
+fn arb_payload() -> impl Strategy<Value = UTXOEncryptedPayload> {
+    (any::<u64>(), any::<bool>(), any::<HASH>(), any::<HASH>(), any::<HASH>(), any::<u8>()).prop_map(
+        |(amount, is_return, receiver_vault, randomness, history_root, version)| UTXOEncryptedPayload {
+            amount,
+            is_return,
+            receiver_vault,
+            randomness,
+            history_root,
+            version,
+        },
+    )
+}
+
+fn arb_payload_v1() -> impl Strategy<Value = UTXOEncryptedPayloadV1> {
+    (any::<u64>(), any::<bool>(), any::<HASH>(), any::<HASH>(), prop::collection::vec(any::<HASH>(), 0..4)).prop_map(
+        |(amount, is_return, receiver_vault, randomness, utxo_spent_list)| UTXOEncryptedPayloadV1 {
+            amount,
+            is_return,
+            receiver_vault,
+            randomness,
+            utxo_spent_list,
+            version: UTXOEncryptedPayloadV1::VERSION,
+        },
+    )
+}
+
+// A version 1 input as wasm hands it to the guest, header built the way the v1 guest did
+fn input_v1(payload: &UTXOEncryptedPayloadV1, prev: HASH, epoch: u32) -> DecryptedInput {
+    let mut header = UTXOCommitmentHeader::compute::<Sha256>(&payload.to_current::<Sha256>(), prev, epoch);
+    header.ciphertext_commitment = payload.commitment::<Sha256>();
+    header.utxo_hash = UTXOCommitmentHeader::compute_hash::<Sha256>(&header.ciphertext_commitment, &prev, epoch);
+
+    DecryptedInput {
+        header,
+        payload: payload.to_current::<Sha256>(),
+        utxo_spent_list: Some(payload.utxo_spent_list.clone()),
+    }
+}
+
+fn to_hash(bytes: &[u8]) -> HASH {
+    bytes.try_into().expect("32 bytes")
+}
+
+proptest! {
+    #[test]
+    fn backends_agree_on_commitment(payload in arb_payload()) {
+        prop_assert_eq!(payload.commitment::<Sha256>(), payload.commitment::<Guest>());
+    }
+
+    #[test]
+    fn backends_agree_on_header(payload in arb_payload(), prev in any::<HASH>(), epoch in any::<u32>()) {
+        let host = UTXOCommitmentHeader::compute::<Sha256>(&payload, prev, epoch);
+        let guest = UTXOCommitmentHeader::compute::<Guest>(&payload, prev, epoch);
+
+        prop_assert_eq!(host.utxo_hash, guest.utxo_hash);
+        prop_assert_eq!(host.ciphertext_commitment, guest.ciphertext_commitment);
+        prop_assert_eq!(verify_header::<Guest>(&host, &payload), Ok(()));
+    }
+
+    #[test]
+    fn backends_agree_on_history(acc in any::<HASH>(), root in any::<HASH>(), utxo_hash in any::<HASH>()) {
+        prop_assert_eq!(
+            fold_history::<Sha256>(&acc, &root, &utxo_hash),
+            fold_history::<Guest>(&acc, &root, &utxo_hash)
+        );
+    }
+
+    #[test]
+    fn verify_header_rejects_tampering(payload in arb_payload(), prev in any::<HASH>(), epoch in any::<u32>(), flip in any::<u8>()) {
+        let header = UTXOCommitmentHeader::compute::<Sha256>(&payload, prev, epoch);
+
+        let mut other = payload.clone();
+        other.randomness[0] ^= flip | 1;
+        prop_assert_eq!(verify_header::<Guest>(&header, &other), Err(HeaderError::CommitmentMismatch));
+
+        let mut relinked = header.clone();
+        relinked.prev_utxo_hash[31] ^= flip | 1;
+        prop_assert_eq!(verify_header::<Guest>(&relinked, &payload), Err(HeaderError::HashMismatch));
+
+        let mut moved = header.clone();
+        moved.epoch = epoch.wrapping_add(1);
+        prop_assert_eq!(verify_header::<Guest>(&moved, &payload), Err(HeaderError::HashMismatch));
+    }
+
+    #[test]
+    fn backends_agree_on_v1_inputs(payload in arb_payload_v1(), prev in any::<HASH>(), epoch in any::<u32>()) {
+        prop_assert_eq!(payload.commitment::<Sha256>(), payload.commitment::<Guest>());
+        prop_assert_eq!(payload.history_root::<Sha256>(), payload.history_root::<Guest>());
+        prop_assert_eq!(input_v1(&payload, prev, epoch).verify::<Guest>(), Ok(()));
+    }
+
+    #[test]
+    fn v1_inputs_need_their_spent_list(payload in arb_payload_v1(), prev in any::<HASH>(), epoch in any::<u32>(), extra in any::<HASH>()) {
+        let input = input_v1(&payload, prev, epoch);
+
+        let missing = DecryptedInput { utxo_spent_list: None, ..input.clone() };
+        prop_assert_eq!(missing.verify::<Guest>(), Err(HeaderError::UnsupportedVersion(UTXOEncryptedPayloadV1::VERSION)));
+
+        // A different list no longer folds into the history root the outputs inherit
+        let mut longer = input.clone();
+        longer.utxo_spent_list.as_mut().unwrap().push(extra);
+        prop_assert_eq!(longer.verify::<Guest>(), Err(HeaderError::HistoryMismatch));
+
+        // Re-folding the root to match still breaks the commitment
+        longer.payload.history_root = fold_history::<Guest>(&input.payload.history_root, &[0u8; 32], &extra);
+        prop_assert_eq!(longer.verify::<Guest>(), Err(HeaderError::CommitmentMismatch));
+    }
+}
+
+proptest! {
+    // Kyber encryption makes each case slow, a few are enough to cover the wasm path
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn wasm_output_passes_guest_verification(
+        amount in any::<u64>(),
+        is_return in any::<bool>(),
+        vault in any::<HASH>(),
+        prev in any::<HASH>(),
+        epoch in any::<u32>(),
+        spent in prop::collection::vec((any::<HASH>(), any::<HASH>()), 1..4),
+    ) {
+        let receiver = derive_kyber_key(&[7u8; 32], RngVersion::CURRENT);
+        let spent_inputs: Vec<u8> = spent.iter().flat_map(|(root, hash)| root.iter().chain(hash.iter()).copied()).collect();
+
+        let output = build_output(&receiver.public, &vault, amount, &prev, epoch, is_return, &spent_inputs).unwrap();
+
+        // The guest folds the same inputs into the outputs' history root
+        let history_root = spent.iter().fold([0u8; 32], |acc, (root, hash)| fold_history::<Guest>(&acc, root, hash));
+        let payload = UTXOEncryptedPayload {
+            amount,
+            is_return,
+            receiver_vault: vault,
+            randomness: to_hash(&output.randomness),
+            history_root,
+            version: PAYLOAD_VERSION,
+        };
+        let expected = UTXOCommitmentHeader::compute::<Guest>(&payload, prev, epoch);
+
+        prop_assert_eq!(to_hash(&output.ciphertext_commitment), expected.ciphertext_commitment);
+        prop_assert_eq!(to_hash(&output.utxo_hash), expected.utxo_hash);
+        prop_assert_eq!(to_hash(&output.prev_utxo_hash), prev);
+        prop_assert_eq!(output.epoch, epoch);
+    }
+}
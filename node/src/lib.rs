@@ -5,24 +5,27 @@
 // Change License: Apache License 2.0
 // Use of this software is governed by the LICENSE file.
 
-use std::{path::Path, time::Duration};
+use std::{fmt, fs::File, io::Write, path::Path, time::Duration};
 
 use anchor_lang::prelude::*;
 use anyhow::{Error, Result, anyhow};
 use base64::{Engine, prelude::BASE64_STANDARD};
 use futures::{StreamExt, stream};
 use interface::{PROGRAM_ID, accounts, instructions, submit_attestation};
-use qcash::QcashEvent;
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use qcash::{LEDGER_PAGE_SEED, Ledger, LedgerPage, NULLIFIER_SEED, ProverRegistry, QcashEvent, Utxo, ZkProof, error::ErrorCode};
+use qcash_core::{HASH, QspvGuestOutput, UTXOCommitmentHeader};
 use risc0_zkvm::Receipt;
 use sha2::{Digest, Sha256};
 use solana_client::{
+    client_error::ClientError,
     nonblocking::rpc_client::RpcClient,
     rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
 };
 use solana_pubsub_client::nonblocking::pubsub_client::PubsubClient;
 use solana_sdk::{
-    commitment_config::CommitmentConfig, message::Message, signature::Keypair, signer::Signer,
-    transaction::Transaction,
+    commitment_config::CommitmentConfig, instruction::{AccountMeta, InstructionError}, message::Message,
+    signature::Keypair, signer::Signer, transaction::{Transaction, TransactionError},
 };
 use tokio::sync::{Mutex, mpsc};
 use tracing::{debug, error, info, warn};
@@ -35,11 +38,123 @@ pub const IMAGE_ID: [u8; 32] = [
     2, 253, 149, 209, 231, 84, 195, 127,
 ];
 
+/// Size of a serialized Solana keypair, as stored in the key files
+const KEYPAIR_LENGTH: usize = 64;
+
+/// How often to poll a vote transaction that failed to confirm
+const SETTLE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Where the node's keys stand against the on-chain prover registry
+#[derive(Debug, PartialEq)]
+pub enum KeySync {
+    /// The registry holds our current key hash
+    InSync,
+    /// The registry already holds our next key hash: a vote landed but the rotation after it didn't
+    RotationPending,
+    /// Neither key hash is registered, only the admin can recover with reset_prover_key
+    Unregistered,
+}
+
+/// Paths of the three rotating key files and the rotation journal
+struct KeyFiles {
+    previous: String,
+    current: String,
+    next: String,
+    journal: String,
+}
+
+impl KeyFiles {
+    fn new(current: String, next: String, previous: String) -> Self {
+        let journal = format!("{}.rotation", current);
+        Self {
+            previous,
+            current,
+            next,
+            journal,
+        }
+    }
+
+    fn exist(&self) -> bool {
+        Path::new(&self.previous).exists()
+            && Path::new(&self.current).exists()
+            && Path::new(&self.next).exists()
+    }
+
+    /// Load previous, current and next keys
+    fn load(&self) -> Result<(Keypair, Keypair, Keypair)> {
+        Ok((
+            SolanaKeyManager::load_solana_key(&self.previous)?,
+            SolanaKeyManager::load_solana_key(&self.current)?,
+            SolanaKeyManager::load_solana_key(&self.next)?,
+        ))
+    }
+
+    /// Replace all three key files
+    /// The full key set is journaled first, so a crash part way through is finished by replay_journal
+    fn write(&self, previous: &Keypair, current: &Keypair, next: &Keypair) -> Result<()> {
+        let mut journal = Vec::with_capacity(3 * KEYPAIR_LENGTH);
+        journal.extend_from_slice(&previous.to_bytes());
+        journal.extend_from_slice(&current.to_bytes());
+        journal.extend_from_slice(&next.to_bytes());
+
+        write_atomic(&self.journal, &journal)?;
+        self.replay_journal()?;
+
+        Ok(())
+    }
+
+    /// Finish a key write left behind by a crash, returns whether there was one
+    fn replay_journal(&self) -> Result<bool> {
+        if !Path::new(&self.journal).exists() {
+            return Ok(false);
+        }
+
+        let journal = std::fs::read(&self.journal)?;
+        if journal.len() != 3 * KEYPAIR_LENGTH {
+            return Err(anyhow!(
+                "Key rotation journal {} is {} bytes, expected {}",
+                self.journal,
+                journal.len(),
+                3 * KEYPAIR_LENGTH
+            ));
+        }
+
+        let (previous, rest) = journal.split_at(KEYPAIR_LENGTH);
+        let (current, next) = rest.split_at(KEYPAIR_LENGTH);
+        write_atomic(&self.previous, previous)?;
+        write_atomic(&self.current, current)?;
+        write_atomic(&self.next, next)?;
+
+        std::fs::remove_file(&self.journal)?;
+
+        Ok(true)
+    }
+}
+
+/// Write a file so readers see either the old or the new contents, never a partial write
+fn write_atomic(path: impl AsRef<Path>, data: &[u8]) -> Result<()> {
+    let path = path.as_ref();
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+
+    let mut file = File::create(&tmp)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, path)?;
+
+    // Persist the rename itself
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    File::open(dir)?.sync_all()?;
+
+    Ok(())
+}
+
 /// Solana Key Manager for handling key rotation and management
 pub struct SolanaKeyManager {
-    current_key_file: String,
-    next_key_file: String,
-    previous_key_file: String,
+    files: KeyFiles,
     current_key: Keypair,
     next_key: Keypair,
     previous_key: Keypair,
@@ -52,28 +167,26 @@ impl SolanaKeyManager {
         next_key_file: String,
         previous_key_file: String,
     ) -> Result<Self> {
-        // Load or create previous key
-        if !Path::new(&previous_key_file).exists() {
-            info!("No previous key found, rotating keys...");
-            // If no previous key exists, copy current key to previous and rotate keys
-            std::fs::copy(&current_key_file, &previous_key_file)?;
+        let files = KeyFiles::new(current_key_file, next_key_file, previous_key_file);
 
-            // Move next key to current
-            std::fs::copy(&next_key_file, &current_key_file)?;
+        if files.replay_journal()? {
+            info!("Finished a key rotation interrupted by a restart");
+        }
 
-            // Generate new next key
-            Self::generate_solana_key(&next_key_file)?;
+        // Load or create previous key
+        if !Path::new(&files.previous).exists() {
+            info!("No previous key found, rotating keys...");
+            // If no previous key exists, current becomes previous, next becomes current and a new next is generated
+            let current_key = Self::load_solana_key(&files.current)?;
+            let next_key = Self::load_solana_key(&files.next)?;
+            files.write(&current_key, &next_key, &Keypair::new())?;
         }
 
         // Load the final keys
-        let previous_key = Self::load_solana_key(&previous_key_file)?;
-        let current_key = Self::load_solana_key(&current_key_file)?;
-        let next_key = Self::load_solana_key(&next_key_file)?;
+        let (previous_key, current_key, next_key) = files.load()?;
 
         Ok(Self {
-            current_key_file,
-            next_key_file,
-            previous_key_file,
+            files,
             current_key,
             next_key,
             previous_key,
@@ -88,20 +201,14 @@ impl SolanaKeyManager {
     ) -> Result<Self> {
         info!("Generating all new Solana keys");
 
-        // Generate all three keys
-        Self::generate_solana_key(&current_key_file)?;
-        Self::generate_solana_key(&next_key_file)?;
-        Self::generate_solana_key(&previous_key_file)?;
+        let files = KeyFiles::new(current_key_file, next_key_file, previous_key_file);
+        let (previous_key, current_key, next_key) = (Keypair::new(), Keypair::new(), Keypair::new());
+        files.write(&previous_key, &current_key, &next_key)?;
 
-        // Load the generated keys
-        let previous_key = Self::load_solana_key(&previous_key_file)?;
-        let current_key = Self::load_solana_key(&current_key_file)?;
-        let next_key = Self::load_solana_key(&next_key_file)?;
+        info!("Generated Solana key pairs saved next to {:?}", files.current);
 
         Ok(Self {
-            current_key_file,
-            next_key_file,
-            previous_key_file,
+            files,
             current_key,
             next_key,
             previous_key,
@@ -128,43 +235,60 @@ impl SolanaKeyManager {
         Ok(keypair)
     }
 
-    /// Generate a new Solana keypair and save to file
-    fn generate_solana_key(output: impl AsRef<Path>) -> Result<()> {
-        info!("Generating new Solana key pair");
-
-        let keypair = Keypair::new();
-        let key_bytes = keypair.to_bytes();
-
-        std::fs::write(&output, &key_bytes)?;
-
-        info!("Generated Solana key pair saved to {:?}", output.as_ref());
-
-        Ok(())
-    }
-
     /// Rotate Solana keys: current becomes previous, next becomes current, generate new next
     pub fn rotate_keys(&mut self) -> Result<()> {
         info!("Rotating Solana keys");
 
-        // Move current key to previous (backup)
-        std::fs::copy(&self.current_key_file, &self.previous_key_file)?;
-
-        // Move next key to current
-        std::fs::copy(&self.next_key_file, &self.current_key_file)?;
-
-        // Generate new next key
-        Self::generate_solana_key(&self.next_key_file)?;
+        let new_next_key = Keypair::new();
+        self.files
+            .write(&self.current_key, &self.next_key, &new_next_key)?;
 
-        // Reload keys
-        self.previous_key = Self::load_solana_key(&self.previous_key_file)?;
-        self.current_key = Self::load_solana_key(&self.current_key_file)?;
-        self.next_key = Self::load_solana_key(&self.next_key_file)?;
+        let current_key = std::mem::replace(&mut self.next_key, new_next_key);
+        self.previous_key = std::mem::replace(&mut self.current_key, current_key);
 
         info!("Solana key rotation completed");
 
         Ok(())
     }
 
+    /// Bring the key files back in line with `registry`, returning where they stood before
+    /// Every vote moves the registry to our next key hash, and we rotate once it confirms.
+    /// A crash or a confirmation timeout in between leaves the registry one key ahead, rotating catches up.
+    pub fn sync_with(&mut self, registry: &ProverRegistry) -> Result<KeySync> {
+        let sync = self.key_sync(registry);
+        match sync {
+            KeySync::InSync => debug!("Prover keys in sync with the registry"),
+            KeySync::RotationPending => {
+                warn!("Prover registry already holds our next key hash, finishing the key rotation");
+                self.rotate_keys()?;
+            }
+            KeySync::Unregistered => {
+                return Err(anyhow!(
+                    "Neither current key hash {} nor next key hash {} is in the prover registry, \
+                     the admin has to run reset_prover_key with {}",
+                    hex::encode(self.current_key_hash()),
+                    hex::encode(self.next_key_hash()),
+                    hex::encode(self.current_key_hash())
+                ));
+            }
+        }
+        Ok(sync)
+    }
+
+    /// Compare our key hashes against the prover registry
+    pub fn key_sync(&self, registry: &ProverRegistry) -> KeySync {
+        let current_key_hash: [u8; 32] = self.current_key_hash().try_into().unwrap();
+        let next_key_hash: [u8; 32] = self.next_key_hash().try_into().unwrap();
+
+        if registry.find_prover(&current_key_hash).is_some() {
+            KeySync::InSync
+        } else if registry.find_prover(&next_key_hash).is_some() {
+            KeySync::RotationPending
+        } else {
+            KeySync::Unregistered
+        }
+    }
+
     /// Get the current Solana key
     pub fn current_key(&self) -> &Keypair {
         &self.current_key
@@ -190,6 +314,203 @@ impl SolanaKeyManager {
     }
 }
 
+/// Why the node voted against a UTXO
+#[derive(Debug)]
+pub enum RejectionReason {
+    /// The receipt does not verify against IMAGE_ID
+    InvalidProof(String),
+    /// The journal is not a QspvGuestOutput
+    UndecodableJournal(String),
+    /// The guest never checked the sender holds the Kyber secret key, anyone could have built the proof
+    SenderKeyNotProven,
+    /// The UTXO points at a different proof account than the event
+    ZkProofMismatch { event: Pubkey, account: Option<Pubkey> },
+    /// The UTXO came from create_utxo, but every proof commits a receiver and a change output
+    /// and only `transfer` creates both, finalizing it alone would strand the change
+    NotATransfer,
+    /// The receiver UTXO points at a different change UTXO than the event
+    ChangeUtxoMismatch { event: Pubkey, account: Option<Pubkey> },
+    /// Neither journal output has this utxo_hash
+    UtxoHashMismatch { account: HASH, receiver: HASH, change: HASH },
+    PrevUtxoHashMismatch { account: HASH, journal: HASH },
+    CiphertextCommitmentMismatch { account: HASH, journal: HASH },
+    EpochMismatch { account: u32, journal: u32 },
+    /// The ciphertext and payload on chain are not what the guest encrypted
+    OutputHashMismatch { account: HASH, journal: HASH },
+    NullifiersMismatch { account: usize, journal: usize },
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidProof(e) => write!(f, "proof verification failed: {}", e),
+            Self::UndecodableJournal(e) => write!(f, "can't decode journal: {}", e),
+            Self::SenderKeyNotProven => write!(f, "proof carries no sender key witness"),
+            Self::ZkProofMismatch { event, account } => {
+                write!(f, "zk proof {} does not match UTXO's {:?}", event, account)
+            }
+            Self::NotATransfer => write!(f, "proven UTXO was not created with its change by transfer"),
+            Self::ChangeUtxoMismatch { event, account } => {
+                write!(f, "change UTXO {} does not match UTXO's {:?}", event, account)
+            }
+            Self::UtxoHashMismatch { account, receiver, change } => write!(
+                f,
+                "utxo_hash {} matches neither journal output (receiver {}, change {})",
+                hex::encode(account),
+                hex::encode(receiver),
+                hex::encode(change)
+            ),
+            Self::PrevUtxoHashMismatch { account, journal } => write!(
+                f,
+                "prev_utxo_hash {} != journal {}",
+                hex::encode(account),
+                hex::encode(journal)
+            ),
+            Self::CiphertextCommitmentMismatch { account, journal } => write!(
+                f,
+                "ciphertext_commitment {} != journal {}",
+                hex::encode(account),
+                hex::encode(journal)
+            ),
+            Self::EpochMismatch { account, journal } => {
+                write!(f, "epoch {} != journal {}", account, journal)
+            }
+            Self::OutputHashMismatch { account, journal } => write!(
+                f,
+                "output_hash {} != journal {}",
+                hex::encode(account),
+                hex::encode(journal)
+            ),
+            Self::NullifiersMismatch { account, journal } => write!(
+                f,
+                "nullifiers differ from journal ({} on UTXO, {} in journal)",
+                account, journal
+            ),
+        }
+    }
+}
+
+/// Verify the receipt and decode its journal
+fn decode_receipt(receipt: &Receipt) -> std::result::Result<QspvGuestOutput, RejectionReason> {
+    receipt
+        .verify(IMAGE_ID)
+        .map_err(|e| RejectionReason::InvalidProof(e.to_string()))?;
+
+    receipt
+        .journal
+        .decode()
+        .map_err(|e| RejectionReason::UndecodableJournal(e.to_string()))
+}
+
+/// Check a UTXO account against one output of the journal
+fn check_output(
+    utxo: &Utxo,
+    zk_proof: &Pubkey,
+    header: &UTXOCommitmentHeader,
+    output_hash: HASH,
+    nullifiers: &[HASH],
+) -> std::result::Result<(), RejectionReason> {
+    if utxo.zk_proof_pubkey != Some(*zk_proof) {
+        return Err(RejectionReason::ZkProofMismatch {
+            event: *zk_proof,
+            account: utxo.zk_proof_pubkey,
+        });
+    }
+
+    if utxo.prev_utxo_hash != header.prev_utxo_hash {
+        return Err(RejectionReason::PrevUtxoHashMismatch {
+            account: utxo.prev_utxo_hash,
+            journal: header.prev_utxo_hash,
+        });
+    }
+
+    if utxo.ciphertext_commitment != header.ciphertext_commitment {
+        return Err(RejectionReason::CiphertextCommitmentMismatch {
+            account: utxo.ciphertext_commitment,
+            journal: header.ciphertext_commitment,
+        });
+    }
+
+    if utxo.epoch != header.epoch {
+        return Err(RejectionReason::EpochMismatch {
+            account: utxo.epoch,
+            journal: header.epoch,
+        });
+    }
+
+    if utxo.output_hash != output_hash {
+        return Err(RejectionReason::OutputHashMismatch {
+            account: utxo.output_hash,
+            journal: output_hash,
+        });
+    }
+
+    if utxo.nullifiers != nullifiers {
+        return Err(RejectionReason::NullifiersMismatch {
+            account: utxo.nullifiers.len(),
+            journal: nullifiers.len(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Verify the receipt and check its journal describes both UTXOs of a transfer
+pub fn check_transfer_receipt(
+    receipt: &Receipt,
+    zk_proof: &Pubkey,
+    receiver: &Utxo,
+    change_key: &Pubkey,
+    change: &Utxo,
+) -> std::result::Result<(), RejectionReason> {
+    check_transfer_journal(&decode_receipt(receipt)?, zk_proof, receiver, change_key, change)
+}
+
+/// Check a verified journal describes both UTXOs of a transfer
+fn check_transfer_journal(
+    output: &QspvGuestOutput,
+    zk_proof: &Pubkey,
+    receiver: &Utxo,
+    change_key: &Pubkey,
+    change: &Utxo,
+) -> std::result::Result<(), RejectionReason> {
+    if !output.sender_key_proven {
+        return Err(RejectionReason::SenderKeyNotProven);
+    }
+
+    if receiver.change_utxo != Some(*change_key) {
+        return Err(RejectionReason::ChangeUtxoMismatch {
+            event: *change_key,
+            account: receiver.change_utxo,
+        });
+    }
+
+    if receiver.utxo_hash != output.receiver_commitment.utxo_hash
+        || change.utxo_hash != output.return_commitment.utxo_hash
+    {
+        return Err(RejectionReason::UtxoHashMismatch {
+            account: receiver.utxo_hash,
+            receiver: output.receiver_commitment.utxo_hash,
+            change: output.return_commitment.utxo_hash,
+        });
+    }
+
+    check_output(
+        receiver,
+        zk_proof,
+        &output.receiver_commitment,
+        output.receiver_output_hash,
+        &output.nullifiers,
+    )?;
+    check_output(
+        change,
+        zk_proof,
+        &output.return_commitment,
+        output.return_output_hash,
+        &[],
+    )
+}
+
 pub struct QcashNodeConfig {
     pub previous_key_file: Option<String>, //Default current-key + .previous
     pub current_key_file: String,
@@ -243,9 +564,12 @@ impl QcashNode {
         };
 
         // Check if keys exist before generating new ones
-        let keys_exist = Path::new(&current_key_file).exists()
-            && Path::new(&next_key_file).exists()
-            && Path::new(&previous_key_file).exists();
+        let keys_exist = KeyFiles::new(
+            current_key_file.clone(),
+            next_key_file.clone(),
+            previous_key_file.clone(),
+        )
+        .exist();
 
         let key_manager = if config.generate_keys {
             if keys_exist {
@@ -284,6 +608,14 @@ impl QcashNode {
     }
 
     pub async fn run(&self) {
+        // Catch up with a vote that landed while we were down
+        {
+            let mut key_manager = self.key_manager.lock().await;
+            if let Err(e) = self.reconcile_keys(&mut key_manager).await {
+                error!("Key reconciliation failed: {}", e);
+            }
+        }
+
         let websocket_url = self.websocket_url.clone();
         let (tx, mut rx) = mpsc::unbounded_channel::<Vec<u8>>();
 
@@ -300,6 +632,92 @@ impl QcashNode {
         }
     }
 
+    /// Bring the key files back in line with the prover registry, see SolanaKeyManager::sync_with
+    /// Returns the registry they were checked against
+    async fn reconcile_keys(&self, key_manager: &mut SolanaKeyManager) -> Result<ProverRegistry> {
+        let registry = self.fetch_prover_registry().await?;
+        key_manager.sync_with(&registry)?;
+        Ok(registry)
+    }
+
+    /// Wait until a vote we never saw confirm has either landed or can no longer land
+    /// Until its blockhash expires it may still be processed, a registry read before then
+    /// could keep our old keys and miss the rotation it makes
+    async fn settle_transaction(&self, tx: &Transaction) -> Result<()> {
+        let signature = tx.signatures[0];
+        loop {
+            if self.rpc_client.get_signature_status(&signature).await?.is_some() {
+                return Ok(());
+            }
+
+            // A processed transaction can still confirm after its blockhash expires
+            let processed = self
+                .rpc_client
+                .get_signature_status_with_commitment(&signature, CommitmentConfig::processed())
+                .await?
+                .is_some();
+            let expired = !self
+                .rpc_client
+                .is_blockhash_valid(&tx.message.recent_blockhash, CommitmentConfig::processed())
+                .await?;
+            if expired && !processed {
+                return Ok(());
+            }
+
+            tokio::time::sleep(SETTLE_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn fetch_utxo(&self, utxo: &Pubkey) -> Result<Utxo> {
+        let utxo_data = self
+            .rpc_client
+            .get_account_data(utxo)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch UTXO account: {}", e))?;
+        Utxo::try_deserialize(&mut &utxo_data[..])
+            .map_err(|e| anyhow!("Can't parse UTXO account: {}", e))
+    }
+
+    async fn fetch_ledger(&self) -> Result<Ledger> {
+        let (ledger_pda, _bump) = accounts::SubmitAttestation::ledger_pda(&PROGRAM_ID);
+        let ledger_data = self
+            .rpc_client
+            .get_account_data(&ledger_pda)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch ledger: {}", e))?;
+        Ledger::try_deserialize(&mut &ledger_data[..])
+            .map_err(|e| anyhow!("Can't parse ledger: {}", e))
+    }
+
+    /// Number of these nullifiers that already have a nullifier account, each one is a spent input
+    async fn spent_nullifiers(&self, nullifiers: &[[u8; 32]]) -> Result<usize> {
+        let pdas: Vec<Pubkey> = nullifiers.iter().map(nullifier_pda).collect();
+        let accounts = self
+            .rpc_client
+            .get_multiple_accounts(&pdas)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch nullifier accounts: {}", e))?;
+
+        // Lamports sent to an unused address don't spend it, only the program creates nullifiers
+        Ok(accounts
+            .iter()
+            .flatten()
+            .filter(|account| account.owner == PROGRAM_ID)
+            .count())
+    }
+
+    async fn fetch_prover_registry(&self) -> Result<ProverRegistry> {
+        let (prover_registry_pda, _bump) =
+            accounts::SubmitAttestation::prover_registry_pda(&PROGRAM_ID);
+        let registry_data = self
+            .rpc_client
+            .get_account_data(&prover_registry_pda)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch prover registry: {}", e))?;
+        ProverRegistry::try_deserialize(&mut &registry_data[..])
+            .map_err(|e| anyhow!("Can't parse prover registry: {}", e))
+    }
+
     async fn process_event(&self, event: Vec<u8>) {
         // Parse the event object and process ZkProofChunkWritten messages
         match QcashEvent::parse(&event) {
@@ -309,12 +727,26 @@ impl QcashNode {
                         &utxo_event.zk_proof,
                         &utxo_event.utxo,
                         utxo_event.utxo_hash,
+                        None,
                     )
                     .await
                 {
                     warn!("Failed to process Utxo: {}", e);
                 }
             }
+            Ok(QcashEvent::TransferCreated(transfer_event)) => {
+                if let Err(e) = self
+                    .process_utxo_created(
+                        &transfer_event.zk_proof,
+                        &transfer_event.receiver_utxo,
+                        transfer_event.receiver_utxo_hash,
+                        Some(&transfer_event.change_utxo),
+                    )
+                    .await
+                {
+                    warn!("Failed to process transfer: {}", e);
+                }
+            }
             Ok(QcashEvent::VaultCompleted(vault_event)) => {
                 info!(
                     "Vault completed for key hash: {:?}, total length: {}",
@@ -334,6 +766,7 @@ impl QcashNode {
         zk_proof: &Pubkey,
         utxo: &Pubkey,
         utxo_hash: [u8; 32],
+        change_utxo: Option<&Pubkey>,
     ) -> Result<()> {
         let proof_data = self
             .rpc_client
@@ -341,22 +774,93 @@ impl QcashNode {
             .await
             .map_err(|e| anyhow!("Failed to download proof: {}", e))?;
 
-        // ZkProof header: 8 (discriminator) + 4 (total_len) + 4 (bytes_written) = 16 bytes
-        let receipt: Receipt = bincode::deserialize(&proof_data[16..])
+        // ZkProof header: 8 (discriminator) + 4 (total_len) + 4 (bytes_written) + 32 (payer) + 32 (locked_by)
+        let receipt: Receipt = bincode::deserialize(&proof_data[ZkProof::HEADER_SIZE..])
             .map_err(|e| anyhow!("Can't parse proof: {}", e))?;
 
+        // The journal is checked against the UTXO account
+        let utxo_account = self.fetch_utxo(utxo).await?;
+
+        // Finalized or rejected while the event was queued, the program would refuse the vote
+        if !utxo_account.is_pending() {
+            info!("UTXO {} is {:?}, not voting", utxo, utxo_account.status);
+            return Ok(());
+        }
+
+        // A transfer's change UTXO is checked and finalized along with the receiver
+        let change_account = match change_utxo {
+            Some(change_utxo) => {
+                let change_data = self
+                    .rpc_client
+                    .get_account_data(change_utxo)
+                    .await
+                    .map_err(|e| anyhow!("Failed to fetch change UTXO account: {}", e))?;
+                Some(
+                    Utxo::try_deserialize(&mut &change_data[..])
+                        .map_err(|e| anyhow!("Can't parse change UTXO account: {}", e))?,
+                )
+            }
+            None => None,
+        };
+
         // Get mutable lock on key_manager for potential key rotation
         let mut key_manager = self.key_manager.lock().await;
+
+        // An earlier vote may have landed after we gave up on it, catch up before voting again
+        let registry = self.reconcile_keys(&mut key_manager).await?;
         let next_key_hash = key_manager.next_key_hash().try_into().unwrap();
 
-        let vote = if let Err(e) = receipt.verify(IMAGE_ID) {
-            info!("Proof verification failed: {}", e);
-            false
-        } else {
-            info!("Proof verified successfully!");
-            true
+        // Our stake account is keyed by the unique ID the registry holds for our current key
+        let current_key_hash: [u8; 32] = key_manager.current_key_hash().try_into().unwrap();
+        let unique_id = registry
+            .find_prover(&current_key_hash)
+            .map(|index| registry.provers[index].unique_id)
+            .ok_or_else(|| anyhow!("Current key hash is not in the prover registry"))?;
+
+        let check = match (change_utxo, &change_account) {
+            (Some(change_utxo), Some(change_account)) => {
+                check_transfer_receipt(&receipt, zk_proof, &utxo_account, change_utxo, change_account)
+            }
+            _ => Err(RejectionReason::NotATransfer),
+        };
+        let mut vote = match check {
+            Ok(()) => {
+                info!("Proof verified and matches UTXO {}", utxo);
+                true
+            }
+            Err(reason) => {
+                warn!(utxo = %utxo, reason = ?reason, "Rejecting UTXO: {}", reason);
+                false
+            }
         };
 
+        let ledger = self.fetch_ledger().await?;
+
+        // A proof against a tip that fell out of the ledger window or from an expired epoch
+        // can't finalize anymore, voting it down lets the payer reclaim the rent
+        if vote {
+            // An input spent by an earlier transfer makes the deciding valid vote fail, the UTXO
+            // would sit pending until it expires. Voting it down rejects and refunds it instead.
+            let spent = self.spent_nullifiers(&utxo_account.nullifiers).await?;
+            if spent > 0 {
+                warn!(utxo = %utxo, spent, "Rejecting UTXO: it spends inputs that are already spent");
+                vote = false;
+            }
+
+            if !ledger.is_recent_tip(&utxo_account.prev_utxo_hash) {
+                warn!(utxo = %utxo, "Rejecting UTXO: it links to a ledger tip that is no longer recent");
+                vote = false;
+            }
+
+            // Judge the epoch by the cluster clock, the same one the program uses
+            let slot = self.rpc_client.get_slot().await?;
+            let now = self.rpc_client.get_block_time(slot).await?;
+            if !ledger.is_live_epoch(utxo_account.epoch, now) {
+                warn!(utxo = %utxo, epoch = utxo_account.epoch, "Rejecting UTXO: its epoch has expired");
+                vote = false;
+            }
+        }
+
         let current_key_pubkey = key_manager.current_key().pubkey();
         let previous_key_pubkey = key_manager.previous_key().pubkey();
 
@@ -408,17 +912,61 @@ impl QcashNode {
             utxo
         );
 
-        let recent_blockhash = self.rpc_client.get_latest_blockhash().await?;
-        let tx = create_submit_attestation_transaction(
-            &key_manager.current_key(),
-            &key_manager.previous_key(),
-            next_key_hash,
-            utxo,
-            utxo_hash,
-            vote,
-            recent_blockhash,
-        )?;
-        self.rpc_client.send_and_confirm_transaction(&tx).await?;
+        // Other provers may have voted while we checked the proof, and the fee is split among the
+        // voters at the time ours lands. Their votes may also have finalized UTXOs and moved the
+        // ledger on to later pages. Build from fresh copies of the UTXO and the ledger, and
+        // rebuild once more if another vote still lands first.
+        let mut rebuilt = false;
+        loop {
+            let utxo_account = self.fetch_utxo(utxo).await?;
+            if !utxo_account.is_pending() {
+                info!("UTXO {} became {:?} before our vote, not voting", utxo, utxo_account.status);
+                return Ok(());
+            }
+            let ledger = self.fetch_ledger().await?;
+
+            let recent_blockhash = self.rpc_client.get_latest_blockhash().await?;
+            let tx = create_submit_attestation_transaction(
+                key_manager.current_key(),
+                key_manager.previous_key(),
+                &AttestationAccounts {
+                    utxo,
+                    change_utxo,
+                    unique_id,
+                    nullifiers: &utxo_account.nullifiers,
+                    co_voters: &co_voters(&utxo_account, vote, unique_id),
+                    ledger_count: ledger.count,
+                },
+                instructions::SubmitAttestation {
+                    next_key_hash,
+                    utxo_hash,
+                    vote,
+                },
+                recent_blockhash,
+            )?;
+            match self.rpc_client.send_and_confirm_transaction(&tx).await {
+                Ok(_) => break,
+                // A failed instruction changes nothing, our keys are still the registered ones
+                Err(e)
+                    if !rebuilt
+                        && (is_program_error(&e, ErrorCode::RewardAccountsMismatch)
+                            || is_program_error(&e, ErrorCode::LedgerPageMismatch)) =>
+                {
+                    warn!(utxo = %utxo, "Another vote landed before ours, rebuilding the attestation");
+                    rebuilt = true;
+                }
+                Err(e) => {
+                    // The vote may have landed even though we never saw it confirm, or land later
+                    if let Err(settle_error) = self.settle_transaction(&tx).await {
+                        warn!("Couldn't settle the attestation transaction: {}", settle_error);
+                    }
+                    if let Err(sync_error) = self.reconcile_keys(&mut key_manager).await {
+                        warn!("Key reconciliation failed: {}", sync_error);
+                    }
+                    return Err(e.into());
+                }
+            }
+        }
 
         // Key rotation after successful attestation
         // The program has updated the prover registry with next_key_hash,
@@ -435,36 +983,91 @@ impl QcashNode {
     }
 }
 
+/// Earlier voters that agree with us, they share the attestation fee if our vote decides the UTXO
+fn co_voters(utxo: &Utxo, vote: bool, unique_id: u64) -> Vec<u64> {
+    utxo.votes
+        .iter()
+        .flatten()
+        .filter(|v| v.is_used() && v.is_valid == vote && v.prover_id != unique_id)
+        .map(|v| v.prover_id)
+        .collect()
+}
+
+/// Checks if a transaction failed with this program error
+fn is_program_error(error: &ClientError, code: ErrorCode) -> bool {
+    matches!(
+        error.get_transaction_error(),
+        Some(TransactionError::InstructionError(_, InstructionError::Custom(custom)))
+            if custom == ERROR_CODE_OFFSET + code as u32
+    )
+}
+
+/// Accounts an attestation touches besides the program's own PDAs
+struct AttestationAccounts<'a> {
+    utxo: &'a Pubkey,
+    /// Change UTXO of a transfer, finalized along with `utxo`
+    change_utxo: Option<&'a Pubkey>,
+    /// Our unique ID in the prover registry, keys our stake account
+    unique_id: u64,
+    /// Nullifiers spent by `utxo`, in the order the UTXO lists them
+    nullifiers: &'a [[u8; 32]],
+    /// Earlier voters sharing the fee with us, in vote order
+    co_voters: &'a [u64],
+    /// Ledger count the pages are picked from
+    ledger_count: u64,
+}
+
 fn create_submit_attestation_transaction(
     current_key: &Keypair,
     previous_key: &Keypair,
-    next_key_hash: [u8; 32],
-    utxo: &Pubkey,
-    utxo_hash: [u8; 32],
-    vote: bool,
+    attestation: &AttestationAccounts,
+    args: instructions::SubmitAttestation,
     recent_blockhash: solana_sdk::hash::Hash,
 ) -> Result<Transaction> {
     let (prover_registry_pda, _bump) =
         accounts::SubmitAttestation::prover_registry_pda(&PROGRAM_ID);
     let (ledger_pda, _bump) = accounts::SubmitAttestation::ledger_pda(&PROGRAM_ID);
-
-    let ix = submit_attestation(
+    let (program_config_pda, _bump) =
+        accounts::SubmitAttestation::program_config_pda(&PROGRAM_ID);
+    let (prover_stake_pda, _bump) =
+        accounts::RegisterProver::prover_stake_pda(&PROGRAM_ID, attestation.unique_id);
+
+    // The page the next finalized UTXO goes to, and the one after it in case a change UTXO
+    // crosses the boundary or other votes finalize UTXOs before ours lands
+    let (page, _slot) = LedgerPage::locate(attestation.ledger_count);
+    let current_page_pda = ledger_page_pda(page);
+    let next_page_pda = ledger_page_pda(page + 1);
+
+    let mut ix = submit_attestation(
         &PROGRAM_ID,
         accounts::SubmitAttestation {
             prover: current_key.pubkey(),
             prover_old: previous_key.pubkey(),
             prover_registry: prover_registry_pda,
+            program_config: program_config_pda,
+            prover_stake: prover_stake_pda,
             ledger: ledger_pda,
-            utxo: utxo.to_owned(),
+            ledger_page: current_page_pda,
+            next_ledger_page: next_page_pda,
+            utxo: attestation.utxo.to_owned(),
+            change_utxo: attestation.change_utxo.copied(),
             ..Default::default()
         },
-        instructions::SubmitAttestation {
-            next_key_hash,
-            utxo_hash,
-            vote,
-        },
+        args,
     );
 
+    // Nullifier PDAs go in the remaining accounts, in the same order as on the UTXO
+    for nullifier in attestation.nullifiers {
+        ix.accounts.push(AccountMeta::new(nullifier_pda(nullifier), false));
+    }
+
+    // Then the stakes of the earlier voters on our side, in vote order
+    for &co_voter in attestation.co_voters {
+        let (co_voter_stake_pda, _bump) =
+            accounts::RegisterProver::prover_stake_pda(&PROGRAM_ID, co_voter);
+        ix.accounts.push(AccountMeta::new(co_voter_stake_pda, false));
+    }
+
     // Create a message with the instruction
     let message = Message::new(&[ix], Some(&previous_key.pubkey()));
 
@@ -478,6 +1081,14 @@ fn create_submit_attestation_transaction(
     Ok(transaction)
 }
 
+fn ledger_page_pda(page: u64) -> Pubkey {
+    Pubkey::find_program_address(&[LEDGER_PAGE_SEED, &page.to_le_bytes()], &PROGRAM_ID).0
+}
+
+fn nullifier_pda(nullifier: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[NULLIFIER_SEED, nullifier.as_ref()], &PROGRAM_ID).0
+}
+
 async fn events_subscription(
     websocket_url: String,
     tx_chan: mpsc::UnboundedSender<Vec<u8>>,
@@ -529,3 +1140,334 @@ async fn events_subscription(
         tokio::time::sleep(Duration::from_secs(10)).await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qcash::{MAX_VOTES_ALLOWED, ProverVote, UtxoStatus};
+    use qcash_core::KYBER_CIPHERTEXT_SIZE;
+
+    const ZK_PROOF: Pubkey = Pubkey::new_from_array([7u8; 32]);
+    const CHANGE: Pubkey = Pubkey::new_from_array([8u8; 32]);
+
+    fn header(seed: u8) -> UTXOCommitmentHeader {
+        UTXOCommitmentHeader {
+            utxo_hash: [seed; 32],
+            prev_utxo_hash: [seed + 1; 32],
+            ciphertext_commitment: [seed + 2; 32],
+            epoch: seed as u32,
+            kyber_ciphertext: [0u8; KYBER_CIPHERTEXT_SIZE],
+            nonce: [0u8; 12],
+            encrypted_payload: Vec::new(),
+        }
+    }
+
+    fn journal() -> QspvGuestOutput {
+        QspvGuestOutput {
+            receiver_commitment: header(10),
+            return_commitment: header(20),
+            receiver_output_hash: [30u8; 32],
+            return_output_hash: [40u8; 32],
+            sender_key_proven: true,
+            nullifiers: vec![[50u8; 32], [51u8; 32]],
+        }
+    }
+
+    fn utxo(header: &UTXOCommitmentHeader, output_hash: HASH, nullifiers: &[HASH]) -> Utxo {
+        Utxo {
+            epoch: header.epoch,
+            utxo_hash: header.utxo_hash,
+            prev_utxo_hash: header.prev_utxo_hash,
+            ciphertext_commitment: header.ciphertext_commitment,
+            output_hash,
+            nonce: [0u8; 12],
+            encrypted_payload: Vec::new(),
+            kyber_ciphertext: [0u8; KYBER_CIPHERTEXT_SIZE],
+            zk_proof_pubkey: Some(ZK_PROOF),
+            votes: Some([ProverVote::default(); MAX_VOTES_ALLOWED]),
+            nullifiers: nullifiers.to_vec(),
+            change_utxo: None,
+            payer: Pubkey::default(),
+            status: UtxoStatus::Pending,
+            attestation_fee: 0,
+            ledger_prev_utxo_hash: [0u8; 32],
+            ledger_prev_tip: [0u8; 32],
+            ledger_index: 0,
+            slashed_votes: [false; MAX_VOTES_ALLOWED],
+            bump: 0,
+        }
+    }
+
+    /// The receiver and change UTXO accounts a transfer with this journal creates
+    fn transfer_utxos(output: &QspvGuestOutput) -> (Utxo, Utxo) {
+        let mut receiver = utxo(
+            &output.receiver_commitment,
+            output.receiver_output_hash,
+            &output.nullifiers,
+        );
+        receiver.change_utxo = Some(CHANGE);
+        let change = utxo(&output.return_commitment, output.return_output_hash, &[]);
+        (receiver, change)
+    }
+
+    /// Breaks one field of a journal or of the UTXO accounts built from it
+    type Tamper = fn(&mut QspvGuestOutput, &mut Utxo, &mut Utxo);
+    type Expect = fn(&RejectionReason) -> bool;
+
+    #[test]
+    fn accepts_matching_journal() {
+        let output = journal();
+        let (receiver, change) = transfer_utxos(&output);
+
+        assert!(check_transfer_journal(&output, &ZK_PROOF, &receiver, &CHANGE, &change).is_ok());
+    }
+
+    #[test]
+    fn rejects_mismatched_journal() {
+        let cases: &[(&str, Tamper, Expect)] = &[
+            (
+                "sender key not proven",
+                |output, _, _| output.sender_key_proven = false,
+                |r| matches!(r, RejectionReason::SenderKeyNotProven),
+            ),
+            (
+                "other zk proof",
+                |_, receiver, _| receiver.zk_proof_pubkey = Some(Pubkey::new_from_array([9u8; 32])),
+                |r| matches!(r, RejectionReason::ZkProofMismatch { .. }),
+            ),
+            (
+                "unknown utxo_hash",
+                |_, receiver, _| receiver.utxo_hash = [0u8; 32],
+                |r| matches!(r, RejectionReason::UtxoHashMismatch { .. }),
+            ),
+            (
+                "prev_utxo_hash",
+                |output, _, _| output.receiver_commitment.prev_utxo_hash = [0u8; 32],
+                |r| matches!(r, RejectionReason::PrevUtxoHashMismatch { .. }),
+            ),
+            (
+                "ciphertext_commitment",
+                |output, _, _| output.receiver_commitment.ciphertext_commitment = [0u8; 32],
+                |r| matches!(r, RejectionReason::CiphertextCommitmentMismatch { .. }),
+            ),
+            (
+                "epoch",
+                |_, receiver, _| receiver.epoch += 1,
+                |r| matches!(r, RejectionReason::EpochMismatch { .. }),
+            ),
+            (
+                "output_hash",
+                |output, _, _| output.receiver_output_hash = [0u8; 32],
+                |r| matches!(r, RejectionReason::OutputHashMismatch { .. }),
+            ),
+            (
+                "missing nullifier",
+                |_, receiver, _| {
+                    receiver.nullifiers.pop();
+                },
+                |r| matches!(r, RejectionReason::NullifiersMismatch { .. }),
+            ),
+        ];
+
+        for (name, tamper, expect) in cases {
+            let mut output = journal();
+            let (mut receiver, mut change) = transfer_utxos(&output);
+            tamper(&mut output, &mut receiver, &mut change);
+
+            let result = check_transfer_journal(&output, &ZK_PROOF, &receiver, &CHANGE, &change);
+            assert!(matches!(&result, Err(reason) if expect(reason)), "{}: {:?}", name, result);
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_transfer_journal() {
+        let cases: &[(&str, Tamper, Expect)] = &[
+            (
+                "other change UTXO",
+                |_, receiver, _| receiver.change_utxo = None,
+                |r| matches!(r, RejectionReason::ChangeUtxoMismatch { .. }),
+            ),
+            (
+                "outputs swapped",
+                |_, receiver, change| std::mem::swap(&mut receiver.utxo_hash, &mut change.utxo_hash),
+                |r| matches!(r, RejectionReason::UtxoHashMismatch { .. }),
+            ),
+            (
+                "change from another proof",
+                |_, _, change| change.zk_proof_pubkey = None,
+                |r| matches!(r, RejectionReason::ZkProofMismatch { .. }),
+            ),
+            (
+                "change epoch",
+                |output, _, _| output.return_commitment.epoch += 1,
+                |r| matches!(r, RejectionReason::EpochMismatch { .. }),
+            ),
+            (
+                "change output_hash",
+                |output, _, _| output.return_output_hash = [0u8; 32],
+                |r| matches!(r, RejectionReason::OutputHashMismatch { .. }),
+            ),
+            (
+                "nullifiers on the change",
+                |_, _, change| change.nullifiers = vec![[50u8; 32]],
+                |r| matches!(r, RejectionReason::NullifiersMismatch { .. }),
+            ),
+        ];
+
+        for (name, tamper, expect) in cases {
+            let mut output = journal();
+            let (mut receiver, mut change) = transfer_utxos(&output);
+            tamper(&mut output, &mut receiver, &mut change);
+
+            let result = check_transfer_journal(&output, &ZK_PROOF, &receiver, &CHANGE, &change);
+            assert!(matches!(&result, Err(reason) if expect(reason)), "{}: {:?}", name, result);
+        }
+    }
+
+    #[test]
+    fn co_voters_are_earlier_votes_on_our_side() {
+        let mut utxo = utxo(&header(10), [30u8; 32], &[]);
+        for (prover_id, is_valid) in [(3, true), (4, false), (5, true)] {
+            utxo.record_vote(prover_id, is_valid).unwrap();
+        }
+
+        assert_eq!(co_voters(&utxo, true, 6), vec![3, 5]);
+        assert_eq!(co_voters(&utxo, false, 6), vec![4]);
+
+        // Our own earlier vote is never passed back as a co-voter
+        assert_eq!(co_voters(&utxo, true, 5), vec![3]);
+    }
+
+    /// A fresh directory for one test's key files
+    /// Removed with its key files when dropped at the end of the test
+    fn key_dir(name: &str) -> tempfile::TempDir {
+        tempfile::Builder::new()
+            .prefix(&format!("qcash-node-{}-", name))
+            .tempdir()
+            .unwrap()
+    }
+
+    fn key_paths(dir: &Path) -> (String, String, String) {
+        let path = |name: &str| dir.join(name).to_str().unwrap().to_owned();
+        (path("current.json"), path("next.json"), path("previous.json"))
+    }
+
+    fn new_keys(dir: &Path) -> SolanaKeyManager {
+        let (current, next, previous) = key_paths(dir);
+        SolanaKeyManager::new_with_new_keys(current, next, previous).unwrap()
+    }
+
+    fn load_keys(dir: &Path) -> SolanaKeyManager {
+        let (current, next, previous) = key_paths(dir);
+        SolanaKeyManager::new(current, next, previous).unwrap()
+    }
+
+    fn pubkeys(keys: &SolanaKeyManager) -> [Pubkey; 3] {
+        [keys.previous_key().pubkey(), keys.current_key().pubkey(), keys.next_key.pubkey()]
+    }
+
+    fn registry_with(key_hash: Vec<u8>) -> ProverRegistry {
+        let mut registry = ProverRegistry::default();
+        registry.register_prover(1, key_hash.try_into().unwrap()).unwrap();
+        registry
+    }
+
+    #[test]
+    fn in_sync_keeps_keys() {
+        let dir = key_dir("in-sync");
+        let mut keys = new_keys(dir.path());
+        let before = pubkeys(&keys);
+        let registry = registry_with(keys.current_key_hash());
+
+        assert_eq!(keys.sync_with(&registry).unwrap(), KeySync::InSync);
+        assert_eq!(pubkeys(&keys), before);
+        assert_eq!(pubkeys(&load_keys(dir.path())), before);
+    }
+
+    #[test]
+    fn rotation_pending_rotates_keys() {
+        let dir = key_dir("rotation-pending");
+        let mut keys = new_keys(dir.path());
+        let [_, current, next] = pubkeys(&keys);
+        let registry = registry_with(keys.next_key_hash());
+
+        assert_eq!(keys.sync_with(&registry).unwrap(), KeySync::RotationPending);
+        assert_eq!(keys.key_sync(&registry), KeySync::InSync);
+        assert_eq!(keys.previous_key().pubkey(), current);
+        assert_eq!(keys.current_key().pubkey(), next);
+        assert_eq!(pubkeys(&load_keys(dir.path())), pubkeys(&keys));
+
+        // Syncing again with the same registry changes nothing
+        assert_eq!(keys.sync_with(&registry).unwrap(), KeySync::InSync);
+        assert_eq!(keys.current_key().pubkey(), next);
+    }
+
+    #[test]
+    fn unregistered_keeps_keys() {
+        let dir = key_dir("unregistered");
+        let mut keys = new_keys(dir.path());
+        let before = pubkeys(&keys);
+        let registry = registry_with(vec![9u8; 32]);
+
+        assert_eq!(keys.key_sync(&registry), KeySync::Unregistered);
+        assert!(keys.sync_with(&registry).is_err());
+        assert_eq!(pubkeys(&keys), before);
+        assert_eq!(pubkeys(&load_keys(dir.path())), before);
+    }
+
+    #[test]
+    fn vote_landing_after_a_sync_is_caught_up() {
+        let dir = key_dir("late-vote");
+        let mut keys = new_keys(dir.path());
+        let [_, _, next] = pubkeys(&keys);
+        let mut registry = registry_with(keys.current_key_hash());
+
+        // The vote hadn't landed when we checked after it failed to confirm
+        assert_eq!(keys.sync_with(&registry).unwrap(), KeySync::InSync);
+
+        // Then it did, moving the registry to our next key hash
+        registry.provers[0].update_pubkey_hash(keys.next_key_hash().try_into().unwrap());
+        assert_eq!(keys.sync_with(&registry).unwrap(), KeySync::RotationPending);
+        assert_eq!(keys.current_key().pubkey(), next);
+        assert_eq!(keys.key_sync(&registry), KeySync::InSync);
+    }
+
+    #[test]
+    fn interrupted_key_write_is_replayed() {
+        let dir = key_dir("journal");
+        let keys = new_keys(dir.path());
+        let rotated = [Keypair::new(), Keypair::new(), Keypair::new()];
+
+        // A crash right after journaling the new key set, before any key file was replaced
+        let journal: Vec<u8> = rotated.iter().flat_map(|key| key.to_bytes()).collect();
+        write_atomic(&keys.files.journal, &journal).unwrap();
+        let journal_path = keys.files.journal.clone();
+        drop(keys);
+
+        let reloaded = load_keys(dir.path());
+        assert_eq!(pubkeys(&reloaded), rotated.map(|key| key.pubkey()));
+        assert!(!Path::new(&journal_path).exists());
+    }
+
+    #[test]
+    fn truncated_journal_is_an_error() {
+        let dir = key_dir("truncated-journal");
+        let keys = new_keys(dir.path());
+        write_atomic(&keys.files.journal, &[0u8; KEYPAIR_LENGTH]).unwrap();
+
+        let (current, next, previous) = key_paths(dir.path());
+        assert!(SolanaKeyManager::new(current, next, previous).is_err());
+    }
+
+    #[test]
+    fn write_atomic_replaces_contents() {
+        let dir = key_dir("write-atomic");
+        let path = dir.path().join("file");
+
+        write_atomic(&path, b"old").unwrap();
+        write_atomic(&path, b"new").unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+        assert!(!dir.path().join("file.tmp").exists());
+    }
+}
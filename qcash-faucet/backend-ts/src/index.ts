@@ -27,6 +27,10 @@ const faucetKeypair = Keypair.fromSecretKey(Uint8Array.from(JSON.parse(process.e
 const wallet = new anchor.Wallet(faucetKeypair);
 const provider = new anchor.AnchorProvider(connection, wallet);
 const CHUNKS_SIZE = 600;
+// Must match LEDGER_PAGE_SIZE in the Solana program
+const LEDGER_PAGE_SIZE = 256;
+// Must match EPOCH_DURATION in the Solana program
+const EPOCH_DURATION = 24 * 60 * 60;
 
 const program = new anchor.Program<SolanaPrograms>(idl as SolanaPrograms, provider);
 
@@ -47,16 +51,31 @@ app.post("/airdrop", async (req, res) => {
         const kyberKey = Uint8Array.from(vaultAccount.kyberPubkey);
         console.log("kyberKey", kyberKey);
 
-        const epoch = 0;
         const amount = BigInt(100); // 100 tokens
 
-        // Get the current ledger tip hash for prev_utxo_hash
+        // Link to the current ledger tip, the program accepts it while it stays recent
         const [ledgerPda] = PublicKey.findProgramAddressSync(
             [Buffer.from("ledger")],
             program.programId
         );
         const ledgerAccount = await program.account.ledger.fetch(ledgerPda);
-        const prevUtxoHash = new Uint8Array(ledgerAccount.lastValidUtxoHash);
+        const prevUtxoHash = new Uint8Array(ledgerAccount.tipHash);
+
+        // Epochs count from the ledger's genesis, the program refuses expired or future ones
+        const genesisTimestamp = Number(ledgerAccount.genesisTimestamp.toString());
+        const epoch = Math.max(0, Math.floor((Date.now() / 1000 - genesisTimestamp) / EPOCH_DURATION));
+
+        // The airdrop is indexed in the ledger page holding its position, pass the next page
+        // too in case other UTXOs get finalized before the airdrop lands
+        const page = BigInt(ledgerAccount.count.toString()) / BigInt(LEDGER_PAGE_SIZE);
+        const [ledgerPagePda, nextLedgerPagePda] = [page, page + BigInt(1)].map((p) => {
+            const pageBytes = Buffer.alloc(8);
+            pageBytes.writeBigUInt64LE(p);
+            return PublicKey.findProgramAddressSync(
+                [Buffer.from("ledger_page"), pageBytes],
+                program.programId
+            )[0];
+        });
 
         const outputResult = wasm.prepare_output(
             kyberKey,                               // receiver kyber public key
@@ -64,7 +83,8 @@ app.post("/airdrop", async (req, res) => {
             amount,                                 // amount
             prevUtxoHash,                           // prev_utxo_hash (ledger tip)
             epoch,                                  // epoch
-            false                                   // is_return
+            false,                                  // is_return
+            new Uint8Array(0)                       // spent_inputs (airdrops spend nothing)
         );
 
         // Extract values from prepare_output result
@@ -102,6 +122,7 @@ app.post("/airdrop", async (req, res) => {
 
             await program.methods.writeLoader(offset, Buffer.from(chunk))
                 .accounts({
+                    authority: faucetKeypair.publicKey,
                     loader: loaderKeypair.publicKey,
                 })
                 .signers([])
@@ -127,10 +148,14 @@ app.post("/airdrop", async (req, res) => {
             Buffer.from(encryptedPayload),
             Array.from(nonce) as number[],
             Array.from(ciphertextCommitment) as number[],
-            epoch
+            epoch,
+            Array.from(prevUtxoHash) as number[]
         )
             .accounts({
+                vault: vaultPubKey,
                 loader: loaderKeypair.publicKey,
+                ledgerPage: ledgerPagePda,
+                nextLedgerPage: nextLedgerPagePda,
             })
             .signers([faucetKeypair])
             .rpc();
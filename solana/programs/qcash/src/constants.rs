@@ -13,12 +13,66 @@ pub const SEED: &str = "anchor";
 /// Maximum number of provers in registry
 pub const MAX_PROVERS: usize = 100;
 
-/// Minimum attestations required for UTXO to be valid
+/// Default minimum attestations required for UTXO to be valid
+/// Written to ProgramConfig at init_program, changed with update_config
 pub const MIN_ATTESTATIONS_REQUIRED: u16 = 1;
 
+/// Default invalid votes required to reject a UTXO
+/// Written to ProgramConfig at init_program, changed with update_config
+/// Only a floor, see ProgramConfig::rejection_quorum
+pub const MIN_REJECTIONS_REQUIRED: u16 = 1;
+
+/// Default airdrops a single vault can receive per Solana epoch
+/// Written to ProgramConfig at init_program, changed with update_config
+pub const DEFAULT_MAX_AIRDROPS_PER_VAULT: u32 = 5;
+
+/// Default airdrops the faucet can make per Solana epoch across all vaults
+/// Written to ProgramConfig at init_program, changed with update_config
+pub const DEFAULT_MAX_AIRDROPS_PER_EPOCH: u32 = 1000;
+
+/// Default lamports escrowed per UTXO and paid to the provers that decide it
+/// Written to ProgramConfig at init_program, changed with update_config
+pub const DEFAULT_ATTESTATION_FEE: u64 = 50_000;
+
+/// Default minimum lamports a prover must have staked to vote (0.1 SOL)
+/// Written to ProgramConfig at init_program, changed with update_config
+pub const DEFAULT_MIN_PROVER_STAKE: u64 = 100_000_000;
+
+/// Default share of a prover's stake slashed per wrong vote, in basis points (10%)
+/// Written to ProgramConfig at init_program, changed with update_config
+pub const DEFAULT_SLASH_BPS: u16 = 1_000;
+
+/// Default seconds unstaked lamports wait before they can be withdrawn (7 days)
+/// Written to ProgramConfig at init_program, changed with update_config
+pub const DEFAULT_UNBONDING_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+/// Maximum members of an M-of-N admin signer set
+pub const MAX_ADMIN_SIGNERS: usize = 10;
+
 /// Maximum votes allowed per UTXO (same as MAX_PROVERS for simplicity)
 pub const MAX_VOTES_ALLOWED: usize = 10;
 
+/// Number of recent ledger tips a new UTXO may link to
+/// Proofs built against an older tip can't finalize and have to be proven again
+pub const LEDGER_TIP_WINDOW: usize = 64;
+
+/// Length of a ledger epoch in seconds, epoch 0 starts when the ledger is initialized
+pub const EPOCH_DURATION: i64 = 24 * 60 * 60;
+
+/// Number of past epochs a new UTXO may still declare
+/// Proofs from older epochs are expired and can't finalize
+pub const EPOCH_GRACE: u32 = 1;
+
+/// Number of finalized UTXO hashes per ledger index page
+/// 256 hashes keep a page (8 KiB) under the 10 KiB limit for accounts created by CPI
+pub const LEDGER_PAGE_SIZE: usize = 256;
+
+/// Maximum nullifiers (spent inputs) a single UTXO can carry
+pub const MAX_NULLIFIERS_PER_UTXO: usize = 16;
+
 /// Kyber ciphertext size
 pub const KYBER_CIPHERTEXT_SIZE: usize = 1088;
 
@@ -37,5 +91,20 @@ pub const PROVER_REGISTRY_SEED: &[u8] = b"prover_registry";
 /// Seed for ledger PDA
 pub const LEDGER_SEED: &[u8] = b"ledger";
 
+/// Seed for ledger index page PDA
+pub const LEDGER_PAGE_SEED: &[u8] = b"ledger_page";
+
 /// Seed for UTXO PDA
 pub const UTXO_SEED: &[u8] = b"utxo";
+
+/// Seed for nullifier PDA
+pub const NULLIFIER_SEED: &[u8] = b"nullifier";
+
+/// Seed for prover stake PDA
+pub const PROVER_STAKE_SEED: &[u8] = b"prover_stake";
+
+/// Seed for the per-vault airdrop limit PDA
+pub const AIRDROP_VAULT_SEED: &[u8] = b"airdrop_vault";
+
+/// Seed for the program-wide airdrop limit PDA
+pub const AIRDROP_EPOCH_SEED: &[u8] = b"airdrop_epoch";
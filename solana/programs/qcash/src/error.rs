@@ -74,4 +74,136 @@ pub enum ErrorCode {
 
     #[msg("Min attestations not met")]
     MinAttestationsNotMet,
+
+    #[msg("Too many nullifiers for a single UTXO")]
+    TooManyNullifiers,
+
+    #[msg("Nullifier accounts do not match the UTXO nullifiers")]
+    NullifierAccountsMismatch,
+
+    #[msg("Nullifier already spent")]
+    NullifierAlreadySpent,
+
+    #[msg("Output hash does not match the loader ciphertext and payload")]
+    OutputHashMismatch,
+
+    #[msg("Receiver and change outputs must use different loaders")]
+    DuplicateLoader,
+
+    #[msg("Change UTXO does not belong to this transfer")]
+    ChangeUtxoMismatch,
+
+    #[msg("Min attestations must be between 1 and the number of active provers (at most MAX_VOTES_ALLOWED)")]
+    InvalidMinAttestations,
+
+    #[msg("Min rejections must put min attestations out of reach and be at most the number of active provers (and MAX_VOTES_ALLOWED)")]
+    InvalidMinRejections,
+
+    #[msg("UTXO is no longer pending")]
+    UtxoNotPending,
+
+    #[msg("UTXO has not been rejected or expired")]
+    UtxoNotRejected,
+
+    #[msg("Signer is not the payer of this UTXO")]
+    UnauthorizedPayer,
+
+    #[msg("ZK proof account does not match the UTXO")]
+    ZkProofMismatch,
+
+    #[msg("ZK proof account already initialized")]
+    ZkProofAlreadyInitialized,
+
+    #[msg("ZK proof account not initialized")]
+    ZkProofNotInitialized,
+
+    #[msg("UTXO is still pending")]
+    UtxoNotSettled,
+
+    #[msg("ZK proof account is not owned by the program")]
+    ZkProofInvalidOwner,
+
+    #[msg("ZK proof has not been fully written")]
+    ZkProofIncomplete,
+
+    #[msg("ZK proof is already used by another UTXO")]
+    ZkProofAlreadyLocked,
+
+    #[msg("Signer is not the authority of this upload account")]
+    UnauthorizedWriter,
+
+    #[msg("Unauthorized: Only the faucet authority can airdrop")]
+    UnauthorizedFaucet,
+
+    #[msg("Vault has reached its airdrop limit for this epoch")]
+    AirdropVaultLimitReached,
+
+    #[msg("Faucet has reached its airdrop limit for this epoch")]
+    AirdropEpochLimitReached,
+
+    #[msg("Not enough admin signers signed this instruction")]
+    InsufficientAdminSignatures,
+
+    #[msg("Signer is not the pending admin")]
+    UnauthorizedPendingAdmin,
+
+    #[msg("Invalid admin signer set or threshold")]
+    InvalidAdminSigners,
+
+    #[msg("Prover already active")]
+    ProverAlreadyActive,
+
+    #[msg("Prover must be deactivated before it is removed")]
+    ProverStillActive,
+
+    #[msg("Slash share must be at most 10000 basis points")]
+    InvalidSlashBps,
+
+    #[msg("Unbonding period can't be negative")]
+    InvalidUnbondingPeriod,
+
+    #[msg("Stake account belongs to another owner")]
+    UnauthorizedStakeOwner,
+
+    #[msg("Stake account does not belong to this prover")]
+    StakeMismatch,
+
+    #[msg("Prover stake is below the minimum")]
+    InsufficientStake,
+
+    #[msg("Unbonding period has not ended")]
+    UnbondingNotEnded,
+
+    #[msg("Prover has no vote against the UTXO's outcome to slash")]
+    NoSlashableVote,
+
+    #[msg("Wrong votes on this UTXO must be slashed first")]
+    UnslashedVotes,
+
+    #[msg("Stake accounts don't match the provers that decided the UTXO")]
+    RewardAccountsMismatch,
+
+    #[msg("No attestation rewards to claim")]
+    NoRewards,
+
+    #[msg("UTXO links to a ledger tip that is no longer recent")]
+    StaleLedgerTip,
+
+    #[msg("Ledger page accounts don't match the pages being appended to")]
+    LedgerPageMismatch,
+
+    #[msg("UTXO declares an epoch that is expired or hasn't started")]
+    StaleEpoch,
+
+    #[msg("Account already has its current layout")]
+    AlreadyMigrated,
+
+    #[msg("No slashed lamports to sweep")]
+    NothingToSweep,
+
+    #[msg("Genesis timestamp is in the future")]
+    InvalidGenesisTimestamp,
+
+    #[msg("Finalized legacy UTXO does not link to the last one replayed")]
+    LegacyUtxoOutOfOrder,
 }
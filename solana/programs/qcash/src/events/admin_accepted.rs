@@ -0,0 +1,17 @@
+//! Event emitted when a proposed admin takes over
+//!
+//! This event is emitted after the pending admin accepts the handoff.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when a proposed admin takes over
+#[event]
+#[derive(Debug)]
+pub struct AdminAccepted {
+    /// Admin authority before the handoff
+    pub previous_admin: Pubkey,
+    /// New admin authority
+    pub new_admin: Pubkey,
+    /// Timestamp when accepted
+    pub timestamp: i64,
+}
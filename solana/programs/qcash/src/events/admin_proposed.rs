@@ -0,0 +1,17 @@
+//! Event emitted when a new admin is proposed
+//!
+//! This event is emitted after the admin proposes a handoff, or cancels one.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when a new admin is proposed
+#[event]
+#[derive(Debug)]
+pub struct AdminProposed {
+    /// Admin who made the proposal
+    pub admin: Pubkey,
+    /// Proposed admin, None when a proposal was cancelled
+    pub pending_admin: Option<Pubkey>,
+    /// Timestamp when proposed
+    pub timestamp: i64,
+}
@@ -0,0 +1,19 @@
+//! Event emitted when the admin signer set changes
+//!
+//! This event is emitted after the admin hands its rights to an M-of-N signer set.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when the admin signer set changes
+#[event]
+#[derive(Debug)]
+pub struct AdminSignersUpdated {
+    /// Admin signer who submitted the change
+    pub admin: Pubkey,
+    /// New admin signer set
+    pub signers: Vec<Pubkey>,
+    /// Admin signers required per instruction
+    pub threshold: u8,
+    /// Timestamp when updated
+    pub timestamp: i64,
+}
@@ -5,6 +5,8 @@ use anchor_lang::prelude::*;
 pub struct AirdropCompleted {
     /// UTXO account PDA
     pub utxo: Pubkey,
+    /// Vault receiving the airdrop
+    pub vault: Pubkey,
     /// UTXO hash
     pub utxo_hash: [u8; 32],
     /// Previous UTXO hash
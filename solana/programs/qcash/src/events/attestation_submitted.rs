@@ -36,6 +36,8 @@ pub struct AttestationSubmitted {
     pub new_ledger_tip: Option<[u8; 32]>,
     /// New ledger count (if threshold met)
     pub new_ledger_count: Option<u64>,
+    /// Escrowed attestation fee paid to the deciding provers (if this vote decided the UTXO)
+    pub fee_paid: u64,
     /// Timestamp when submitted
     pub timestamp: i64,
 }
@@ -0,0 +1,33 @@
+//! Event emitted when the program config is updated
+//!
+//! This event is emitted after the admin changes the program configuration.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when the program config is updated
+#[event]
+#[derive(Debug)]
+pub struct ConfigUpdated {
+    /// Admin who updated the config
+    pub admin: Pubkey,
+    /// Minimum attestations required for UTXO validity
+    pub min_attestations: u16,
+    /// Invalid votes required to reject a UTXO
+    pub min_rejections: u16,
+    /// Only signer allowed to airdrop
+    pub faucet_authority: Pubkey,
+    /// Airdrops a single vault can receive per Solana epoch
+    pub max_airdrops_per_vault: u32,
+    /// Airdrops the faucet can make per Solana epoch
+    pub max_airdrops_per_epoch: u32,
+    /// Lamports a prover must have staked to vote
+    pub min_prover_stake: u64,
+    /// Share of a prover's stake slashed per wrong vote, in basis points
+    pub slash_bps: u16,
+    /// Seconds unstaked lamports wait before they can be withdrawn
+    pub unbonding_period: i64,
+    /// Lamports escrowed per UTXO for the provers that decide it
+    pub attestation_fee: u64,
+    /// Timestamp when updated
+    pub timestamp: i64,
+}
@@ -0,0 +1,23 @@
+//! Event emitted when an old ledger is migrated
+//!
+//! This event is emitted after growing a ledger created before the running tip was appended to its layout.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when an old ledger is migrated
+#[event]
+#[derive(Debug)]
+pub struct LedgerMigrated {
+    /// Admin who migrated the ledger
+    pub admin: Pubkey,
+    /// Finalized UTXOs left to replay onto the running tip with migrate_utxo
+    pub legacy_count: u64,
+    /// Genesis timestamp kept from the original deployment
+    pub genesis_timestamp: i64,
+    /// Account size before the migration
+    pub old_size: u32,
+    /// Account size after the migration
+    pub new_size: u32,
+    /// Timestamp when migrated
+    pub timestamp: i64,
+}
@@ -2,49 +2,109 @@
 //!
 //! This module defines the various events emitted by the qcash program during its operations.
 
+pub mod admin_accepted;
+pub mod admin_proposed;
+pub mod admin_signers_updated;
 pub mod airdrop_completed;
 use anchor_lang::prelude::*;
 
 pub mod attestation_submitted;
+pub mod config_updated;
 pub mod ledger_initialized;
+pub mod ledger_migrated;
 pub mod loader_chunk_written;
 pub mod loader_initialized;
+pub mod nullifier_spent;
+pub mod program_config_migrated;
 pub mod program_initialized;
 pub mod prover_deactivated;
+pub mod prover_key_reset;
+pub mod prover_reactivated;
 pub mod prover_registered;
+pub mod prover_removed;
+pub mod prover_slashed;
+pub mod rewards_claimed;
+pub mod slashed_swept;
+pub mod stake_withdrawn;
+pub mod transfer_created;
+pub mod unstake_requested;
 pub mod utxo_created;
+pub mod utxo_migrated;
+pub mod utxo_reclaimed;
+pub mod utxo_rejected;
 pub mod vault_completed;
 pub mod vault_initialized;
 pub mod zk_proof_chunk_written;
+pub mod zk_proof_closed;
 pub mod zk_proof_initialized;
 
+pub use admin_accepted::*;
+pub use admin_proposed::*;
+pub use admin_signers_updated::*;
 pub use airdrop_completed::*;
 pub use attestation_submitted::*;
+pub use config_updated::*;
 pub use ledger_initialized::*;
+pub use ledger_migrated::*;
 pub use loader_chunk_written::*;
 pub use loader_initialized::*;
+pub use nullifier_spent::*;
+pub use program_config_migrated::*;
 pub use program_initialized::*;
 pub use prover_deactivated::*;
+pub use prover_key_reset::*;
+pub use prover_reactivated::*;
 pub use prover_registered::*;
+pub use prover_removed::*;
+pub use prover_slashed::*;
+pub use rewards_claimed::*;
+pub use slashed_swept::*;
+pub use stake_withdrawn::*;
+pub use transfer_created::*;
+pub use unstake_requested::*;
 pub use utxo_created::*;
+pub use utxo_migrated::*;
+pub use utxo_reclaimed::*;
+pub use utxo_rejected::*;
 pub use vault_completed::*;
 pub use vault_initialized::*;
 pub use zk_proof_chunk_written::*;
+pub use zk_proof_closed::*;
 pub use zk_proof_initialized::*;
 
 #[derive(Debug)]
 pub enum QcashEvent {
+    AdminAccepted(AdminAccepted),
+    AdminProposed(AdminProposed),
+    AdminSignersUpdated(AdminSignersUpdated),
     AttestationSubmitted(AttestationSubmitted),
+    ConfigUpdated(ConfigUpdated),
     LedgerInitialized(LedgerInitialized),
+    LedgerMigrated(LedgerMigrated),
     LoaderChunkWritten(LoaderChunkWritten),
     LoaderInitialized(LoaderInitialized),
+    NullifierSpent(NullifierSpent),
+    ProgramConfigMigrated(ProgramConfigMigrated),
     ProgramInitialized(ProgramInitialized),
     ProverDeactivated(ProverDeactivated),
+    ProverKeyReset(ProverKeyReset),
+    ProverReactivated(ProverReactivated),
     ProverRegistered(ProverRegistered),
+    ProverRemoved(ProverRemoved),
+    ProverSlashed(ProverSlashed),
+    RewardsClaimed(RewardsClaimed),
+    SlashedSwept(SlashedSwept),
+    StakeWithdrawn(StakeWithdrawn),
+    TransferCreated(TransferCreated),
+    UnstakeRequested(UnstakeRequested),
     UtxoCreated(UtxoCreated),
+    UtxoMigrated(UtxoMigrated),
+    UtxoReclaimed(UtxoReclaimed),
+    UtxoRejected(UtxoRejected),
     VaultCompleted(VaultCompleted),
     VaultInitialized(VaultInitialized),
     ZkProofChunkWritten(ZkProofChunkWritten),
+    ZkProofClosed(ZkProofClosed),
     ZkProofInitialized(ZkProofInitialized),
 }
 
@@ -61,14 +121,34 @@ impl QcashEvent {
 
         // Parse based on discriminator using match
         match discriminator {
+            AdminAccepted::DISCRIMINATOR => {
+                let event = AdminAccepted::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::AdminAccepted(event))
+            }
+            AdminProposed::DISCRIMINATOR => {
+                let event = AdminProposed::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::AdminProposed(event))
+            }
+            AdminSignersUpdated::DISCRIMINATOR => {
+                let event = AdminSignersUpdated::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::AdminSignersUpdated(event))
+            }
             AttestationSubmitted::DISCRIMINATOR => {
                 let event = AttestationSubmitted::deserialize(&mut &data[8..])?;
                 Ok(QcashEvent::AttestationSubmitted(event))
             }
+            ConfigUpdated::DISCRIMINATOR => {
+                let event = ConfigUpdated::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::ConfigUpdated(event))
+            }
             LedgerInitialized::DISCRIMINATOR => {
                 let event = LedgerInitialized::deserialize(&mut &data[8..])?;
                 Ok(QcashEvent::LedgerInitialized(event))
             }
+            LedgerMigrated::DISCRIMINATOR => {
+                let event = LedgerMigrated::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::LedgerMigrated(event))
+            }
             LoaderChunkWritten::DISCRIMINATOR => {
                 let event = LoaderChunkWritten::deserialize(&mut &data[8..])?;
                 Ok(QcashEvent::LoaderChunkWritten(event))
@@ -77,6 +157,14 @@ impl QcashEvent {
                 let event = LoaderInitialized::deserialize(&mut &data[8..])?;
                 Ok(QcashEvent::LoaderInitialized(event))
             }
+            NullifierSpent::DISCRIMINATOR => {
+                let event = NullifierSpent::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::NullifierSpent(event))
+            }
+            ProgramConfigMigrated::DISCRIMINATOR => {
+                let event = ProgramConfigMigrated::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::ProgramConfigMigrated(event))
+            }
             ProgramInitialized::DISCRIMINATOR => {
                 let event = ProgramInitialized::deserialize(&mut &data[8..])?;
                 Ok(QcashEvent::ProgramInitialized(event))
@@ -85,14 +173,62 @@ impl QcashEvent {
                 let event = ProverDeactivated::deserialize(&mut &data[8..])?;
                 Ok(QcashEvent::ProverDeactivated(event))
             }
+            ProverKeyReset::DISCRIMINATOR => {
+                let event = ProverKeyReset::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::ProverKeyReset(event))
+            }
+            ProverReactivated::DISCRIMINATOR => {
+                let event = ProverReactivated::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::ProverReactivated(event))
+            }
             ProverRegistered::DISCRIMINATOR => {
                 let event = ProverRegistered::deserialize(&mut &data[8..])?;
                 Ok(QcashEvent::ProverRegistered(event))
             }
+            ProverRemoved::DISCRIMINATOR => {
+                let event = ProverRemoved::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::ProverRemoved(event))
+            }
+            ProverSlashed::DISCRIMINATOR => {
+                let event = ProverSlashed::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::ProverSlashed(event))
+            }
+            RewardsClaimed::DISCRIMINATOR => {
+                let event = RewardsClaimed::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::RewardsClaimed(event))
+            }
+            SlashedSwept::DISCRIMINATOR => {
+                let event = SlashedSwept::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::SlashedSwept(event))
+            }
+            StakeWithdrawn::DISCRIMINATOR => {
+                let event = StakeWithdrawn::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::StakeWithdrawn(event))
+            }
+            TransferCreated::DISCRIMINATOR => {
+                let event = TransferCreated::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::TransferCreated(event))
+            }
+            UnstakeRequested::DISCRIMINATOR => {
+                let event = UnstakeRequested::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::UnstakeRequested(event))
+            }
             UtxoCreated::DISCRIMINATOR => {
                 let event = UtxoCreated::deserialize(&mut &data[8..])?;
                 Ok(QcashEvent::UtxoCreated(event))
             }
+            UtxoMigrated::DISCRIMINATOR => {
+                let event = UtxoMigrated::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::UtxoMigrated(event))
+            }
+            UtxoReclaimed::DISCRIMINATOR => {
+                let event = UtxoReclaimed::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::UtxoReclaimed(event))
+            }
+            UtxoRejected::DISCRIMINATOR => {
+                let event = UtxoRejected::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::UtxoRejected(event))
+            }
             VaultCompleted::DISCRIMINATOR => {
                 let event = VaultCompleted::deserialize(&mut &data[8..])?;
                 Ok(QcashEvent::VaultCompleted(event))
@@ -105,6 +241,10 @@ impl QcashEvent {
                 let event = ZkProofChunkWritten::deserialize(&mut &data[8..])?;
                 Ok(QcashEvent::ZkProofChunkWritten(event))
             }
+            ZkProofClosed::DISCRIMINATOR => {
+                let event = ZkProofClosed::deserialize(&mut &data[8..])?;
+                Ok(QcashEvent::ZkProofClosed(event))
+            }
             ZkProofInitialized::DISCRIMINATOR => {
                 let event = ZkProofInitialized::deserialize(&mut &data[8..])?;
                 Ok(QcashEvent::ZkProofInitialized(event))
@@ -0,0 +1,19 @@
+//! Event emitted when a nullifier is spent
+//!
+//! This event is emitted for each input nullifier recorded when a UTXO is finalized.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when a nullifier is spent
+#[event]
+#[derive(Debug)]
+pub struct NullifierSpent {
+    /// The nullifier committed by the guest
+    pub nullifier: [u8; 32],
+    /// Nullifier account PDA
+    pub nullifier_account: Pubkey,
+    /// Hash of the UTXO that spent it
+    pub utxo_hash: [u8; 32],
+    /// Timestamp when spent
+    pub timestamp: i64,
+}
@@ -0,0 +1,19 @@
+//! Event emitted when an old program config is migrated
+//!
+//! This event is emitted after growing a config created before fields were appended to its layout.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when an old program config is migrated
+#[event]
+#[derive(Debug)]
+pub struct ProgramConfigMigrated {
+    /// Admin who migrated the config
+    pub admin: Pubkey,
+    /// Account size before the migration
+    pub old_size: u32,
+    /// Account size after the migration
+    pub new_size: u32,
+    /// Timestamp when migrated
+    pub timestamp: i64,
+}
@@ -12,6 +12,8 @@ pub struct ProgramInitialized {
     pub admin: Pubkey,
     /// Minimum attestations required for UTXO validity
     pub min_attestations: u16,
+    /// Invalid votes required to reject a UTXO
+    pub min_rejections: u16,
     /// Program config PDA bump
     pub config_bump: u8,
     /// Prover registry PDA bump
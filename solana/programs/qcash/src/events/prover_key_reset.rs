@@ -0,0 +1,19 @@
+//! Event emitted when a prover's key is force-reset
+//!
+//! This event is emitted after the admin replaces the key hash of a prover that lost its rotation keys.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when a prover's key is force-reset
+#[event]
+#[derive(Debug)]
+pub struct ProverKeyReset {
+    /// Admin who reset the key
+    pub admin: Pubkey,
+    /// Unique identifier for the prover
+    pub unique_id: u64,
+    /// Hash of the key the prover signs with next
+    pub new_pubkey_hash: [u8; 32],
+    /// Timestamp when reset
+    pub timestamp: i64,
+}
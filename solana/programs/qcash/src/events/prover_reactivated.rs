@@ -0,0 +1,19 @@
+//! Event emitted when a prover is reactivated
+//!
+//! This event is emitted after the admin reactivates a deactivated prover.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when a prover is reactivated
+#[event]
+#[derive(Debug)]
+pub struct ProverReactivated {
+    /// Admin who reactivated the prover
+    pub admin: Pubkey,
+    /// Unique identifier for the prover
+    pub unique_id: u64,
+    /// Number of active provers after reactivation
+    pub active_provers: u16,
+    /// Timestamp when reactivated
+    pub timestamp: i64,
+}
@@ -18,6 +18,10 @@ pub struct ProverRegistered {
     pub prover_pubkey_hash: [u8; 32],
     /// Total number of provers after registration
     pub total_provers: u32,
+    /// Account that locked the stake
+    pub staker: Pubkey,
+    /// Lamports staked after registration
+    pub stake_amount: u64,
     /// Timestamp when registered
     pub timestamp: i64,
 }
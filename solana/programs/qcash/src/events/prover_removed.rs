@@ -0,0 +1,21 @@
+//! Event emitted when a prover is removed
+//!
+//! This event is emitted after the admin removes a deactivated prover from the prover registry.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when a prover is removed
+#[event]
+#[derive(Debug)]
+pub struct ProverRemoved {
+    /// Admin who removed the prover
+    pub admin: Pubkey,
+    /// Unique identifier of the removed prover
+    pub unique_id: u64,
+    /// Key hash the prover had when removed
+    pub prover_pubkey_hash: [u8; 32],
+    /// Total number of provers after removal
+    pub total_provers: u32,
+    /// Timestamp when removed
+    pub timestamp: i64,
+}
@@ -0,0 +1,27 @@
+//! Event emitted when a prover is slashed
+//!
+//! This event is emitted after a prover's stake is slashed for voting against a UTXO's final outcome.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when a prover's stake is slashed
+#[event]
+#[derive(Debug)]
+pub struct ProverSlashed {
+    /// Account that submitted the slash
+    pub authority: Pubkey,
+    /// Unique identifier of the slashed prover
+    pub unique_id: u64,
+    /// UTXO account carrying the wrong vote
+    pub utxo: Pubkey,
+    /// Hash of the UTXO
+    pub utxo_hash: [u8; 32],
+    /// Lamports slashed
+    pub amount: u64,
+    /// Lamports still staked
+    pub remaining_stake: u64,
+    /// Lamports still unbonding
+    pub remaining_unbonding: u64,
+    /// Timestamp when slashed
+    pub timestamp: i64,
+}
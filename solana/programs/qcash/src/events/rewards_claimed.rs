@@ -0,0 +1,19 @@
+//! Event emitted when a prover's attestation rewards are claimed
+//!
+//! This event is emitted after the stake owner withdraws the attestation fees the prover earned.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when attestation rewards are claimed
+#[event]
+#[derive(Debug)]
+pub struct RewardsClaimed {
+    /// Stake owner who received the rewards
+    pub owner: Pubkey,
+    /// Unique identifier of the prover
+    pub unique_id: u64,
+    /// Lamports claimed
+    pub amount: u64,
+    /// Timestamp when claimed
+    pub timestamp: i64,
+}
@@ -0,0 +1,19 @@
+//! Event emitted when slashed lamports are swept
+//!
+//! This event is emitted after the admin moves the slashed lamports held by the program config.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when slashed lamports are swept
+#[event]
+#[derive(Debug)]
+pub struct SlashedSwept {
+    /// Admin signer who swept the lamports
+    pub admin: Pubkey,
+    /// Account that received them
+    pub destination: Pubkey,
+    /// Lamports swept
+    pub amount: u64,
+    /// Timestamp when swept
+    pub timestamp: i64,
+}
@@ -0,0 +1,21 @@
+//! Event emitted when unbonded stake is withdrawn
+//!
+//! This event is emitted after the stake owner withdraws lamports whose unbonding period is over.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when unbonded stake is withdrawn
+#[event]
+#[derive(Debug)]
+pub struct StakeWithdrawn {
+    /// Stake owner who received the lamports
+    pub owner: Pubkey,
+    /// Unique identifier of the prover
+    pub unique_id: u64,
+    /// Lamports withdrawn
+    pub amount: u64,
+    /// Lamports still staked
+    pub remaining_stake: u64,
+    /// Timestamp when withdrawn
+    pub timestamp: i64,
+}
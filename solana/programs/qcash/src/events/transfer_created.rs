@@ -0,0 +1,27 @@
+//! Event emitted when a transfer is created
+//!
+//! This event is emitted after both UTXOs of a transfer are created.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when a transfer is created
+#[event]
+#[derive(Debug)]
+pub struct TransferCreated {
+    /// Receiver UTXO account PDA, the one provers vote on
+    pub receiver_utxo: Pubkey,
+    /// Receiver UTXO hash
+    pub receiver_utxo_hash: [u8; 32],
+    /// Change UTXO account PDA, finalized with the receiver UTXO
+    pub change_utxo: Pubkey,
+    /// Change UTXO hash
+    pub change_utxo_hash: [u8; 32],
+    /// Ledger tip the receiver UTXO links to
+    pub prev_utxo_hash: [u8; 32],
+    /// ZK proof reference
+    pub zk_proof: Pubkey,
+    /// Number of nullifiers spent when the transfer is finalized
+    pub nullifier_count: u8,
+    /// Timestamp when created
+    pub timestamp: i64,
+}
@@ -0,0 +1,25 @@
+//! Event emitted when a prover's stake starts unbonding
+//!
+//! This event is emitted after the stake owner moves part of the stake into unbonding.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when part of a prover's stake starts unbonding
+#[event]
+#[derive(Debug)]
+pub struct UnstakeRequested {
+    /// Stake owner
+    pub owner: Pubkey,
+    /// Unique identifier of the prover
+    pub unique_id: u64,
+    /// Lamports moved into unbonding
+    pub amount: u64,
+    /// Lamports still staked
+    pub remaining_stake: u64,
+    /// Total lamports unbonding
+    pub unbonding_amount: u64,
+    /// Unix timestamp when the unbonding lamports can be withdrawn
+    pub unbonding_ends_at: i64,
+    /// Timestamp when requested
+    pub timestamp: i64,
+}
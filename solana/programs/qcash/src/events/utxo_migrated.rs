@@ -0,0 +1,28 @@
+//! Event emitted when an old UTXO is migrated
+//!
+//! This event is emitted after growing a UTXO created before output_hash and the later fields were appended to its layout.
+
+use anchor_lang::prelude::*;
+use crate::state::UtxoStatus;
+
+/// Event emitted when an old UTXO is migrated
+#[event]
+#[derive(Debug)]
+pub struct UtxoMigrated {
+    /// Admin who migrated the UTXO
+    pub admin: Pubkey,
+    /// UTXO account
+    pub utxo: Pubkey,
+    /// Hash of the UTXO
+    pub utxo_hash: [u8; 32],
+    /// Status rebuilt from its votes
+    pub status: UtxoStatus,
+    /// Position it was replayed at on the ledger, 0 unless finalized
+    pub ledger_index: u64,
+    /// Account size before the migration
+    pub old_size: u32,
+    /// Account size after the migration
+    pub new_size: u32,
+    /// Timestamp when migrated
+    pub timestamp: i64,
+}
@@ -0,0 +1,23 @@
+//! Event emitted when a rejected UTXO is reclaimed
+//!
+//! This event is emitted after the payer closes a rejected UTXO and its proof account.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when a rejected UTXO is reclaimed
+#[event]
+#[derive(Debug)]
+pub struct UtxoReclaimed {
+    /// UTXO account PDA that was closed
+    pub utxo: Pubkey,
+    /// UTXO hash
+    pub utxo_hash: [u8; 32],
+    /// Change UTXO closed along with it, if created by transfer
+    pub change_utxo: Option<Pubkey>,
+    /// Payer who received the rent
+    pub payer: Pubkey,
+    /// Total lamports returned to the payer
+    pub lamports_reclaimed: u64,
+    /// Timestamp when reclaimed
+    pub timestamp: i64,
+}
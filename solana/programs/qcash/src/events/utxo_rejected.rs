@@ -0,0 +1,28 @@
+//! Event emitted when a UTXO is rejected
+//!
+//! This event is emitted when a UTXO collects enough invalid votes to reach the rejection threshold,
+//! or is voted down after it went stale.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when a UTXO is rejected
+#[event]
+#[derive(Debug)]
+pub struct UtxoRejected {
+    /// UTXO account PDA
+    pub utxo: Pubkey,
+    /// UTXO hash
+    pub utxo_hash: [u8; 32],
+    /// Change UTXO rejected along with it, if created by transfer
+    pub change_utxo: Option<Pubkey>,
+    /// Account that can reclaim the rent
+    pub payer: Pubkey,
+    /// Number of valid votes
+    pub valid_votes: u16,
+    /// Number of invalid votes
+    pub invalid_votes: u16,
+    /// Settled as UtxoStatus::Expired, no vote on it is slashable
+    pub expired: bool,
+    /// Timestamp when rejected
+    pub timestamp: i64,
+}
@@ -0,0 +1,21 @@
+//! Event emitted when a ZK proof account is closed
+//!
+//! This event is emitted after a settled proof account is closed and its rent returned.
+
+use anchor_lang::prelude::*;
+
+/// Event emitted when a ZK proof account is closed
+#[event]
+#[derive(Debug)]
+pub struct ZkProofClosed {
+    /// ZK proof account that was closed
+    pub zk_proof: Pubkey,
+    /// Settled UTXO that referenced the proof
+    pub utxo: Pubkey,
+    /// Payer who received the lamports
+    pub payer: Pubkey,
+    /// Lamports returned to the payer
+    pub lamports: u64,
+    /// Timestamp when closed
+    pub timestamp: i64,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::AdminAccepted;
+use crate::state::ProgramConfig;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    /// Proposed admin taking over
+    pub new_admin: Signer<'info>,
+
+    /// Program configuration
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.pending_admin == Some(new_admin.key()) @ ErrorCode::UnauthorizedPendingAdmin,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Complete an admin handoff started with propose_admin
+/// The new admin is a single key, any admin signer set is dropped
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    let new_admin = ctx.accounts.new_admin.key();
+    let previous_admin = program_config.admin_authority;
+
+    program_config.admin_authority = new_admin;
+    program_config.pending_admin = None;
+    program_config.admin_signers.clear();
+    program_config.admin_threshold = 0;
+
+    // Emit event
+    emit!(AdminAccepted {
+        previous_admin,
+        new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -2,12 +2,15 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::error::ErrorCode;
 use crate::events::AirdropCompleted;
-use crate::state::{Utxo, Ledger, Loader};
+use crate::state::{AirdropEpochLimit, AirdropVaultLimit, Ledger, LedgerPage, Loader, ProgramConfig, Utxo, Vault};
 
 /// Airdrop instruction - creates a UTXO without proof verification
 /// This is used by the faucet to distribute tokens without going through
 /// the full ZK proof and attestation flow.
 /// Security Note: This bypasses proof verification and immediately finalizes.
+/// Only the faucet authority in ProgramConfig may call it, each vault and the
+/// faucet as a whole are rate limited per Solana epoch, and mainnet builds
+/// compile it out entirely.
 /// The ciphertext_commitment must be pre-calculated by the faucet to match
 /// the prover's verification logic (hash of plaintext payload fields).
 #[derive(Accounts)]
@@ -16,6 +19,37 @@ pub struct Airdrop<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    /// Program configuration, holds the faucet authority and limits
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.is_faucet(&signer.key()) @ ErrorCode::UnauthorizedFaucet,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Vault receiving the airdrop
+    pub vault: Box<Account<'info, Vault>>,
+
+    /// Airdrops this vault received in the current epoch
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = AirdropVaultLimit::SIZE,
+        seeds = [AIRDROP_VAULT_SEED, vault.key().as_ref()],
+        bump,
+    )]
+    pub airdrop_vault_limit: Box<Account<'info, AirdropVaultLimit>>,
+
+    /// Airdrops made in the current epoch across all vaults
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = AirdropEpochLimit::SIZE,
+        seeds = [AIRDROP_EPOCH_SEED],
+        bump,
+    )]
+    pub airdrop_epoch_limit: Box<Account<'info, AirdropEpochLimit>>,
+
     #[account(
         mut,
         seeds = [LEDGER_SEED],
@@ -23,6 +57,14 @@ pub struct Airdrop<'info> {
     )]
     pub ledger: Box<Account<'info, Ledger>>,
 
+    /// CHECK: ledger index page holding `ledger.count`, checked and written in the handler
+    #[account(mut)]
+    pub ledger_page: UncheckedAccount<'info>,
+
+    /// CHECK: the page after `ledger_page`, in case the ledger moved on since the faucet read it
+    #[account(mut)]
+    pub next_ledger_page: UncheckedAccount<'info>,
+
     /// New UTXO account to be created
     /// Uses same UTXO_SEED as regular UTXOs - differentiated by optional voting fields
     #[account(
@@ -34,11 +76,12 @@ pub struct Airdrop<'info> {
     )]
     pub utxo: Box<Account<'info, Utxo>>,
 
-    /// Loader account containing Kyber ciphertext (will be closed after use)
+    /// Loader account containing Kyber ciphertext, uploaded by the signer (will be closed after use)
     #[account(
         mut,
         close = signer,
-        constraint = loader.ciphertext.len() == KYBER_CIPHERTEXT_SIZE
+        constraint = loader.ciphertext.len() == KYBER_CIPHERTEXT_SIZE,
+        constraint = loader.authority == signer.key() @ ErrorCode::UnauthorizedWriter
     )]
     pub loader: Box<Account<'info, Loader>>,
 
@@ -52,6 +95,7 @@ pub fn airdrop(
     nonce: [u8; NONCE_SIZE],
     ciphertext_commitment: [u8; 32],
     epoch: u32,
+    prev_utxo_hash: [u8; 32],
 ) -> Result<()> {
     let ledger = &mut ctx.accounts.ledger;
     let loader = &ctx.accounts.loader;
@@ -59,7 +103,27 @@ pub fn airdrop(
     let utxo = &mut ctx.accounts.utxo;
     let bump = ctx.bumps.utxo;
 
-    let prev_utxo_hash = ledger.get_tip_hash();
+    // The faucet hashed the UTXO against a tip that may have moved since
+    require!(ledger.is_recent_tip(&prev_utxo_hash), ErrorCode::StaleLedgerTip);
+
+    // Airdrops declare a live epoch like any other UTXO
+    require!(
+        ledger.is_live_epoch(epoch, Clock::get()?.unix_timestamp),
+        ErrorCode::StaleEpoch
+    );
+
+    // Rate limit per vault and per faucet, counted in Solana epochs
+    let program_config = &ctx.accounts.program_config;
+    let current_epoch = Clock::get()?.epoch;
+
+    let vault_limit = &mut ctx.accounts.airdrop_vault_limit;
+    vault_limit.vault = ctx.accounts.vault.key();
+    vault_limit.bump = ctx.bumps.airdrop_vault_limit;
+    vault_limit.record(current_epoch, program_config.max_airdrops_per_vault)?;
+
+    let epoch_limit = &mut ctx.accounts.airdrop_epoch_limit;
+    epoch_limit.bump = ctx.bumps.airdrop_epoch_limit;
+    epoch_limit.record(current_epoch, program_config.max_airdrops_per_epoch)?;
 
     // Verify payload size
     require!(
@@ -78,15 +142,24 @@ pub fn airdrop(
         nonce,
         encrypted_payload,
         loader.ciphertext,
+        ctx.accounts.signer.key(),
         bump,
     );
 
-    // Immediately update ledger tip (no attestation required for airdrop)
-    ledger.update_tip(utxo_hash);
+    // Immediately append to the ledger (no attestation required for airdrop)
+    utxo.append_to_ledger(ledger);
+    LedgerPage::append(
+        &[ctx.accounts.ledger_page.as_ref(), ctx.accounts.next_ledger_page.as_ref()],
+        utxo.ledger_index,
+        &utxo_hash,
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
 
     // Emit event
     emit!(AirdropCompleted {
         utxo: utxo_key,
+        vault: ctx.accounts.vault.key(),
         utxo_hash,
         prev_utxo_hash,
         epoch,
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::RewardsClaimed;
+use crate::state::ProverStake;
+
+#[derive(Accounts)]
+#[instruction(unique_id: u64)]
+pub struct ClaimRewards<'info> {
+    /// Stake owner, receives the rewards
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Prover's stake, holds the earned attestation fees
+    #[account(
+        mut,
+        seeds = [PROVER_STAKE_SEED, unique_id.to_le_bytes().as_ref()],
+        bump = prover_stake.bump,
+        has_one = owner @ ErrorCode::UnauthorizedStakeOwner,
+    )]
+    pub prover_stake: Box<Account<'info, ProverStake>>,
+}
+
+/// Withdraw all attestation fees the prover has earned, no unbonding needed
+pub fn claim_rewards(ctx: Context<ClaimRewards>, unique_id: u64) -> Result<()> {
+    let prover_stake = &mut ctx.accounts.prover_stake;
+
+    let amount = prover_stake.rewards;
+    require!(amount > 0, ErrorCode::NoRewards);
+
+    prover_stake.rewards = 0;
+    prover_stake.sub_lamports(amount)?;
+    ctx.accounts.owner.add_lamports(amount)?;
+
+    // Emit event
+    emit!(RewardsClaimed {
+        owner: ctx.accounts.owner.key(),
+        unique_id,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::events::ZkProofClosed;
+use crate::state::{Utxo, ZkProof};
+
+#[derive(Accounts)]
+pub struct CloseZkProof<'info> {
+    /// ZK proof account to close
+    /// CHECK: Owner is checked here, the header is read in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub zk_proof: AccountInfo<'info>,
+
+    /// UTXO the proof is locked to, must be finalized or rejected
+    #[account(
+        constraint = utxo.zk_proof_pubkey == Some(zk_proof.key()) @ ErrorCode::ZkProofMismatch,
+        constraint = !utxo.is_pending() @ ErrorCode::UtxoNotSettled,
+    )]
+    pub utxo: Box<Account<'info, Utxo>>,
+
+    /// Payer recorded in the proof header, receives the lamports
+    /// CHECK: Matched against the ZkProof header in the handler
+    #[account(mut)]
+    pub payer: AccountInfo<'info>,
+}
+
+/// Anyone can close a settled proof, the lamports always go to the recorded payer
+pub fn close_zk_proof(ctx: Context<CloseZkProof>) -> Result<()> {
+    let zk_proof = &ctx.accounts.zk_proof;
+    let payer = &ctx.accounts.payer;
+
+    {
+        let data = zk_proof.try_borrow_data()?;
+        require!(ZkProof::is_initialized(&data), ErrorCode::ZkProofNotInitialized);
+        require_keys_eq!(ZkProof::read_payer(&data), payer.key(), ErrorCode::UnauthorizedPayer);
+        require_keys_eq!(
+            ZkProof::read_locked_by(&data),
+            ctx.accounts.utxo.key(),
+            ErrorCode::ZkProofMismatch
+        );
+    }
+
+    let lamports = zk_proof.lamports();
+    ZkProof::close(zk_proof, payer)?;
+
+    // Emit event
+    emit!(ZkProofClosed {
+        zk_proof: zk_proof.key(),
+        utxo: ctx.accounts.utxo.key(),
+        payer: payer.key(),
+        lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,43 +1,62 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use crate::constants::*;
 use crate::error::ErrorCode;
 use crate::events::UtxoCreated;
-use crate::state::{Utxo, Ledger, Loader};
+use crate::state::{Utxo, Ledger, Loader, ProgramConfig, ZkProof};
 
 #[derive(Accounts)]
-#[instruction(utxo_hash: [u8; 32], encrypted_payload: Vec<u8>)]
+#[instruction(
+    utxo_hash: [u8; 32],
+    encrypted_payload: Vec<u8>,
+    nonce: [u8; NONCE_SIZE],
+    ciphertext_commitment: [u8; 32],
+    epoch: u32,
+    nullifiers: Vec<[u8; 32]>,
+    output_hash: [u8; 32],
+    prev_utxo_hash: [u8; 32],
+)]
 pub struct CreateUtxo<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    /// Ledger account to verify previous UTXO hash
+    /// Ledger account, the UTXO must link to one of its recent tips
     #[account(
         seeds = [LEDGER_SEED],
         bump = ledger.bump,
     )]
     pub ledger: Box<Account<'info, Ledger>>,
 
+    /// Program configuration, holds the attestation fee
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
     /// New UTXO account to be created
     /// PDA: ["utxo", utxo_hash]
     #[account(
         init,
         payer = signer,
-        space = Utxo::size(encrypted_payload.len()),
+        space = Utxo::size(encrypted_payload.len(), nullifiers.len()),
         seeds = [UTXO_SEED, utxo_hash.as_ref()],
         bump,
     )]
     pub utxo: Box<Account<'info, Utxo>>,
 
-    /// Loader account containing Kyber ciphertext (will be closed after use)
+    /// Loader account containing Kyber ciphertext, uploaded by the signer (will be closed after use)
     #[account(
         mut,
         close = signer,
-        constraint = loader.ciphertext.len() == KYBER_CIPHERTEXT_SIZE
+        constraint = loader.ciphertext.len() == KYBER_CIPHERTEXT_SIZE,
+        constraint = loader.authority == signer.key() @ ErrorCode::UnauthorizedWriter
     )]
     pub loader: Box<Account<'info, Loader>>,
 
-    /// ZK Proof account reference
-    /// CHECK: Just storing the pubkey reference, not accessing data
+    /// ZK Proof account, locked to this UTXO
+    /// CHECK: Owner, header and completeness are validated in the handler
+    #[account(mut)]
     pub zk_proof: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
@@ -50,6 +69,9 @@ pub fn create_utxo(
     nonce: [u8; NONCE_SIZE],
     ciphertext_commitment: [u8; 32],
     epoch: u32,
+    nullifiers: Vec<[u8; 32]>,
+    output_hash: [u8; 32],
+    prev_utxo_hash: [u8; 32],
 ) -> Result<()> {
     let ledger = &ctx.accounts.ledger;
     let loader = &ctx.accounts.loader;
@@ -57,7 +79,14 @@ pub fn create_utxo(
     let utxo = &mut ctx.accounts.utxo;
     let bump = ctx.bumps.utxo;
 
-    let prev_utxo_hash = ledger.get_tip_hash();
+    // The proof links to the ledger tip it was built against, which may have moved since
+    require!(ledger.is_recent_tip(&prev_utxo_hash), ErrorCode::StaleLedgerTip);
+
+    // The proof committed the epoch it was built in, expired proofs have to be redone
+    require!(
+        ledger.is_live_epoch(epoch, Clock::get()?.unix_timestamp),
+        ErrorCode::StaleEpoch
+    );
 
     // Verify payload size
     require!(
@@ -65,6 +94,22 @@ pub fn create_utxo(
         ErrorCode::PayloadTooLarge
     );
 
+    // Verify nullifier count, they are spent when this UTXO is finalized
+    require!(
+        nullifiers.len() <= MAX_NULLIFIERS_PER_UTXO,
+        ErrorCode::TooManyNullifiers
+    );
+
+    // Both sides of this check come from the client, it only pins output_hash to the stored bytes
+    // The binding to what the guest encrypted happens in the node, which compares it with the journal
+    require!(
+        Utxo::compute_output_hash(&loader.ciphertext, &nonce, &encrypted_payload) == output_hash,
+        ErrorCode::OutputHashMismatch
+    );
+
+    // The proof must be a complete qcash proof, uploaded by the signer, that no other UTXO has used
+    ZkProof::validate_and_lock(&ctx.accounts.zk_proof, &ctx.accounts.signer.key(), &utxo_key)?;
+
     // Initialize UTXO
     utxo.initialize(
         epoch,
@@ -75,9 +120,27 @@ pub fn create_utxo(
         encrypted_payload,
         loader.ciphertext,
         ctx.accounts.zk_proof.key(),
+        nullifiers,
+        ctx.accounts.signer.key(),
         bump,
     );
 
+    // Escrow the provers' fee in the UTXO until its outcome is decided
+    let attestation_fee = ctx.accounts.program_config.attestation_fee;
+    if attestation_fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: utxo.to_account_info(),
+                },
+            ),
+            attestation_fee,
+        )?;
+        utxo.attestation_fee = attestation_fee;
+    }
+
     // Emit event
     emit!(UtxoCreated {
         utxo: utxo_key,
@@ -1,12 +1,12 @@
 use anchor_lang::prelude::*;
 use solana_sha256_hasher::hash;
 use crate::constants::*;
-use crate::error::ErrorCode;
 use crate::events::ProverDeactivated;
 use crate::state::{ProgramConfig, ProverRegistry};
 
 #[derive(Accounts)]
 pub struct DeactivateProver<'info> {
+    /// Admin, or one of the admin signers with the others passed as remaining accounts
     #[account(mut)]
     pub admin: Signer<'info>,
 
@@ -14,7 +14,6 @@ pub struct DeactivateProver<'info> {
     #[account(
         seeds = [PROGRAM_CONFIG_SEED],
         bump = program_config.bump,
-        constraint = program_config.is_admin(&admin.key()) @ ErrorCode::UnauthorizedAdmin,
     )]
     pub program_config: Box<Account<'info, ProgramConfig>>,
 
@@ -32,6 +31,10 @@ pub struct DeactivateProver<'info> {
 }
 
 pub fn deactivate_prover(ctx: Context<DeactivateProver>) -> Result<()> {
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
     let prover_registry = &mut ctx.accounts.prover_registry;
     let prover_pubkey = ctx.accounts.prover_pubkey.key();
 
@@ -15,7 +15,7 @@ pub struct InitLedger<'info> {
         seeds = [LEDGER_SEED],
         bump,
     )]
-    pub ledger: Account<'info, Ledger>,
+    pub ledger: Box<Account<'info, Ledger>>,
 
     pub system_program: Program<'info, System>,
 }
@@ -24,14 +24,16 @@ pub fn init_ledger(ctx: Context<InitLedger>) -> Result<()> {
     let ledger = &mut ctx.accounts.ledger;
     let bump = ctx.bumps.ledger;
 
-    ledger.initialize(bump);
+    let now = Clock::get()?.unix_timestamp;
+
+    ledger.initialize(bump, now);
 
     // Emit event
     emit!(LedgerInitialized {
         count: ledger.count,
         genesis_hash: ledger.last_valid_utxo_hash,
         bump,
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: now,
     });
 
     Ok(())
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
+use crate::error::ErrorCode;
 use crate::events::ProgramInitialized;
 use crate::state::{ProgramConfig, ProverRegistry};
 
@@ -38,10 +39,17 @@ pub fn init_program(ctx: Context<InitProgram>) -> Result<()> {
     let config_bump = ctx.bumps.program_config;
     let registry_bump = ctx.bumps.prover_registry;
 
+    // No provers are registered yet, the default rejection quorum must still be safe
+    require!(
+        MIN_REJECTIONS_REQUIRED >= ProgramConfig::min_safe_rejections(0, MIN_ATTESTATIONS_REQUIRED),
+        ErrorCode::InvalidMinRejections
+    );
+
     // Initialize program config with admin as the deployer
     program_config.initialize(
         ctx.accounts.admin.key(),
         MIN_ATTESTATIONS_REQUIRED,
+        MIN_REJECTIONS_REQUIRED,
         config_bump,
     );
 
@@ -52,6 +60,7 @@ pub fn init_program(ctx: Context<InitProgram>) -> Result<()> {
     emit!(ProgramInitialized {
         admin: ctx.accounts.admin.key(),
         min_attestations: MIN_ATTESTATIONS_REQUIRED,
+        min_rejections: MIN_REJECTIONS_REQUIRED,
         config_bump,
         registry_bump,
         timestamp: Clock::get()?.unix_timestamp,
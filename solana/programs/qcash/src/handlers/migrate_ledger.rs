@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::LedgerMigrated;
+use crate::state::{Ledger, ProgramConfig};
+use crate::utils::grow_account;
+
+#[derive(Accounts)]
+pub struct MigrateLedger<'info> {
+    /// Admin, pays for the extra space
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program configuration, holds the admin
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Ledger, still at Ledger::LEGACY_SIZE
+    /// CHECK: The legacy layout is too short to deserialize as a Ledger, the PDA and owner
+    /// are checked here and the discriminator in the handler
+    #[account(
+        mut,
+        seeds = [LEDGER_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub ledger: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a ledger created before the running tip was appended after `bump`
+/// The running tip restarts from genesis, its finalized UTXOs are replayed onto it by
+/// migrate_utxo. `genesis_timestamp` is when the ledger was first initialized, so the epochs
+/// UTXOs already declared keep their meaning
+pub fn migrate_ledger(ctx: Context<MigrateLedger>, genesis_timestamp: i64) -> Result<()> {
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
+    let ledger_info = ctx.accounts.ledger.to_account_info();
+    let old_size = ledger_info.data_len();
+    require!(old_size < Ledger::SIZE, ErrorCode::AlreadyMigrated);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(genesis_timestamp <= now, ErrorCode::InvalidGenesisTimestamp);
+
+    // Legacy layout: discriminator | count | last_valid_utxo_hash | bump | padding
+    let (count, bump) = {
+        let data = ledger_info.try_borrow_data()?;
+        require!(
+            old_size >= Ledger::LEGACY_SIZE && data[..8] == *Ledger::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        let count = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        (count, data[48])
+    };
+
+    grow_account(
+        &ledger_info,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        Ledger::SIZE,
+    )?;
+
+    let ledger = Ledger::from_legacy(count, bump, genesis_timestamp);
+    ledger.try_serialize(&mut &mut ledger_info.try_borrow_mut_data()?[..])?;
+
+    // Emit event
+    emit!(LedgerMigrated {
+        admin: ctx.accounts.admin.key(),
+        legacy_count: count,
+        genesis_timestamp,
+        old_size: old_size as u32,
+        new_size: Ledger::SIZE as u32,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::ProgramConfigMigrated;
+use crate::state::ProgramConfig;
+use crate::utils::grow_account;
+
+#[derive(Accounts)]
+pub struct MigrateProgramConfig<'info> {
+    /// Admin, pays for the extra space
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program configuration, still at ProgramConfig::LEGACY_SIZE
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a config created before fields were appended after `bump` and set those fields
+/// to their defaults, they read as zeros until then
+/// Runs once, a migrated config's zeros may be deliberate (e.g. a paused faucet)
+pub fn migrate_program_config(ctx: Context<MigrateProgramConfig>) -> Result<()> {
+    // Old configs have no signer set, so this is the single admin check
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
+    let config_info = ctx.accounts.program_config.to_account_info();
+    let old_size = config_info.data_len();
+    require!(old_size < ProgramConfig::SIZE, ErrorCode::AlreadyMigrated);
+
+    grow_account(
+        &config_info,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ProgramConfig::SIZE,
+    )?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.set_appended_defaults();
+
+    // Emit event
+    emit!(ProgramConfigMigrated {
+        admin: ctx.accounts.admin.key(),
+        old_size: old_size as u32,
+        new_size: ProgramConfig::SIZE as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
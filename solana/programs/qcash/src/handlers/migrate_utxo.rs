@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::UtxoMigrated;
+use crate::state::{Ledger, LedgerPage, LegacyUtxo, ProgramConfig, Utxo, UtxoStatus};
+use crate::utils::grow_account;
+
+#[derive(Accounts)]
+#[instruction(utxo_hash: [u8; 32])]
+pub struct MigrateUtxo<'info> {
+    /// Admin, pays for the extra space
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program configuration, holds the admin
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// UTXO, still at the size the first release gave it
+    /// CHECK: The legacy layout does not deserialize as a Utxo, the PDA and owner are checked
+    /// here and the discriminator and size in the handler
+    #[account(
+        mut,
+        seeds = [UTXO_SEED, utxo_hash.as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub utxo: UncheckedAccount<'info>,
+
+    /// Ledger, already migrated, finalized legacy UTXOs are replayed onto its running tip
+    #[account(
+        mut,
+        seeds = [LEDGER_SEED],
+        bump = ledger.bump,
+    )]
+    pub ledger: Box<Account<'info, Ledger>>,
+
+    /// CHECK: ledger index page holding `ledger.count`, checked and written in the handler
+    /// Only written for a finalized UTXO, created if it lands on the first slot of the page
+    #[account(mut)]
+    pub ledger_page: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a UTXO created before output_hash and the later fields were appended after `bump`
+/// Finalized ones are appended to the running tip and indexed so inputs can be proven from
+/// them. They must be migrated in their original ledger order: walking prev_utxo_hash back
+/// from the old last_valid_utxo_hash, oldest first
+pub fn migrate_utxo(ctx: Context<MigrateUtxo>, _utxo_hash: [u8; 32]) -> Result<()> {
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
+    let utxo_info = ctx.accounts.utxo.to_account_info();
+    let old_size = utxo_info.data_len();
+
+    let legacy = {
+        let data = utxo_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *Utxo::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyUtxo::deserialize(&mut &data[8..])?
+    };
+    // A current account parses as a legacy one too, only its size tells them apart
+    require!(old_size == Utxo::legacy_size(&legacy), ErrorCode::AlreadyMigrated);
+
+    let new_size = if legacy.votes.is_some() {
+        Utxo::size(legacy.encrypted_payload.len(), 0)
+    } else {
+        Utxo::size_airdrop(legacy.encrypted_payload.len())
+    };
+    grow_account(
+        &utxo_info,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        new_size,
+    )?;
+
+    let mut utxo = Utxo::from_legacy(legacy, ctx.accounts.admin.key());
+    if utxo.status == UtxoStatus::Finalized {
+        let ledger = &mut ctx.accounts.ledger;
+        require!(ledger.is_next_legacy(&utxo.prev_utxo_hash), ErrorCode::LegacyUtxoOutOfOrder);
+
+        utxo.append_to_ledger(ledger);
+        LedgerPage::append(
+            &[ctx.accounts.ledger_page.as_ref()],
+            utxo.ledger_index,
+            &utxo.utxo_hash,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        ledger.legacy_cursor = utxo.utxo_hash;
+        ledger.legacy_remaining -= 1;
+    }
+    utxo.try_serialize(&mut &mut utxo_info.try_borrow_mut_data()?[..])?;
+
+    // Emit event
+    emit!(UtxoMigrated {
+        admin: ctx.accounts.admin.key(),
+        utxo: utxo_info.key(),
+        utxo_hash: utxo.utxo_hash,
+        status: utxo.status,
+        ledger_index: utxo.ledger_index,
+        old_size: old_size as u32,
+        new_size: new_size as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -4,13 +4,31 @@ pub mod init_program;
 pub mod init_ledger;
 // pub mod append_to_ledger;
 pub mod upload_ciphertext;
-// pub mod transfer;
+pub mod transfer;
 pub mod upload_zk_proof;
 pub mod create_utxo;
 pub mod register_prover;
 pub mod deactivate_prover;
+pub mod reactivate_prover;
+pub mod remove_prover;
+pub mod reset_prover_key;
 pub mod submit_attestation;
+#[cfg(not(feature = "mainnet"))]
 pub mod airdrop;
+pub mod update_config;
+pub mod migrate_program_config;
+pub mod migrate_ledger;
+pub mod migrate_utxo;
+pub mod reclaim_rejected_utxo;
+pub mod close_zk_proof;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod set_admin_signers;
+pub mod request_unstake;
+pub mod withdraw_stake;
+pub mod slash_prover;
+pub mod sweep_slashed;
+pub mod claim_rewards;
 
 pub use init_vault::*;
 pub use complete_vault::*;
@@ -18,10 +36,28 @@ pub use init_program::*;
 pub use init_ledger::*;
 // pub use append_to_ledger::*;
 pub use upload_ciphertext::*;
-// pub use transfer::*;
+pub use transfer::*;
 pub use upload_zk_proof::*;
 pub use create_utxo::*;
 pub use register_prover::*;
 pub use deactivate_prover::*;
+pub use reactivate_prover::*;
+pub use remove_prover::*;
+pub use reset_prover_key::*;
 pub use submit_attestation::*;
+#[cfg(not(feature = "mainnet"))]
 pub use airdrop::*;
+pub use update_config::*;
+pub use migrate_program_config::*;
+pub use migrate_ledger::*;
+pub use migrate_utxo::*;
+pub use reclaim_rejected_utxo::*;
+pub use close_zk_proof::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use set_admin_signers::*;
+pub use request_unstake::*;
+pub use withdraw_stake::*;
+pub use slash_prover::*;
+pub use sweep_slashed::*;
+pub use claim_rewards::*;
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::AdminProposed;
+use crate::state::ProgramConfig;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    /// Admin, or one of the admin signers with the others passed as remaining accounts
+    pub admin: Signer<'info>,
+
+    /// Program configuration
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Propose a new single admin, `None` cancels a pending proposal
+/// The current admin stays in charge until the proposed key calls accept_admin
+pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
+    ctx.accounts.program_config.pending_admin = new_admin;
+
+    // Emit event
+    emit!(AdminProposed {
+        admin: ctx.accounts.admin.key(),
+        pending_admin: new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::ProverReactivated;
+use crate::state::{ProgramConfig, ProverRegistry};
+
+#[derive(Accounts)]
+pub struct ReactivateProver<'info> {
+    /// Admin, or one of the admin signers with the others passed as remaining accounts
+    pub admin: Signer<'info>,
+
+    /// Program configuration
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Prover registry
+    #[account(
+        mut,
+        seeds = [PROVER_REGISTRY_SEED],
+        bump = prover_registry.bump,
+    )]
+    pub prover_registry: Box<Account<'info, ProverRegistry>>,
+}
+
+pub fn reactivate_prover(ctx: Context<ReactivateProver>, unique_id: u64) -> Result<()> {
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
+    let prover_registry = &mut ctx.accounts.prover_registry;
+    prover_registry.reactivate_prover(unique_id)?;
+
+    // Emit event
+    emit!(ProverReactivated {
+        admin: ctx.accounts.admin.key(),
+        unique_id,
+        active_provers: prover_registry.active_prover_count(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
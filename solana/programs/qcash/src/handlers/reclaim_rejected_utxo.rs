@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::events::UtxoReclaimed;
+use crate::state::{Utxo, ZkProof};
+
+/// Closes a rejected or expired UTXO (and its transfer's change UTXO) and the proof it referenced.
+/// Loaders are already closed to the payer when the UTXO is created.
+/// Votes for the rejected UTXO must be slashed first, they are lost with the account.
+#[derive(Accounts)]
+pub struct ReclaimRejectedUtxo<'info> {
+    /// Payer recorded on the UTXO, receives the rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Rejected or expired UTXO to close
+    #[account(
+        mut,
+        close = payer,
+        constraint = utxo.is_reclaimable() @ ErrorCode::UtxoNotRejected,
+        constraint = utxo.payer == payer.key() @ ErrorCode::UnauthorizedPayer,
+        constraint = !utxo.has_unslashed_wrong_votes() @ ErrorCode::UnslashedVotes,
+    )]
+    pub utxo: Box<Account<'info, Utxo>>,
+
+    /// Change UTXO, required when `utxo` was created by `transfer`
+    #[account(
+        mut,
+        close = payer,
+        constraint = utxo.change_utxo == Some(change_utxo.key()) @ ErrorCode::ChangeUtxoMismatch,
+    )]
+    pub change_utxo: Option<Box<Account<'info, Utxo>>>,
+
+    /// ZK proof referenced by the UTXO, closed if the payer also paid for it
+    /// CHECK: Matched against the UTXO, the header is read in the handler
+    #[account(
+        mut,
+        constraint = utxo.zk_proof_pubkey == Some(zk_proof.key()) @ ErrorCode::ZkProofMismatch,
+    )]
+    pub zk_proof: AccountInfo<'info>,
+}
+
+pub fn reclaim_rejected_utxo(ctx: Context<ReclaimRejectedUtxo>) -> Result<()> {
+    let utxo = &ctx.accounts.utxo;
+    let zk_proof = &ctx.accounts.zk_proof;
+    let payer = ctx.accounts.payer.to_account_info();
+
+    // A transfer's change UTXO is closed with its receiver UTXO
+    require!(
+        utxo.change_utxo.is_none() || ctx.accounts.change_utxo.is_some(),
+        ErrorCode::ChangeUtxoMismatch
+    );
+
+    let mut lamports_reclaimed = utxo.to_account_info().lamports();
+    if let Some(change_utxo) = &ctx.accounts.change_utxo {
+        lamports_reclaimed += change_utxo.to_account_info().lamports();
+    }
+
+    // The proof may already be closed, or may have been uploaded by someone else
+    let proof_payer = if zk_proof.owner == &crate::ID {
+        let data = zk_proof.try_borrow_data()?;
+        ZkProof::is_initialized(&data).then(|| ZkProof::read_payer(&data))
+    } else {
+        None
+    };
+    if proof_payer == Some(payer.key()) {
+        lamports_reclaimed += zk_proof.lamports();
+        ZkProof::close(zk_proof, &payer)?;
+    }
+
+    // Emit event
+    emit!(UtxoReclaimed {
+        utxo: utxo.key(),
+        utxo_hash: utxo.utxo_hash,
+        change_utxo: utxo.change_utxo,
+        payer: payer.key(),
+        lamports_reclaimed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,12 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use solana_sha256_hasher::hash;
 use crate::constants::*;
 use crate::error::ErrorCode;
 use crate::events::ProverRegistered;
-use crate::state::{ProgramConfig, ProverRegistry};
+use crate::state::{ProgramConfig, ProverRegistry, ProverStake};
 
 #[derive(Accounts)]
+#[instruction(unique_id: u64)]
 pub struct RegisterProver<'info> {
+    /// Admin, or one of the admin signers with the others passed as remaining accounts
     #[account(mut)]
     pub admin: Signer<'info>,
 
@@ -14,7 +17,6 @@ pub struct RegisterProver<'info> {
     #[account(
         seeds = [PROGRAM_CONFIG_SEED],
         bump = program_config.bump,
-        constraint = program_config.is_admin(&admin.key()) @ ErrorCode::UnauthorizedAdmin,
     )]
     pub program_config: Box<Account<'info, ProgramConfig>>,
 
@@ -29,13 +31,34 @@ pub struct RegisterProver<'info> {
     /// Prover's public key to register
     /// CHECK: We only hash this pubkey, don't need to validate
     pub prover_pubkey: AccountInfo<'info>,
+
+    /// Account locking the stake, the only one allowed to withdraw it
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    /// Prover's stake, kept when a removed prover is registered again under the same ID
+    #[account(
+        init_if_needed,
+        payer = staker,
+        space = ProverStake::SIZE,
+        seeds = [PROVER_STAKE_SEED, unique_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub prover_stake: Box<Account<'info, ProverStake>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn register_prover(
     ctx: Context<RegisterProver>,
     unique_id: u64,
     next_key_hash: [u8; 32],
+    stake_amount: u64,
 ) -> Result<()> {
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
     let prover_registry = &mut ctx.accounts.prover_registry;
     let prover_pubkey = ctx.accounts.prover_pubkey.key();
 
@@ -45,6 +68,37 @@ pub fn register_prover(
     // Register the prover
     prover_registry.register_prover(unique_id, next_key_hash)?;
 
+    // Lock the stake, topping up any stake left from an earlier registration
+    let staker = ctx.accounts.staker.key();
+    let prover_stake = &mut ctx.accounts.prover_stake;
+    if prover_stake.owner == Pubkey::default() {
+        prover_stake.unique_id = unique_id;
+        prover_stake.owner = staker;
+        prover_stake.bump = ctx.bumps.prover_stake;
+    }
+    require!(prover_stake.owner == staker, ErrorCode::UnauthorizedStakeOwner);
+
+    if stake_amount > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.staker.to_account_info(),
+                    to: prover_stake.to_account_info(),
+                },
+            ),
+            stake_amount,
+        )?;
+        prover_stake.amount = prover_stake
+            .amount
+            .checked_add(stake_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+    require!(
+        prover_stake.amount >= ctx.accounts.program_config.min_prover_stake,
+        ErrorCode::InsufficientStake
+    );
+
     // Emit event
     emit!(ProverRegistered {
         admin: ctx.accounts.admin.key(),
@@ -52,6 +106,8 @@ pub fn register_prover(
         prover_pubkey,
         prover_pubkey_hash: next_key_hash,
         total_provers: prover_registry.prover_count,
+        staker,
+        stake_amount: prover_stake.amount,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::ProverRemoved;
+use crate::state::{ProgramConfig, ProverRegistry};
+
+#[derive(Accounts)]
+pub struct RemoveProver<'info> {
+    /// Admin, or one of the admin signers with the others passed as remaining accounts
+    pub admin: Signer<'info>,
+
+    /// Program configuration
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Prover registry
+    #[account(
+        mut,
+        seeds = [PROVER_REGISTRY_SEED],
+        bump = prover_registry.bump,
+    )]
+    pub prover_registry: Box<Account<'info, ProverRegistry>>,
+}
+
+/// Remove a deactivated prover, its slot and unique ID can be registered again
+pub fn remove_prover(ctx: Context<RemoveProver>, unique_id: u64) -> Result<()> {
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
+    let prover_registry = &mut ctx.accounts.prover_registry;
+    let prover = prover_registry.remove_prover(unique_id)?;
+
+    // Emit event
+    emit!(ProverRemoved {
+        admin: ctx.accounts.admin.key(),
+        unique_id,
+        prover_pubkey_hash: prover.pubkey_hash,
+        total_provers: prover_registry.prover_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
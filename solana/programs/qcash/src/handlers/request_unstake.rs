@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::UnstakeRequested;
+use crate::state::{ProgramConfig, ProverStake};
+
+#[derive(Accounts)]
+#[instruction(unique_id: u64)]
+pub struct RequestUnstake<'info> {
+    /// Stake owner
+    pub owner: Signer<'info>,
+
+    /// Program configuration, holds the unbonding period
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Prover's stake
+    #[account(
+        mut,
+        seeds = [PROVER_STAKE_SEED, unique_id.to_le_bytes().as_ref()],
+        bump = prover_stake.bump,
+        has_one = owner @ ErrorCode::UnauthorizedStakeOwner,
+    )]
+    pub prover_stake: Box<Account<'info, ProverStake>>,
+}
+
+/// Move `amount` of the stake into unbonding
+/// Unbonding lamports no longer count towards the minimum stake but stay slashable
+pub fn request_unstake(ctx: Context<RequestUnstake>, unique_id: u64, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let prover_stake = &mut ctx.accounts.prover_stake;
+
+    prover_stake.start_unbonding(amount, now, ctx.accounts.program_config.unbonding_period)?;
+
+    // Emit event
+    emit!(UnstakeRequested {
+        owner: prover_stake.owner,
+        unique_id,
+        amount,
+        remaining_stake: prover_stake.amount,
+        unbonding_amount: prover_stake.unbonding_amount,
+        unbonding_ends_at: prover_stake.unbonding_ends_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
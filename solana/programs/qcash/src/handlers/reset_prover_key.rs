@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::ProverKeyReset;
+use crate::state::{ProgramConfig, ProverRegistry};
+
+#[derive(Accounts)]
+pub struct ResetProverKey<'info> {
+    /// Admin, or one of the admin signers with the others passed as remaining accounts
+    pub admin: Signer<'info>,
+
+    /// Program configuration
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Prover registry
+    #[account(
+        mut,
+        seeds = [PROVER_REGISTRY_SEED],
+        bump = prover_registry.bump,
+    )]
+    pub prover_registry: Box<Account<'info, ProverRegistry>>,
+}
+
+/// Force a prover's key hash to a new value
+/// Used when a node loses its rotation keys and can no longer sign with the registered one.
+/// `new_pubkey_hash` is the hash of the key the node will sign its next attestation with.
+pub fn reset_prover_key(
+    ctx: Context<ResetProverKey>,
+    unique_id: u64,
+    new_pubkey_hash: [u8; 32],
+) -> Result<()> {
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
+    let prover_registry = &mut ctx.accounts.prover_registry;
+    prover_registry.reset_prover_key(unique_id, new_pubkey_hash)?;
+
+    // Emit event
+    emit!(ProverKeyReset {
+        admin: ctx.accounts.admin.key(),
+        unique_id,
+        new_pubkey_hash,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
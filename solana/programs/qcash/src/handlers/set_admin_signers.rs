@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::AdminSignersUpdated;
+use crate::state::ProgramConfig;
+
+#[derive(Accounts)]
+pub struct SetAdminSigners<'info> {
+    /// Admin, or one of the admin signers with the others passed as remaining accounts
+    pub admin: Signer<'info>,
+
+    /// Program configuration
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Hand admin rights to an M-of-N signer set
+/// Use propose_admin/accept_admin to go back to a single admin
+pub fn set_admin_signers(
+    ctx: Context<SetAdminSigners>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
+    require!(
+        !signers.is_empty() && signers.len() <= MAX_ADMIN_SIGNERS,
+        ErrorCode::InvalidAdminSigners
+    );
+    require!(
+        threshold >= 1 && threshold as usize <= signers.len(),
+        ErrorCode::InvalidAdminSigners
+    );
+    for (i, signer) in signers.iter().enumerate() {
+        require!(
+            *signer != Pubkey::default() && !signers[..i].contains(signer),
+            ErrorCode::InvalidAdminSigners
+        );
+    }
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.admin_signers = signers.clone();
+    program_config.admin_threshold = threshold;
+
+    // Emit event
+    emit!(AdminSignersUpdated {
+        admin: ctx.accounts.admin.key(),
+        signers,
+        threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
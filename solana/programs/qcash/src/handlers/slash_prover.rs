@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::ProverSlashed;
+use crate::state::{ProgramConfig, ProverStake, Utxo};
+
+/// Anyone can slash a vote that went against a UTXO's final outcome.
+/// Slashed lamports go to the program config account, not the caller,
+/// so a prover gains nothing by slashing itself. The admin moves them out with sweep_slashed.
+#[derive(Accounts)]
+#[instruction(unique_id: u64)]
+pub struct SlashProver<'info> {
+    /// Caller, pays nothing but the transaction fee
+    pub authority: Signer<'info>,
+
+    /// Program configuration, holds the slash share and receives the slashed lamports
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Finalized or rejected UTXO carrying the wrong vote
+    #[account(
+        mut,
+        constraint = !utxo.is_pending() @ ErrorCode::NoSlashableVote,
+    )]
+    pub utxo: Box<Account<'info, Utxo>>,
+
+    /// Stake of the prover that cast the wrong vote
+    #[account(
+        mut,
+        seeds = [PROVER_STAKE_SEED, unique_id.to_le_bytes().as_ref()],
+        bump = prover_stake.bump,
+    )]
+    pub prover_stake: Box<Account<'info, ProverStake>>,
+}
+
+pub fn slash_prover(ctx: Context<SlashProver>, unique_id: u64) -> Result<()> {
+    let utxo = &mut ctx.accounts.utxo;
+    let prover_stake = &mut ctx.accounts.prover_stake;
+
+    // Each wrong vote is slashed once
+    utxo.mark_slashed(unique_id)?;
+
+    let amount = prover_stake.slash(ctx.accounts.program_config.slash_bps)?;
+    if amount > 0 {
+        prover_stake.sub_lamports(amount)?;
+        ctx.accounts.program_config.add_lamports(amount)?;
+    }
+
+    // Emit event
+    emit!(ProverSlashed {
+        authority: ctx.accounts.authority.key(),
+        unique_id,
+        utxo: utxo.key(),
+        utxo_hash: utxo.utxo_hash,
+        amount,
+        remaining_stake: prover_stake.amount,
+        remaining_unbonding: prover_stake.unbonding_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -3,8 +3,9 @@ use anchor_lang::system_program;
 use solana_sha256_hasher::hash;
 use crate::constants::*;
 use crate::error::ErrorCode;
-use crate::events::AttestationSubmitted;
-use crate::state::{Utxo, Ledger, ProverRegistry};
+use crate::events::{AttestationSubmitted, NullifierSpent, UtxoRejected};
+use crate::state::{Utxo, UtxoStatus, Ledger, LedgerPage, Nullifier, ProgramConfig, ProverRegistry, ProverStake};
+use crate::utils::create_pda_account;
 
 #[derive(Accounts)]
 #[instruction(utxo_hash: [u8; 32])]
@@ -36,6 +37,10 @@ pub struct SubmitAttestation<'info> {
     )]
     pub utxo: Box<Account<'info, Utxo>>,
 
+    /// Change UTXO, required when `utxo` was created by `transfer`
+    #[account(mut)]
+    pub change_utxo: Option<Box<Account<'info, Utxo>>>,
+
     /// Prover registry
     #[account(
         mut,
@@ -44,11 +49,38 @@ pub struct SubmitAttestation<'info> {
     )]
     pub prover_registry: Box<Account<'info, ProverRegistry>>,
 
+    /// Program configuration, holds the attestation threshold
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Voting prover's stake, matched to the prover by unique ID in the handler
+    /// (stake accounts are only created at their ["prover_stake", unique_id] PDA)
+    #[account(mut)]
+    pub prover_stake: Box<Account<'info, ProverStake>>,
+
+    /// CHECK: ledger index page holding `ledger.count`, checked and written in the handler
+    /// Created by this vote if it finalizes the first UTXO of the page
+    #[account(mut)]
+    pub ledger_page: UncheckedAccount<'info>,
+
+    /// CHECK: the page after `ledger_page`, checked and written in the handler
+    /// Needed when a transfer's change lands on the next page or the ledger moved on since
+    /// the prover read it
+    #[account(mut)]
+    pub next_ledger_page: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn submit_attestation(
-    ctx: Context<SubmitAttestation>,
+/// Remaining accounts: one writable nullifier PDA per entry in `utxo.nullifiers`, in order.
+/// They are created when this vote meets the attestation threshold.
+/// Then the writable stake PDAs of the earlier voters that voted the same way, in vote order.
+/// They share the UTXO's attestation fee with this prover if this vote decides the outcome.
+pub fn submit_attestation<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SubmitAttestation<'info>>,
     _utxo_hash: [u8; 32],
     vote: bool,
     next_key_hash: [u8; 32],
@@ -59,10 +91,21 @@ pub fn submit_attestation(
     let prover_registry = &mut ctx.accounts.prover_registry;
     let prover_pubkey = ctx.accounts.prover.key();
 
-    // Verify UTXO's previous hash matches ledger's last valid hash
+    // Finalized and rejected UTXOs take no more votes
+    require!(utxo.is_pending(), ErrorCode::UtxoNotPending);
+
+    // The tip the proof linked to must still be recent and its epoch live for the UTXO
+    // to finalize, a stale UTXO can only be voted down so its payer can reclaim the rent
+    let recent_tip = ledger.is_recent_tip(&utxo.prev_utxo_hash);
+    let live_epoch = ledger.is_live_epoch(utxo.epoch, Clock::get()?.unix_timestamp);
+    require!(!vote || recent_tip, ErrorCode::StaleLedgerTip);
+    require!(!vote || live_epoch, ErrorCode::StaleEpoch);
+    let stale = !recent_tip || !live_epoch;
+
+    // A transfer's change UTXO is finalized in the same vote as its receiver UTXO
     require!(
-        utxo.prev_utxo_hash == ledger.get_tip_hash(),
-        ErrorCode::UtxoHashMismatch
+        utxo.change_utxo == ctx.accounts.change_utxo.as_ref().map(|change| change.key()),
+        ErrorCode::ChangeUtxoMismatch
     );
 
     // Hash OLD prover's public key for verification
@@ -76,6 +119,14 @@ pub fn submit_attestation(
 
     let prover_unique_id = prover_info.unique_id;
 
+    // Only provers with enough stake at risk can vote
+    let prover_stake = &ctx.accounts.prover_stake;
+    require!(prover_stake.unique_id == prover_unique_id, ErrorCode::StakeMismatch);
+    require!(
+        prover_stake.amount >= ctx.accounts.program_config.min_prover_stake,
+        ErrorCode::InsufficientStake
+    );
+
     // Record vote on UTXO
     utxo.record_vote(prover_unique_id, vote)?;
 
@@ -97,15 +148,84 @@ pub fn submit_attestation(
     }
 
     // Check if minimum attestations threshold is met
-    let threshold_met = utxo.threshold_met(MIN_ATTESTATIONS_REQUIRED);
+    let program_config = &ctx.accounts.program_config;
+    let threshold_met = utxo.threshold_met(program_config.min_attestations);
+    let rejection_quorum = program_config.rejection_quorum(prover_registry.active_prover_count());
+    // A stale UTXO can never finalize, the first vote against it settles it
+    let rejected = !threshold_met && (stale || utxo.rejection_met(rejection_quorum));
+    let (nullifier_accounts, voter_stake_accounts) = ctx
+        .remaining_accounts
+        .split_at(utxo.nullifiers.len().min(ctx.remaining_accounts.len()));
     let (new_ledger_tip, new_ledger_count) = if threshold_met {
-        // Update ledger with new valid UTXO
-        ledger.update_tip(utxo.utxo_hash);
-        (Some(utxo.utxo_hash), Some(ledger.count))
+        // Refuse to finalize if any input was already spent elsewhere
+        spend_nullifiers(
+            &utxo.nullifiers,
+            utxo.utxo_hash,
+            nullifier_accounts,
+            &ctx.accounts.prover.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        // Sequence the new valid UTXO on the ledger, then its change (which links to it),
+        // and index both so wallets can find them by ledger position
+        let ledger_pages = [
+            ctx.accounts.ledger_page.as_ref(),
+            ctx.accounts.next_ledger_page.as_ref(),
+        ];
+        let prover = ctx.accounts.prover.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        utxo.status = UtxoStatus::Finalized;
+        utxo.append_to_ledger(ledger);
+        LedgerPage::append(&ledger_pages, utxo.ledger_index, &utxo.utxo_hash, &prover, &system_program)?;
+        if let Some(change_utxo) = ctx.accounts.change_utxo.as_mut() {
+            change_utxo.status = UtxoStatus::Finalized;
+            change_utxo.append_to_ledger(ledger);
+            LedgerPage::append(
+                &ledger_pages,
+                change_utxo.ledger_index,
+                &change_utxo.utxo_hash,
+                &prover,
+                &system_program,
+            )?;
+        }
+        (Some(ledger.get_tip_hash()), Some(ledger.count))
     } else {
         (None, None)
     };
 
+    if rejected {
+        // Terminal, the payer can now reclaim the rent
+        let status = utxo.rejection_status(stale, program_config.min_attestations);
+        utxo.status = status;
+        if let Some(change_utxo) = ctx.accounts.change_utxo.as_mut() {
+            change_utxo.status = status;
+        }
+
+        emit!(UtxoRejected {
+            utxo: utxo_key,
+            utxo_hash: utxo.utxo_hash,
+            change_utxo: utxo.change_utxo,
+            payer: utxo.payer,
+            valid_votes: utxo.get_valid_votes(),
+            invalid_votes: utxo.get_invalid_votes(),
+            expired: status == UtxoStatus::Expired,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    // The outcome is decided, the provers that decided it share the escrowed fee
+    let fee_paid = if threshold_met || rejected {
+        pay_attestation_fee(
+            utxo,
+            &mut ctx.accounts.prover_stake,
+            prover_unique_id,
+            voter_stake_accounts,
+        )?
+    } else {
+        0
+    };
+
     // Emit event
     emit!(AttestationSubmitted {
         utxo: utxo_key,
@@ -122,8 +242,104 @@ pub fn submit_attestation(
         threshold_met,
         new_ledger_tip,
         new_ledger_count,
+        fee_paid,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
 }
+
+/// Split the UTXO's escrowed attestation fee among the provers whose votes decided it
+/// Each gets an equal share credited to its stake rewards, the deciding voter also gets the remainder
+fn pay_attestation_fee<'info>(
+    utxo: &mut Account<'info, Utxo>,
+    voter_stake: &mut Account<'info, ProverStake>,
+    voter_unique_id: u64,
+    other_stake_accounts: &'info [AccountInfo<'info>],
+) -> Result<u64> {
+    let fee = utxo.attestation_fee;
+    if fee == 0 {
+        return Ok(0);
+    }
+
+    let deciding_voters = utxo.deciding_voters();
+    let other_voters: Vec<u64> = deciding_voters
+        .iter()
+        .copied()
+        .filter(|&id| id != voter_unique_id)
+        .collect();
+    require!(
+        other_stake_accounts.len() == other_voters.len(),
+        ErrorCode::RewardAccountsMismatch
+    );
+
+    let share = fee / deciding_voters.len() as u64;
+    for (&unique_id, account) in other_voters.iter().zip(other_stake_accounts.iter()) {
+        let mut stake = Account::<ProverStake>::try_from(account)?;
+        require!(stake.unique_id == unique_id, ErrorCode::RewardAccountsMismatch);
+
+        stake.rewards = stake.rewards.checked_add(share).ok_or(ErrorCode::ArithmeticOverflow)?;
+        stake.exit(&crate::ID)?;
+        utxo.sub_lamports(share)?;
+        stake.add_lamports(share)?;
+    }
+
+    let remainder = fee - share * other_voters.len() as u64;
+    voter_stake.rewards = voter_stake
+        .rewards
+        .checked_add(remainder)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    utxo.sub_lamports(remainder)?;
+    voter_stake.add_lamports(remainder)?;
+
+    utxo.attestation_fee = 0;
+    Ok(fee)
+}
+
+/// Create a nullifier PDA for every nullifier of the finalized UTXO
+/// The prover casting the deciding vote pays the rent
+fn spend_nullifiers<'info>(
+    nullifiers: &[[u8; 32]],
+    utxo_hash: [u8; 32],
+    nullifier_accounts: &'info [AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require!(
+        nullifier_accounts.len() == nullifiers.len(),
+        ErrorCode::NullifierAccountsMismatch
+    );
+
+    for (nullifier, account) in nullifiers.iter().zip(nullifier_accounts.iter()) {
+        let (expected_pda, bump) =
+            Pubkey::find_program_address(&[NULLIFIER_SEED, nullifier.as_ref()], &crate::ID);
+        require_keys_eq!(account.key(), expected_pda, ErrorCode::NullifierAccountsMismatch);
+
+        // An existing nullifier account means a double spend
+        require!(
+            account.owner != &crate::ID,
+            ErrorCode::NullifierAlreadySpent
+        );
+
+        let signer_seeds: &[&[u8]] = &[NULLIFIER_SEED, nullifier.as_ref(), &[bump]];
+
+        create_pda_account(account, payer, system_program, Nullifier::SIZE, signer_seeds)?;
+
+        let record = Nullifier {
+            nullifier: *nullifier,
+            spent_in: utxo_hash,
+            bump,
+        };
+        let mut data = account.try_borrow_mut_data()?;
+        record.try_serialize(&mut &mut data[..])?;
+
+        emit!(NullifierSpent {
+            nullifier: *nullifier,
+            nullifier_account: expected_pda,
+            utxo_hash,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
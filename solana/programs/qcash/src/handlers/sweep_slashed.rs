@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::SlashedSwept;
+use crate::state::ProgramConfig;
+
+#[derive(Accounts)]
+pub struct SweepSlashed<'info> {
+    /// Admin, or one of the admin signers with the others passed as remaining accounts
+    pub admin: Signer<'info>,
+
+    /// Program configuration, slash_prover moves slashed lamports here
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// CHECK: Any account chosen by the admin, e.g. a treasury, only receives lamports
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+/// Move everything the program config holds above its rent exemption to `destination`
+/// The config's own rent is all that stays, so that is exactly the slashed lamports
+pub fn sweep_slashed(ctx: Context<SweepSlashed>) -> Result<()> {
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
+    let config_info = ctx.accounts.program_config.to_account_info();
+    let rent = Rent::get()?.minimum_balance(config_info.data_len());
+    let amount = config_info.lamports().saturating_sub(rent);
+    require!(amount > 0, ErrorCode::NothingToSweep);
+
+    ctx.accounts.program_config.sub_lamports(amount)?;
+    ctx.accounts.destination.add_lamports(amount)?;
+
+    // Emit event
+    emit!(SlashedSwept {
+        admin: ctx.accounts.admin.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
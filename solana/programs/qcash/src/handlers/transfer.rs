@@ -1,83 +1,206 @@
 use anchor_lang::prelude::*;
-use solana_program::hash::hash;
-
-use crate::{KYBER_CIPHERTEXT_SIZE, Ledger, Loader, Utxo};
+use anchor_lang::system_program;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::TransferCreated;
+use crate::state::{Utxo, Ledger, Loader, ProgramConfig, ZkProof};
+
+/// One output of a transfer, as produced by prepare_output
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransferOutput {
+    pub utxo_hash: [u8; 32],
+    pub encrypted_payload: Vec<u8>,
+    pub nonce: [u8; NONCE_SIZE],
+    pub ciphertext_commitment: [u8; 32],
+    pub epoch: u32,
+    pub output_hash: [u8; 32],
+}
 
 #[derive(Accounts)]
-pub struct Transfer<'info>{
+#[instruction(
+    receiver: TransferOutput,
+    change: TransferOutput,
+    nullifiers: Vec<[u8; 32]>,
+    prev_utxo_hash: [u8; 32],
+)]
+pub struct Transfer<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    /// Ledger account, the receiver UTXO links to one of its recent tips
     #[account(
-        mut,
-        seeds = [
-            b"ledger"
-        ],
+        seeds = [LEDGER_SEED],
+        bump = ledger.bump,
+    )]
+    pub ledger: Box<Account<'info, Ledger>>,
+
+    /// Program configuration, holds the attestation fee
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Receiver UTXO, carries the votes and the nullifiers of the spent inputs
+    /// PDA: ["utxo", receiver.utxo_hash]
+    #[account(
+        init,
+        payer = signer,
+        space = Utxo::size(receiver.encrypted_payload.len(), nullifiers.len()),
+        seeds = [UTXO_SEED, receiver.utxo_hash.as_ref()],
+        bump,
+    )]
+    pub receiver_utxo: Box<Account<'info, Utxo>>,
+
+    /// Change UTXO, finalized together with the receiver UTXO
+    /// PDA: ["utxo", change.utxo_hash]
+    #[account(
+        init,
+        payer = signer,
+        space = Utxo::size(change.encrypted_payload.len(), 0),
+        seeds = [UTXO_SEED, change.utxo_hash.as_ref()],
         bump,
-        realloc = ledger.to_account_info().data_len() + Utxo::INIT_SPACE,
-        realloc::payer = signer,
-        realloc::zero = false,
     )]
-    pub ledger: Account<'info,Ledger>,
+    pub change_utxo: Box<Account<'info, Utxo>>,
+
+    /// Loader with the receiver's Kyber ciphertext, uploaded by the signer (closed after use)
+    #[account(
+        mut,
+        close = signer,
+        constraint = receiver_loader.ciphertext.len() == KYBER_CIPHERTEXT_SIZE,
+        constraint = receiver_loader.authority == signer.key() @ ErrorCode::UnauthorizedWriter
+    )]
+    pub receiver_loader: Box<Account<'info, Loader>>,
 
-    // We read from here and close it
+    /// Loader with the change Kyber ciphertext, uploaded by the signer (closed after use)
     #[account(
         mut,
         close = signer,
-        constraint = loader.ciphertext.len() == KYBER_CIPHERTEXT_SIZE
+        constraint = change_loader.ciphertext.len() == KYBER_CIPHERTEXT_SIZE,
+        constraint = change_loader.authority == signer.key() @ ErrorCode::UnauthorizedWriter,
+        constraint = change_loader.key() != receiver_loader.key() @ ErrorCode::DuplicateLoader
     )]
-    pub loader: Account<'info,Loader>,
+    pub change_loader: Box<Account<'info, Loader>>,
+
+    /// ZK Proof account covering both outputs, locked to the receiver UTXO
+    /// CHECK: Owner, header and completeness are validated in the handler
+    #[account(mut)]
+    pub zk_proof: AccountInfo<'info>,
 
-    pub system_program:Program<'info,System>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn transfer(
-    ctx:Context<Transfer>,
-    encrypted_payload:Vec<u8>,
-    nonce:[u8;12],
-    // Proof Data
-)->Result<()>{
-
-    let ledger = &mut ctx.accounts.ledger;
-    let loader = &ctx.accounts.loader;
-
-    let prev_utxo_hash = ledger.get_tip_hash();
-
-    // calculating ciphertext commitment
-    // This fingerprint proves that data hasn't changed without needing to read the data
-    let mut commitment_data = Vec::new();
-    commitment_data.extend_from_slice(&loader.ciphertext);
-    commitment_data.extend_from_slice(&nonce);
-    commitment_data.extend_from_slice(&encrypted_payload);
-
-    let ciphertext_commitment = hash(&commitment_data).to_bytes();
-    // TODO: Hardcoded Epcoh
-    let epoch:u32 = 0;
-
-    // calculating Utxo hash
-    // Hash(prev_utxo_hash || ciphertext_commitment ||  epoch)
-    let mut header_data = Vec::new();
-    header_data.extend_from_slice(&prev_utxo_hash);
-    header_data.extend_from_slice(&ciphertext_commitment);
-    header_data.extend_from_slice(&epoch.to_le_bytes());
-
-    let utxo_hash = hash(&header_data).to_bytes();
-
-    let new_utxo = Utxo{
-        kyber_ciphertext: loader.ciphertext,
-        encrypted_payload,
-        ciphertext_commitment,
-        epoch,
-        nonce,
-        prev_utxo_hash,
-        utxo_hash,
-    };
-
-    // Append to ledger
-    ledger.utxos.push(new_utxo);
-    ledger.count+=1;
-
-    msg!("Transfer Finalized. UTXO Hash: {:?}", utxo_hash);
+    ctx: Context<Transfer>,
+    receiver: TransferOutput,
+    change: TransferOutput,
+    nullifiers: Vec<[u8; 32]>,
+    prev_utxo_hash: [u8; 32],
+) -> Result<()> {
+    let ledger = &ctx.accounts.ledger;
+    let receiver_loader = &ctx.accounts.receiver_loader;
+    let change_loader = &ctx.accounts.change_loader;
+    let zk_proof = ctx.accounts.zk_proof.key();
+    let receiver_key = ctx.accounts.receiver_utxo.key();
+    let change_key = ctx.accounts.change_utxo.key();
+
+    // Verify payload sizes
+    require!(
+        receiver.encrypted_payload.len() <= MAX_PAYLOAD_SIZE
+            && change.encrypted_payload.len() <= MAX_PAYLOAD_SIZE,
+        ErrorCode::PayloadTooLarge
+    );
+
+    // Verify nullifier count, they are spent when the transfer is finalized
+    require!(
+        nullifiers.len() <= MAX_NULLIFIERS_PER_UTXO,
+        ErrorCode::TooManyNullifiers
+    );
+
+    // Pin each output_hash to the stored bytes, the node checks it against the guest journal
+    require!(
+        Utxo::compute_output_hash(&receiver_loader.ciphertext, &receiver.nonce, &receiver.encrypted_payload)
+            == receiver.output_hash,
+        ErrorCode::OutputHashMismatch
+    );
+    require!(
+        Utxo::compute_output_hash(&change_loader.ciphertext, &change.nonce, &change.encrypted_payload)
+            == change.output_hash,
+        ErrorCode::OutputHashMismatch
+    );
+
+    // The proof must be a complete qcash proof, uploaded by the signer, that no other UTXO has used
+    ZkProof::validate_and_lock(&ctx.accounts.zk_proof, &ctx.accounts.signer.key(), &receiver_key)?;
+
+    // The receiver links to the ledger tip the proof was built against and the change
+    // links to the receiver, the same chain the guest committed to
+    require!(ledger.is_recent_tip(&prev_utxo_hash), ErrorCode::StaleLedgerTip);
+
+    // The guest commits the same epoch to both outputs, expired proofs have to be redone
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        receiver.epoch == change.epoch && ledger.is_live_epoch(receiver.epoch, now),
+        ErrorCode::StaleEpoch
+    );
+    let receiver_prev_hash = prev_utxo_hash;
+
+    let receiver_utxo = &mut ctx.accounts.receiver_utxo;
+    receiver_utxo.initialize(
+        receiver.epoch,
+        receiver.utxo_hash,
+        receiver_prev_hash,
+        receiver.ciphertext_commitment,
+        receiver.nonce,
+        receiver.encrypted_payload,
+        receiver_loader.ciphertext,
+        zk_proof,
+        nullifiers,
+        ctx.accounts.signer.key(),
+        ctx.bumps.receiver_utxo,
+    );
+    receiver_utxo.change_utxo = Some(change_key);
+
+    // Escrow the provers' fee in the receiver UTXO, the one they vote on
+    let attestation_fee = ctx.accounts.program_config.attestation_fee;
+    if attestation_fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: receiver_utxo.to_account_info(),
+                },
+            ),
+            attestation_fee,
+        )?;
+        receiver_utxo.attestation_fee = attestation_fee;
+    }
+
+    let change_utxo = &mut ctx.accounts.change_utxo;
+    change_utxo.initialize_change(
+        change.epoch,
+        change.utxo_hash,
+        receiver.utxo_hash,
+        change.ciphertext_commitment,
+        change.nonce,
+        change.encrypted_payload,
+        change_loader.ciphertext,
+        zk_proof,
+        ctx.accounts.signer.key(),
+        ctx.bumps.change_utxo,
+    );
+
+    // Emit event
+    emit!(TransferCreated {
+        receiver_utxo: receiver_key,
+        receiver_utxo_hash: receiver.utxo_hash,
+        change_utxo: change_key,
+        change_utxo_hash: change.utxo_hash,
+        prev_utxo_hash: receiver_prev_hash,
+        zk_proof,
+        nullifier_count: ctx.accounts.receiver_utxo.nullifiers.len() as u8,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
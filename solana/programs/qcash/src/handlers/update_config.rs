@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::ConfigUpdated;
+use crate::state::{ProgramConfig, ProverRegistry};
+
+/// Config fields to change, `None` leaves a field as it is
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ConfigUpdate {
+    /// Valid votes needed to finalize a UTXO
+    pub min_attestations: Option<u16>,
+    /// Invalid votes that reject a UTXO
+    pub min_rejections: Option<u16>,
+    /// Only signer allowed to airdrop
+    pub faucet_authority: Option<Pubkey>,
+    /// Airdrops a single vault can receive per Solana epoch, 0 pauses the faucet
+    pub max_airdrops_per_vault: Option<u32>,
+    /// Airdrops the faucet can make per Solana epoch, 0 pauses the faucet
+    pub max_airdrops_per_epoch: Option<u32>,
+    /// Lamports a prover must have staked to vote
+    pub min_prover_stake: Option<u64>,
+    /// Share of a prover's stake slashed per wrong vote, in basis points
+    pub slash_bps: Option<u16>,
+    /// Seconds unstaked lamports wait before they can be withdrawn
+    pub unbonding_period: Option<i64>,
+    /// Lamports escrowed per UTXO for the provers that decide it
+    pub attestation_fee: Option<u64>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// Admin, or one of the admin signers with the others passed as remaining accounts
+    pub admin: Signer<'info>,
+
+    /// Program configuration
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Prover registry, bounds the attestation threshold
+    #[account(
+        seeds = [PROVER_REGISTRY_SEED],
+        bump = prover_registry.bump,
+    )]
+    pub prover_registry: Box<Account<'info, ProverRegistry>>,
+}
+
+pub fn update_config(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+    ctx.accounts
+        .program_config
+        .require_admin(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    let prover_registry = &ctx.accounts.prover_registry;
+
+    // Both quorums must be reachable by the provers we have and fit in a UTXO's vote slots
+    let max_votes = prover_registry
+        .active_prover_count()
+        .min(MAX_VOTES_ALLOWED as u16);
+
+    if let Some(min_attestations) = update.min_attestations {
+        require!(
+            min_attestations >= 1 && min_attestations <= max_votes,
+            ErrorCode::InvalidMinAttestations
+        );
+
+        program_config.min_attestations = min_attestations;
+    }
+
+    // Rejecting must take enough invalid votes that min_attestations is out of reach
+    if let Some(min_rejections) = update.min_rejections {
+        let min_safe = ProgramConfig::min_safe_rejections(
+            prover_registry.active_prover_count(),
+            program_config.min_attestations,
+        );
+        require!(
+            min_rejections >= min_safe && min_rejections <= max_votes,
+            ErrorCode::InvalidMinRejections
+        );
+
+        program_config.min_rejections = min_rejections;
+    }
+
+    if let Some(faucet_authority) = update.faucet_authority {
+        program_config.faucet_authority = faucet_authority;
+    }
+
+    if let Some(max_airdrops_per_vault) = update.max_airdrops_per_vault {
+        program_config.max_airdrops_per_vault = max_airdrops_per_vault;
+    }
+
+    if let Some(max_airdrops_per_epoch) = update.max_airdrops_per_epoch {
+        program_config.max_airdrops_per_epoch = max_airdrops_per_epoch;
+    }
+
+    if let Some(min_prover_stake) = update.min_prover_stake {
+        program_config.min_prover_stake = min_prover_stake;
+    }
+
+    if let Some(slash_bps) = update.slash_bps {
+        require!(slash_bps <= BPS_DENOMINATOR, ErrorCode::InvalidSlashBps);
+        program_config.slash_bps = slash_bps;
+    }
+
+    if let Some(unbonding_period) = update.unbonding_period {
+        require!(unbonding_period >= 0, ErrorCode::InvalidUnbondingPeriod);
+        program_config.unbonding_period = unbonding_period;
+    }
+
+    if let Some(attestation_fee) = update.attestation_fee {
+        program_config.attestation_fee = attestation_fee;
+    }
+
+    // Emit event
+    emit!(ConfigUpdated {
+        admin: ctx.accounts.admin.key(),
+        min_attestations: program_config.min_attestations,
+        min_rejections: program_config.min_rejections,
+        faucet_authority: program_config.faucet_authority,
+        max_airdrops_per_vault: program_config.max_airdrops_per_vault,
+        max_airdrops_per_epoch: program_config.max_airdrops_per_epoch,
+        min_prover_stake: program_config.min_prover_stake,
+        slash_bps: program_config.slash_bps,
+        unbonding_period: program_config.unbonding_period,
+        attestation_fee: program_config.attestation_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -18,6 +18,8 @@ pub struct InitLoader<'info>{
 }
 
 pub fn init_loader(ctx:Context<InitLoader>)->Result<()>{
+    ctx.accounts.loader.authority = ctx.accounts.signer.key();
+
     emit!(LoaderInitialized {
         loader: ctx.accounts.loader.key(),
         size: Loader::LEN as u32,
@@ -29,7 +31,12 @@ pub fn init_loader(ctx:Context<InitLoader>)->Result<()>{
 
 #[derive(Accounts)]
 pub struct WriteLoader<'info>{
-    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::UnauthorizedWriter,
+    )]
     pub loader: Account<'info,Loader>,
 }
 
@@ -38,9 +45,11 @@ pub fn write_loader(ctx:Context<WriteLoader>,offset:u32,data:Vec<u8>)->Result<()
 
     // bound check
     let start = offset as usize;
-    let end = start + data.len();
+    let end = start
+        .checked_add(data.len())
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
     
-    require!(end <= Loader::LEN, ErrorCode::PayloadTooLarge);
+    require!(end <= loader.ciphertext.len(), ErrorCode::PayloadTooLarge);
 
     loader.ciphertext[start..end].copy_from_slice(&data);
 
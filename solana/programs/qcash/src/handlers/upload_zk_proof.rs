@@ -8,17 +8,21 @@ pub struct InitZkProof<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
     
-    /// CHECK: Account is pre-created by client via System Program to bypass 10KB CPI limit.
-    /// We just initialize the data structure here.
+    /// Account is pre-created by client via System Program to bypass 10KB CPI limit.
+    /// We just initialize the data structure here. It must sign, or anyone watching the
+    /// create transaction could initialize it first and become its payer.
     #[account(
         mut,
         owner = crate::ID,
     )]
-    pub zk_proof: AccountInfo<'info>,
+    pub zk_proof: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct WriteZkProof<'info> {
+    /// Account that initialized the proof
+    pub authority: Signer<'info>,
+
     /// CHECK: Account data is validated in the instruction handler
     #[account(mut)]
     pub zk_proof: AccountInfo<'info>,
@@ -27,8 +31,20 @@ pub struct WriteZkProof<'info> {
 pub fn init_zk_proof(ctx: Context<InitZkProof>, total_bytes: u32) -> Result<()> {
     let zk_proof = &ctx.accounts.zk_proof;
     let mut data = zk_proof.try_borrow_mut_data()?;
-    
-    ZkProof::initialize(&mut data, total_bytes);
+
+    // The account must fit the header and the whole proof
+    require!(
+        data.len() >= ZkProof::space(total_bytes)?,
+        ErrorCode::PayloadTooLarge
+    );
+
+    // The header records who paid for the account, so it can't be rewritten
+    require!(
+        !ZkProof::is_initialized(&data),
+        ErrorCode::ZkProofAlreadyInitialized
+    );
+
+    ZkProof::initialize(&mut data, total_bytes, &ctx.accounts.signer.key());
     
     emit!(ZkProofInitialized {
         zk_proof: zk_proof.key(),
@@ -42,6 +58,14 @@ pub fn init_zk_proof(ctx: Context<InitZkProof>, total_bytes: u32) -> Result<()>
 pub fn write_zk_proof(ctx: Context<WriteZkProof>, offset: u32, chunk: Vec<u8>) -> Result<()> {
     let zk_proof = &ctx.accounts.zk_proof;
     let mut data = zk_proof.try_borrow_mut_data()?;
+
+    // Only the account that initialized the proof may write to it
+    require!(ZkProof::is_initialized(&data), ErrorCode::ZkProofNotInitialized);
+    require_keys_eq!(
+        ZkProof::read_payer(&data),
+        ctx.accounts.authority.key(),
+        ErrorCode::UnauthorizedWriter
+    );
     
     // Read current state
     let total_len = ZkProof::read_total_len(&data);
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::StakeWithdrawn;
+use crate::state::ProverStake;
+
+#[derive(Accounts)]
+#[instruction(unique_id: u64)]
+pub struct WithdrawStake<'info> {
+    /// Stake owner, receives the unbonded lamports
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Prover's stake
+    #[account(
+        mut,
+        seeds = [PROVER_STAKE_SEED, unique_id.to_le_bytes().as_ref()],
+        bump = prover_stake.bump,
+        has_one = owner @ ErrorCode::UnauthorizedStakeOwner,
+    )]
+    pub prover_stake: Box<Account<'info, ProverStake>>,
+}
+
+/// Withdraw all unbonding lamports once the unbonding period is over
+pub fn withdraw_stake(ctx: Context<WithdrawStake>, unique_id: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let prover_stake = &mut ctx.accounts.prover_stake;
+
+    let amount = prover_stake.unbonding_amount;
+    require!(amount > 0, ErrorCode::InvalidStakeAmount);
+    require!(now >= prover_stake.unbonding_ends_at, ErrorCode::UnbondingNotEnded);
+
+    prover_stake.unbonding_amount = 0;
+
+    // The stake account is program-owned, move the lamports directly
+    prover_stake.sub_lamports(amount)?;
+    ctx.accounts.owner.add_lamports(amount)?;
+
+    // Emit event
+    emit!(StakeWithdrawn {
+        owner: ctx.accounts.owner.key(),
+        unique_id,
+        amount,
+        remaining_stake: prover_stake.amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
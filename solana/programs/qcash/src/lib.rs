@@ -10,6 +10,7 @@ pub mod error;
 pub mod handlers;
 pub mod state;
 pub mod events;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 
@@ -82,13 +83,32 @@ pub mod solana_programs {
         nonce: [u8; 12],
         ciphertext_commitment: [u8; 32],
         epoch: u32,
+        nullifiers: Vec<[u8; 32]>,
+        output_hash: [u8; 32],
+        prev_utxo_hash: [u8; 32],
     ) -> Result<()> {
-        create_utxo::create_utxo(ctx, utxo_hash, encrypted_payload, nonce, ciphertext_commitment, epoch)
+        create_utxo::create_utxo(ctx, utxo_hash, encrypted_payload, nonce, ciphertext_commitment, epoch, nullifiers, output_hash, prev_utxo_hash)
     }
 
-    /// Register a new prover (admin only)
-    pub fn register_prover(ctx: Context<RegisterProver>, unique_id: u64, next_key_hash: [u8; 32]) -> Result<()> {
-        register_prover::register_prover(ctx, unique_id, next_key_hash)
+    /// Create both UTXOs of a transfer, attested and finalized together
+    pub fn transfer(
+        ctx: Context<Transfer>,
+        receiver: TransferOutput,
+        change: TransferOutput,
+        nullifiers: Vec<[u8; 32]>,
+        prev_utxo_hash: [u8; 32],
+    ) -> Result<()> {
+        transfer::transfer(ctx, receiver, change, nullifiers, prev_utxo_hash)
+    }
+
+    /// Register a new prover (admin only), locking its stake
+    pub fn register_prover(
+        ctx: Context<RegisterProver>,
+        unique_id: u64,
+        next_key_hash: [u8; 32],
+        stake_amount: u64,
+    ) -> Result<()> {
+        register_prover::register_prover(ctx, unique_id, next_key_hash, stake_amount)
     }
 
     /// Deactivate a prover (admin only)
@@ -96,9 +116,95 @@ pub mod solana_programs {
         deactivate_prover::deactivate_prover(ctx)
     }
 
+    /// Reactivate a deactivated prover (admin only)
+    pub fn reactivate_prover(ctx: Context<ReactivateProver>, unique_id: u64) -> Result<()> {
+        reactivate_prover::reactivate_prover(ctx, unique_id)
+    }
+
+    /// Remove a deactivated prover and free its slot (admin only)
+    pub fn remove_prover(ctx: Context<RemoveProver>, unique_id: u64) -> Result<()> {
+        remove_prover::remove_prover(ctx, unique_id)
+    }
+
+    /// Force-reset a prover's key hash after it lost its rotation keys (admin only)
+    pub fn reset_prover_key(ctx: Context<ResetProverKey>, unique_id: u64, new_pubkey_hash: [u8; 32]) -> Result<()> {
+        reset_prover_key::reset_prover_key(ctx, unique_id, new_pubkey_hash)
+    }
+
+    /// Update the program configuration (admin only)
+    pub fn update_config(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+        update_config::update_config(ctx, update)
+    }
+
+    /// Grow a program config created before its appended fields and set them to defaults (admin only)
+    pub fn migrate_program_config(ctx: Context<MigrateProgramConfig>) -> Result<()> {
+        migrate_program_config::migrate_program_config(ctx)
+    }
+
+    /// Grow a ledger created before the running tip, its UTXOs are replayed by migrate_utxo (admin only)
+    pub fn migrate_ledger(ctx: Context<MigrateLedger>, genesis_timestamp: i64) -> Result<()> {
+        migrate_ledger::migrate_ledger(ctx, genesis_timestamp)
+    }
+
+    /// Grow a UTXO created before the fields appended after its bump (admin only)
+    pub fn migrate_utxo(ctx: Context<MigrateUtxo>, utxo_hash: [u8; 32]) -> Result<()> {
+        migrate_utxo::migrate_utxo(ctx, utxo_hash)
+    }
+
+    /// Propose a new admin, None cancels a pending proposal
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+        propose_admin::propose_admin(ctx, new_admin)
+    }
+
+    /// Accept a pending admin proposal
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        accept_admin::accept_admin(ctx)
+    }
+
+    /// Hand admin rights to an M-of-N signer set
+    pub fn set_admin_signers(ctx: Context<SetAdminSigners>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        set_admin_signers::set_admin_signers(ctx, signers, threshold)
+    }
+
+    /// Move part of a prover's stake into unbonding (stake owner only)
+    pub fn request_unstake(ctx: Context<RequestUnstake>, unique_id: u64, amount: u64) -> Result<()> {
+        request_unstake::request_unstake(ctx, unique_id, amount)
+    }
+
+    /// Withdraw unbonded stake once the unbonding period is over (stake owner only)
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, unique_id: u64) -> Result<()> {
+        withdraw_stake::withdraw_stake(ctx, unique_id)
+    }
+
+    /// Slash a prover that voted against a UTXO's final outcome (anyone can call)
+    pub fn slash_prover(ctx: Context<SlashProver>, unique_id: u64) -> Result<()> {
+        slash_prover::slash_prover(ctx, unique_id)
+    }
+
+    /// Move the slashed lamports held by the program config to `destination` (admin only)
+    pub fn sweep_slashed(ctx: Context<SweepSlashed>) -> Result<()> {
+        sweep_slashed::sweep_slashed(ctx)
+    }
+
+    /// Claim the attestation fees a prover has earned (stake owner only)
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, unique_id: u64) -> Result<()> {
+        claim_rewards::claim_rewards(ctx, unique_id)
+    }
+
+    /// Close a rejected or expired UTXO and its proof, returning the rent to the payer
+    pub fn reclaim_rejected_utxo(ctx: Context<ReclaimRejectedUtxo>) -> Result<()> {
+        reclaim_rejected_utxo::reclaim_rejected_utxo(ctx)
+    }
+
+    /// Close a ZK proof once its UTXO is finalized or rejected, returning the rent to the payer
+    pub fn close_zk_proof(ctx: Context<CloseZkProof>) -> Result<()> {
+        close_zk_proof::close_zk_proof(ctx)
+    }
+
     /// Submit attestation vote on a UTXO
-    pub fn submit_attestation(
-        ctx: Context<SubmitAttestation>,
+    /// Pass the UTXO's nullifier PDAs as remaining accounts
+    pub fn submit_attestation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitAttestation<'info>>,
         utxo_hash: [u8; 32],
         vote: bool,
         next_key_hash: [u8; 32],
@@ -107,6 +213,7 @@ pub mod solana_programs {
     }
 
     /// Airdrop tokens to a vault without proof verification
+    #[cfg(not(feature = "mainnet"))]
     pub fn airdrop(
         ctx: Context<Airdrop>,
         utxo_hash: [u8; 32],
@@ -114,7 +221,8 @@ pub mod solana_programs {
         nonce: [u8; 12],
         ciphertext_commitment: [u8; 32],
         epoch: u32,
+        prev_utxo_hash: [u8; 32],
     ) -> Result<()> {
-        airdrop::airdrop(ctx, utxo_hash, encrypted_payload, nonce, ciphertext_commitment, epoch)
+        airdrop::airdrop(ctx, utxo_hash, encrypted_payload, nonce, ciphertext_commitment, epoch, prev_utxo_hash)
     }
 }
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// Airdrops received by one vault in the current Solana epoch
+/// PDA: ["airdrop_vault", vault]
+#[account]
+#[derive(Default)]
+pub struct AirdropVaultLimit {
+    /// Vault receiving the airdrops
+    pub vault: Pubkey,
+
+    /// Solana epoch the count belongs to
+    pub epoch: u64,
+
+    /// Airdrops received in `epoch`
+    pub count: u32,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AirdropVaultLimit {
+    /// Size of the AirdropVaultLimit account in bytes
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // vault
+        8 + // epoch
+        4 + // count
+        1; // bump
+
+    /// Count one airdrop in `epoch`, resetting the counter when the epoch rolls over
+    pub fn record(&mut self, epoch: u64, max_per_epoch: u32) -> Result<()> {
+        self.count = record_in_epoch(&mut self.epoch, self.count, epoch, max_per_epoch)
+            .ok_or(ErrorCode::AirdropVaultLimitReached)?;
+        Ok(())
+    }
+}
+
+/// Airdrops made program-wide in the current Solana epoch
+/// PDA: ["airdrop_epoch"]
+#[account]
+#[derive(Default)]
+pub struct AirdropEpochLimit {
+    /// Solana epoch the count belongs to
+    pub epoch: u64,
+
+    /// Airdrops made in `epoch`
+    pub count: u32,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AirdropEpochLimit {
+    /// Size of the AirdropEpochLimit account in bytes
+    pub const SIZE: usize = 8 + // discriminator
+        8 + // epoch
+        4 + // count
+        1; // bump
+
+    /// Count one airdrop in `epoch`, resetting the counter when the epoch rolls over
+    pub fn record(&mut self, epoch: u64, max_per_epoch: u32) -> Result<()> {
+        self.count = record_in_epoch(&mut self.epoch, self.count, epoch, max_per_epoch)
+            .ok_or(ErrorCode::AirdropEpochLimitReached)?;
+        Ok(())
+    }
+}
+
+/// New count after one more airdrop, None once the limit is reached
+fn record_in_epoch(stored_epoch: &mut u64, count: u32, epoch: u64, max_per_epoch: u32) -> Option<u32> {
+    let count = if *stored_epoch == epoch { count } else { 0 };
+    *stored_epoch = epoch;
+
+    if count >= max_per_epoch {
+        return None;
+    }
+    Some(count + 1)
+}
@@ -1,17 +1,51 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+use crate::constants::*;
 
-/// Ledger account that tracks the last valid UTXO hash
+/// Ledger account that sequences finalized UTXOs
+/// UTXOs are ordered when they are finalized, not when they are proven: a proof may link to
+/// any of the last LEDGER_TIP_WINDOW tips, so transfers proven in parallel can all finalize.
 #[account]
-#[derive(Default)]
 pub struct Ledger {
     /// Total number of valid UTXOs
     pub count: u64,
     
-    /// Hash of the last valid UTXO (genesis is all zeros)
+    /// Hash of the last valid UTXO (genesis is all zeros), where wallets start walking back
     pub last_valid_utxo_hash: [u8; 32],
-    
+
     /// Bump seed for PDA
     pub bump: u8,
+
+    /// Running hash over every finalized UTXO hash in ledger order (genesis is all zeros)
+    /// tip = SHA256(previous tip || utxo_hash), proofs show their inputs lead up to one of these
+    pub tip_hash: [u8; 32],
+
+    /// The last LEDGER_TIP_WINDOW tips, a new UTXO may link to any of them
+    pub recent_tips: [[u8; 32]; LEDGER_TIP_WINDOW],
+
+    /// Unix timestamp the ledger was initialized at, epoch 0 starts then
+    pub genesis_timestamp: i64,
+
+    /// Finalized UTXOs of a migrated ledger not yet replayed onto the running tip
+    pub legacy_remaining: u64,
+
+    /// Hash of the last legacy UTXO replayed, the next one must link to it (genesis is all zeros)
+    pub legacy_cursor: [u8; 32],
+}
+
+impl Default for Ledger {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            last_valid_utxo_hash: [0u8; 32],
+            bump: 0,
+            tip_hash: [0u8; 32],
+            recent_tips: [[0u8; 32]; LEDGER_TIP_WINDOW],
+            genesis_timestamp: 0,
+            legacy_remaining: 0,
+            legacy_cursor: [0u8; 32],
+        }
+    }
 }
 
 impl Ledger {
@@ -20,23 +54,91 @@ impl Ledger {
         8 + // count
         32 + // last_valid_utxo_hash
         1 + // bump
-        64; // padding for future fields
+        32 + // tip_hash
+        32 * LEDGER_TIP_WINDOW + // recent_tips
+        8 + // genesis_timestamp
+        8 + // legacy_remaining
+        32 + // legacy_cursor
+        16; // padding for future fields
+
+    /// Size of ledgers created before tip_hash, recent_tips and genesis_timestamp were appended
+    pub const LEGACY_SIZE: usize = 8 + // discriminator
+        8 + // count
+        32 + // last_valid_utxo_hash
+        1 + // bump
+        64; // padding
 
     /// Initialize the ledger with genesis state
-    pub fn initialize(&mut self, bump: u8) {
+    pub fn initialize(&mut self, bump: u8, now: i64) {
         self.count = 0;
         self.last_valid_utxo_hash = [0u8; 32]; // genesis hash
+        self.tip_hash = [0u8; 32];
+        self.recent_tips = [[0u8; 32]; LEDGER_TIP_WINDOW];
+        self.genesis_timestamp = now;
+        self.legacy_remaining = 0;
+        self.legacy_cursor = [0u8; 32];
         self.bump = bump;
     }
 
+    /// Rebuild a ledger from the fields of a LEGACY_SIZE account
+    /// Its UTXOs were chained by prev_utxo_hash only and are not on the running tip, so the
+    /// ledger restarts from genesis and migrate_utxo replays them in their original order
+    pub fn from_legacy(legacy_count: u64, bump: u8, genesis_timestamp: i64) -> Self {
+        Self {
+            bump,
+            genesis_timestamp,
+            legacy_remaining: legacy_count,
+            ..Self::default()
+        }
+    }
+
+    /// Checks if `prev_utxo_hash` links a finalized legacy UTXO to the last one replayed
+    pub fn is_next_legacy(&self, prev_utxo_hash: &[u8; 32]) -> bool {
+        self.legacy_remaining > 0 && self.legacy_cursor == *prev_utxo_hash
+    }
+
     /// Get the tip hash for chain validation
     pub fn get_tip_hash(&self) -> [u8; 32] {
-        self.last_valid_utxo_hash
+        self.tip_hash
+    }
+
+    /// Checks if a new UTXO may link to `tip`: the current tip or one of the recent ones
+    /// Genesis only counts while fewer than LEDGER_TIP_WINDOW UTXOs are finalized
+    pub fn is_recent_tip(&self, tip: &[u8; 32]) -> bool {
+        if *tip == [0u8; 32] {
+            return self.count < LEDGER_TIP_WINDOW as u64;
+        }
+        self.recent_tips.contains(tip)
+    }
+
+    /// Epoch at unix time `now`, epochs last EPOCH_DURATION seconds from genesis
+    pub fn epoch_at(&self, now: i64) -> u32 {
+        (now.saturating_sub(self.genesis_timestamp).max(0) / EPOCH_DURATION) as u32
+    }
+
+    /// Checks if a UTXO declaring `epoch` can still be created or finalized at `now`
+    /// It must be the current epoch or one of the EPOCH_GRACE before it, never a future one
+    pub fn is_live_epoch(&self, epoch: u32, now: i64) -> bool {
+        let current = self.epoch_at(now);
+        epoch <= current && current - epoch <= EPOCH_GRACE
     }
 
-    /// Update the last valid UTXO hash and increment count
-    pub fn update_tip(&mut self, new_utxo_hash: [u8; 32]) {
+    /// Tip after appending `utxo_hash` to `tip`
+    pub fn next_tip(tip: &[u8; 32], utxo_hash: &[u8; 32]) -> [u8; 32] {
+        hashv(&[tip, utxo_hash]).to_bytes()
+    }
+
+    /// Append a finalized UTXO, returning the previous last UTXO hash and tip
+    /// The UTXO stores both so wallets can walk the ledger back and prove membership
+    pub fn update_tip(&mut self, new_utxo_hash: [u8; 32]) -> ([u8; 32], [u8; 32]) {
+        let prev_utxo_hash = self.last_valid_utxo_hash;
+        let prev_tip = self.tip_hash;
+
+        self.tip_hash = Self::next_tip(&prev_tip, &new_utxo_hash);
+        self.recent_tips[(self.count % LEDGER_TIP_WINDOW as u64) as usize] = self.tip_hash;
         self.last_valid_utxo_hash = new_utxo_hash;
         self.count += 1;
+
+        (prev_utxo_hash, prev_tip)
     }
 }
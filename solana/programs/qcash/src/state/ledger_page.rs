@@ -0,0 +1,99 @@
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::utils::create_pda_account;
+use anchor_lang::prelude::*;
+
+/// One page of the append-only ledger index
+/// Holds the hashes of LEDGER_PAGE_SIZE consecutive finalized UTXOs: the UTXO at ledger
+/// index n lives in page n / LEDGER_PAGE_SIZE, slot n % LEDGER_PAGE_SIZE.
+/// PDA: ["ledger_page", page number as u64 LE], so wallets can derive and batch fetch pages
+/// from any point of the ledger instead of walking it back one UTXO at a time.
+pub struct LedgerPage {
+    pub page: u64,
+    /// Number of hashes written so far, LEDGER_PAGE_SIZE once the page is full
+    pub len: u32,
+    // NOTE: LEDGER_PAGE_SIZE hashes of 32 bytes follow this header in the account data
+}
+
+impl LedgerPage {
+    pub const HEADER_SIZE: usize = 8 + // discriminator
+        8 + // page
+        4; // len
+
+    pub const SIZE: usize = Self::HEADER_SIZE + 32 * LEDGER_PAGE_SIZE;
+
+    // Field offsets (absolute, including discriminator)
+    const PAGE_OFFSET: usize = 8;
+    const LEN_OFFSET: usize = 16;
+
+    pub fn discriminator() -> [u8; 8] {
+        // Custom discriminator for LedgerPage
+        [66, 54, 29, 101, 10, 93, 241, 133]
+    }
+
+    /// Page number and slot of the UTXO at `ledger_index`
+    pub fn locate(ledger_index: u64) -> (u64, usize) {
+        (
+            ledger_index / LEDGER_PAGE_SIZE as u64,
+            (ledger_index % LEDGER_PAGE_SIZE as u64) as usize,
+        )
+    }
+
+    /// Check if the header has already been written
+    pub fn is_initialized(data: &[u8]) -> bool {
+        data.len() >= Self::SIZE && data[0..8] == Self::discriminator()
+    }
+
+    /// Read the page number from account data
+    pub fn read_page(data: &[u8]) -> u64 {
+        u64::from_le_bytes(data[Self::PAGE_OFFSET..Self::PAGE_OFFSET + 8].try_into().unwrap())
+    }
+
+    /// Read the number of hashes written from account data
+    pub fn read_len(data: &[u8]) -> u32 {
+        u32::from_le_bytes(data[Self::LEN_OFFSET..Self::LEN_OFFSET + 4].try_into().unwrap())
+    }
+
+    /// Write the UTXO hash at `ledger_index` into its page
+    /// `pages` must contain that page's account, which is created (paid by `payer`)
+    /// when the hash is the first of its page. Passing the current and next page lets a
+    /// caller cover appends that cross a page boundary or race other finalizations.
+    pub fn append<'info>(
+        pages: &[&AccountInfo<'info>],
+        ledger_index: u64,
+        utxo_hash: &[u8; 32],
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let (page, slot) = Self::locate(ledger_index);
+        let page_bytes = page.to_le_bytes();
+        let (expected_pda, bump) =
+            Pubkey::find_program_address(&[LEDGER_PAGE_SEED, page_bytes.as_ref()], &crate::ID);
+        let account = pages
+            .iter()
+            .find(|account| account.key() == expected_pda)
+            .ok_or(ErrorCode::LedgerPageMismatch)?;
+
+        if slot == 0 && account.owner != &crate::ID {
+            let signer_seeds: &[&[u8]] = &[LEDGER_PAGE_SEED, page_bytes.as_ref(), &[bump]];
+            create_pda_account(account, payer, system_program, Self::SIZE, signer_seeds)?;
+
+            let mut data = account.try_borrow_mut_data()?;
+            data[0..8].copy_from_slice(&Self::discriminator());
+            data[Self::PAGE_OFFSET..Self::PAGE_OFFSET + 8].copy_from_slice(&page_bytes);
+        }
+
+        require_keys_eq!(*account.owner, crate::ID, ErrorCode::LedgerPageMismatch);
+        let mut data = account.try_borrow_mut_data()?;
+        require!(Self::is_initialized(&data), ErrorCode::LedgerPageMismatch);
+
+        // The ledger appends in order, so the slot is always the next free one
+        require!(Self::read_len(&data) as usize == slot, ErrorCode::LedgerPageMismatch);
+
+        let offset = Self::HEADER_SIZE + 32 * slot;
+        data[offset..offset + 32].copy_from_slice(utxo_hash);
+        data[Self::LEN_OFFSET..Self::LEN_OFFSET + 4]
+            .copy_from_slice(&(slot as u32 + 1).to_le_bytes());
+        Ok(())
+    }
+}
@@ -4,9 +4,11 @@ pub const KYBER_CIPHERTEXT_SIZE: usize = 1088;
 
 #[account]
 pub struct Loader{
+    /// Account that created the loader, the only one allowed to write chunks
+    pub authority: Pubkey,
     pub ciphertext: [u8;KYBER_CIPHERTEXT_SIZE]
 }
 
 impl Loader{
-    pub const LEN:usize = 8 + KYBER_CIPHERTEXT_SIZE;
+    pub const LEN:usize = 8 + 32 + KYBER_CIPHERTEXT_SIZE;
 }
@@ -1,15 +1,25 @@
 pub mod vault;
+#[cfg(not(feature = "mainnet"))]
+pub mod airdrop_limit;
 pub mod ledger;
+pub mod ledger_page;
 pub mod loader;
+pub mod nullifier;
 pub mod proof;
 pub mod program_config;
 pub mod prover_registry;
+pub mod prover_stake;
 pub mod utxo;
 
 pub use vault::*;
+#[cfg(not(feature = "mainnet"))]
+pub use airdrop_limit::*;
 pub use ledger::*;
+pub use ledger_page::*;
 pub use loader::*;
+pub use nullifier::*;
 pub use proof::*;
 pub use program_config::*;
 pub use prover_registry::*;
+pub use prover_stake::*;
 pub use utxo::*;
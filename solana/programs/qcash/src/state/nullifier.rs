@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// Marks a UTXO nullifier as spent
+/// PDA: ["nullifier", nullifier]. Existence alone means the note behind it is spent.
+#[account]
+#[derive(Default)]
+pub struct Nullifier {
+    /// The nullifier committed by the guest
+    pub nullifier: [u8; 32],
+
+    /// Hash of the UTXO whose finalization spent it
+    pub spent_in: [u8; 32],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Nullifier {
+    /// Size of the Nullifier account in bytes
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // nullifier
+        32 + // spent_in
+        1; // bump
+}
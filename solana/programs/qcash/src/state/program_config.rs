@@ -1,10 +1,15 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
 
 /// Program configuration account
+/// Fields after `bump` were appended to the original layout, configs created before them
+/// are brought up to SIZE by migrate_program_config
 #[account]
 #[derive(Default)]
 pub struct ProgramConfig {
     /// Admin authority who can manage provers
+    /// Ignored while an admin signer set is configured
     pub admin_authority: Pubkey,
     
     /// Minimum attestations required for UTXO validity
@@ -12,21 +17,114 @@ pub struct ProgramConfig {
     
     /// Bump seed for PDA
     pub bump: u8,
+
+    /// Invalid votes that reject a UTXO, raised to the safe minimum by rejection_quorum
+    /// 0 on configs created before the field existed
+    pub min_rejections: u16,
+
+    /// Only signer allowed to call airdrop
+    pub faucet_authority: Pubkey,
+
+    /// Airdrops a single vault can receive per Solana epoch
+    pub max_airdrops_per_vault: u32,
+
+    /// Airdrops the faucet can make per Solana epoch across all vaults
+    pub max_airdrops_per_epoch: u32,
+
+    /// Lamports a prover must have staked to vote
+    pub min_prover_stake: u64,
+
+    /// Share of a prover's stake slashed per wrong vote, in basis points
+    pub slash_bps: u16,
+
+    /// Seconds unstaked lamports wait before they can be withdrawn
+    pub unbonding_period: i64,
+
+    /// Lamports escrowed by create_utxo and transfer, split among the provers that decide the UTXO
+    pub attestation_fee: u64,
+
+    /// Admin proposed with propose_admin, takes over once it calls accept_admin
+    pub pending_admin: Option<Pubkey>,
+
+    /// M-of-N admin signer set, empty when a single admin_authority is in charge
+    pub admin_signers: Vec<Pubkey>,
+
+    /// Admin signers that must sign an admin instruction
+    pub admin_threshold: u8,
 }
 
 impl ProgramConfig {
+    /// Size of the original ProgramConfig account, before fields were appended after `bump`
+    pub const LEGACY_SIZE: usize = 8 + // discriminator
+        32 + // admin_authority
+        2 + // min_attestations
+        1 + // bump
+        128; // padding for future fields
+
     /// Size of the ProgramConfig account in bytes
     pub const SIZE: usize = 8 + // discriminator
         32 + // admin_authority
         2 + // min_attestations
         1 + // bump
-        128; // padding for future fields
+        2 + // min_rejections
+        32 + // faucet_authority
+        4 + // max_airdrops_per_vault
+        4 + // max_airdrops_per_epoch
+        8 + // min_prover_stake
+        2 + // slash_bps
+        8 + // unbonding_period
+        8 + // attestation_fee
+        1 + 32 + // pending_admin
+        4 + 32 * MAX_ADMIN_SIGNERS + // admin_signers
+        1 + // admin_threshold
+        60; // padding for future fields
 
-    /// Initialize the program config
-    pub fn initialize(&mut self, admin_authority: Pubkey, min_attestations: u16, bump: u8) {
+    /// Initialize the program config, the admin starts out as the faucet authority
+    pub fn initialize(
+        &mut self,
+        admin_authority: Pubkey,
+        min_attestations: u16,
+        min_rejections: u16,
+        bump: u8,
+    ) {
         self.admin_authority = admin_authority;
         self.min_attestations = min_attestations;
         self.bump = bump;
+        self.set_appended_defaults();
+        self.min_rejections = min_rejections;
+    }
+
+    /// Set the fields appended after `bump` to their defaults, the admin becomes the faucet authority
+    /// Old configs read them as zeros, migrate_program_config fills them in once
+    pub fn set_appended_defaults(&mut self) {
+        self.min_rejections = MIN_REJECTIONS_REQUIRED;
+        self.faucet_authority = self.admin_authority;
+        self.max_airdrops_per_vault = DEFAULT_MAX_AIRDROPS_PER_VAULT;
+        self.max_airdrops_per_epoch = DEFAULT_MAX_AIRDROPS_PER_EPOCH;
+        self.min_prover_stake = DEFAULT_MIN_PROVER_STAKE;
+        self.slash_bps = DEFAULT_SLASH_BPS;
+        self.unbonding_period = DEFAULT_UNBONDING_PERIOD;
+        self.attestation_fee = DEFAULT_ATTESTATION_FEE;
+        self.pending_admin = None;
+        self.admin_signers = Vec::new();
+        self.admin_threshold = 0;
+    }
+
+    /// Fewest invalid votes that leave too few provers to reach `min_attestations`
+    /// Any fewer and a minority could reject a UTXO the others can still finalize
+    pub fn min_safe_rejections(active_provers: u16, min_attestations: u16) -> u16 {
+        active_provers
+            .min(MAX_VOTES_ALLOWED as u16)
+            .saturating_sub(min_attestations)
+            + 1
+    }
+
+    /// Invalid votes that reject a UTXO with `active_provers` able to vote
+    /// min_rejections never lowers it below the safe minimum, which also covers a 0 read
+    /// from configs created before min_rejections existed
+    pub fn rejection_quorum(&self, active_provers: u16) -> u16 {
+        self.min_rejections
+            .max(Self::min_safe_rejections(active_provers, self.min_attestations))
     }
 
     /// Check if admin authority is set
@@ -38,4 +136,44 @@ impl ProgramConfig {
     pub fn is_admin(&self, pubkey: &Pubkey) -> bool {
         self.admin_authority == *pubkey
     }
+
+    /// Check if the admin is an M-of-N signer set
+    pub fn is_multisig(&self) -> bool {
+        !self.admin_signers.is_empty()
+    }
+
+    /// Check an admin instruction is authorized
+    /// With a single admin, `admin` must be the admin authority. With a signer set,
+    /// `admin` and the signing `cosigners` (passed as remaining accounts) must
+    /// include at least admin_threshold distinct admin signers.
+    pub fn require_admin(&self, admin: &Pubkey, cosigners: &[AccountInfo]) -> Result<()> {
+        if !self.is_multisig() {
+            require!(self.is_admin(admin), ErrorCode::UnauthorizedAdmin);
+            return Ok(());
+        }
+
+        require!(self.admin_signers.contains(admin), ErrorCode::UnauthorizedAdmin);
+
+        let mut approvals = vec![*admin];
+        for cosigner in cosigners {
+            if cosigner.is_signer
+                && self.admin_signers.contains(cosigner.key)
+                && !approvals.contains(cosigner.key)
+            {
+                approvals.push(*cosigner.key);
+            }
+        }
+
+        require!(
+            approvals.len() >= self.admin_threshold as usize,
+            ErrorCode::InsufficientAdminSignatures
+        );
+
+        Ok(())
+    }
+
+    /// Check if given pubkey is the faucet
+    pub fn is_faucet(&self, pubkey: &Pubkey) -> bool {
+        self.faucet_authority == *pubkey
+    }
 }
@@ -4,17 +4,25 @@ use anchor_lang::prelude::*;
 pub struct ZkProof {
     pub total_len: u32,
     pub bytes_written: u32,
+    /// Account that initialized the proof, pays its rent and is the only one allowed to write chunks
+    pub payer: Pubkey,
+    /// UTXO that consumed this proof, default until create_utxo/transfer
+    pub locked_by: Pubkey,
     // NOTE: Actual proof bytes follow this header in the account data
 }
 
 impl ZkProof {
     pub const HEADER_SIZE: usize = 8 + // discriminator
         4 + // total_len
-        4; // bytes_written
+        4 + // bytes_written
+        32 + // payer
+        32; // locked_by
     
     // Field offsets (absolute, including discriminator)
     const TOTAL_LEN_OFFSET: usize = 8;
     const BYTES_WRITTEN_OFFSET: usize = 12;
+    const PAYER_OFFSET: usize = 16;
+    const LOCKED_BY_OFFSET: usize = 48;
 
     pub fn space(proof_len: u32) -> Result<usize> {
         let space = Self::HEADER_SIZE
@@ -32,7 +40,12 @@ impl ZkProof {
         [99, 112, 199, 152, 247, 109, 47, 105]
     }
 
-    pub fn initialize(data: &mut [u8], total_length: u32) {
+    /// Check if the header has already been written
+    pub fn is_initialized(data: &[u8]) -> bool {
+        data.len() >= Self::HEADER_SIZE && data[0..8] == Self::discriminator()
+    }
+
+    pub fn initialize(data: &mut [u8], total_length: u32, payer: &Pubkey) {
         // Write discriminator
         data[0..8].copy_from_slice(&Self::discriminator());
 
@@ -43,6 +56,12 @@ impl ZkProof {
         // Write bytes_written (initialized to 0)
         data[Self::BYTES_WRITTEN_OFFSET..Self::BYTES_WRITTEN_OFFSET + 4]
             .copy_from_slice(&0u32.to_le_bytes());
+
+        // Write payer
+        data[Self::PAYER_OFFSET..Self::PAYER_OFFSET + 32].copy_from_slice(payer.as_ref());
+
+        // Write locked_by (unlocked)
+        data[Self::LOCKED_BY_OFFSET..Self::LOCKED_BY_OFFSET + 32].fill(0);
     }
 
     /// Read total_len from account data
@@ -65,6 +84,46 @@ impl ZkProof {
         ])
     }
 
+    /// Read payer from account data
+    pub fn read_payer(data: &[u8]) -> Pubkey {
+        Pubkey::new_from_array(
+            data[Self::PAYER_OFFSET..Self::PAYER_OFFSET + 32]
+                .try_into()
+                .unwrap(),
+        )
+    }
+
+    /// Read locked_by from account data
+    pub fn read_locked_by(data: &[u8]) -> Pubkey {
+        Pubkey::new_from_array(
+            data[Self::LOCKED_BY_OFFSET..Self::LOCKED_BY_OFFSET + 32]
+                .try_into()
+                .unwrap(),
+        )
+    }
+
+    /// Check a fully written, unused proof and lock it to a UTXO so it can't be reused
+    /// Only the account that uploaded the proof may use it, a proof seen on chain can't be
+    /// front-run into someone else's UTXO
+    pub fn validate_and_lock(account: &AccountInfo, signer: &Pubkey, utxo: &Pubkey) -> Result<()> {
+        require_keys_eq!(*account.owner, crate::ID, ErrorCode::ZkProofInvalidOwner);
+
+        let mut data = account.try_borrow_mut_data()?;
+        require!(Self::is_initialized(&data), ErrorCode::ZkProofNotInitialized);
+        require_keys_eq!(Self::read_payer(&data), *signer, ErrorCode::UnauthorizedWriter);
+        require!(
+            Self::read_bytes_written(&data) == Self::read_total_len(&data),
+            ErrorCode::ZkProofIncomplete
+        );
+        require!(
+            Self::read_locked_by(&data) == Pubkey::default(),
+            ErrorCode::ZkProofAlreadyLocked
+        );
+
+        data[Self::LOCKED_BY_OFFSET..Self::LOCKED_BY_OFFSET + 32].copy_from_slice(utxo.as_ref());
+        Ok(())
+    }
+
     /// Update bytes_written in account data
     pub fn update_bytes_written(data: &mut [u8], new_bytes_written: u32) {
         data[Self::BYTES_WRITTEN_OFFSET..Self::BYTES_WRITTEN_OFFSET + 4]
@@ -84,4 +143,18 @@ impl ZkProof {
         let chunk_len = chunk.len();
         data[write_offset..write_offset + chunk_len].copy_from_slice(chunk);
     }
+
+    /// Close the account, sending its lamports to the destination
+    pub fn close<'info>(account: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+        let lamports = account.lamports();
+        **destination.try_borrow_mut_lamports()? = destination
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        **account.try_borrow_mut_lamports()? = 0;
+
+        account.assign(&System::id());
+        account.resize(0)?;
+        Ok(())
+    }
 }
@@ -121,6 +121,19 @@ impl ProverRegistry {
             .position(|p| &p.pubkey_hash == pubkey_hash)
     }
 
+    /// Finds a prover by unique ID, stable across key rotations
+    pub fn find_prover_by_id(&self, unique_id: u64) -> Option<usize> {
+        self.provers.iter().position(|p| p.unique_id == unique_id)
+    }
+
+    /// Gets a mutable reference to a prover by unique ID
+    pub fn get_prover_by_id_mut(&mut self, unique_id: u64) -> Result<&mut ProverInfo> {
+        let index = self
+            .find_prover_by_id(unique_id)
+            .ok_or(ErrorCode::ProverNotRegistered)?;
+        Ok(&mut self.provers[index])
+    }
+
     /// Gets a mutable reference to a prover by public key hash
     pub fn get_prover_mut(&mut self, pubkey_hash: &[u8; 32]) -> Result<&mut ProverInfo> {
         let index = self
@@ -129,10 +142,48 @@ impl ProverRegistry {
         Ok(&mut self.provers[index])
     }
 
+    /// Number of provers that can currently vote
+    pub fn active_prover_count(&self) -> u16 {
+        self.provers.iter().filter(|p| p.is_active()).count() as u16
+    }
+
     /// Deactivates a prover
     pub fn deactivate_prover(&mut self, pubkey_hash: &[u8; 32]) -> Result<()> {
         let prover = self.get_prover_mut(pubkey_hash)?;
         prover.deactivate();
         Ok(())
     }
+
+    /// Reactivates a deactivated prover
+    pub fn reactivate_prover(&mut self, unique_id: u64) -> Result<()> {
+        let prover = self.get_prover_by_id_mut(unique_id)?;
+        require!(!prover.is_active(), ErrorCode::ProverAlreadyActive);
+        prover.activate();
+        Ok(())
+    }
+
+    /// Removes a deactivated prover, freeing its slot
+    /// Returns the removed prover
+    pub fn remove_prover(&mut self, unique_id: u64) -> Result<ProverInfo> {
+        let index = self
+            .find_prover_by_id(unique_id)
+            .ok_or(ErrorCode::ProverNotRegistered)?;
+        require!(!self.provers[index].is_active(), ErrorCode::ProverStillActive);
+
+        let prover = self.provers.remove(index);
+        self.prover_count = self.provers.len() as u32;
+        Ok(prover)
+    }
+
+    /// Replaces a prover's key hash, for a node that lost its rotation keys
+    pub fn reset_prover_key(&mut self, unique_id: u64, new_pubkey_hash: [u8; 32]) -> Result<()> {
+        require!(
+            self.find_prover(&new_pubkey_hash).is_none(),
+            ErrorCode::ProverAlreadyRegistered
+        );
+
+        let prover = self.get_prover_by_id_mut(unique_id)?;
+        prover.update_pubkey_hash(new_pubkey_hash);
+        Ok(())
+    }
 }
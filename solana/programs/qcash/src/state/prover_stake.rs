@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+/// SOL a prover locks to back its votes
+/// PDA: ["prover_stake", unique_id]. Keyed by unique ID so it survives key rotation.
+/// The account keeps rent + amount + unbonding_amount + rewards lamports and is never closed,
+/// so a prover's wrong votes stay slashable after it withdraws.
+#[account]
+#[derive(Default)]
+pub struct ProverStake {
+    /// Unique ID of the prover in the registry
+    pub unique_id: u64,
+
+    /// Account that funded the stake, the only one allowed to withdraw it
+    pub owner: Pubkey,
+
+    /// Staked lamports, counted towards the minimum stake needed to vote
+    pub amount: u64,
+
+    /// Lamports waiting out the unbonding period, still slashable
+    pub unbonding_amount: u64,
+
+    /// Unix timestamp when unbonding_amount can be withdrawn
+    pub unbonding_ends_at: i64,
+
+    /// Lamports slashed so far
+    pub slashed_amount: u64,
+
+    /// Attestation fees earned and not yet claimed, never slashed
+    pub rewards: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ProverStake {
+    /// Size of the ProverStake account in bytes
+    pub const SIZE: usize = 8 + // discriminator
+        8 + // unique_id
+        32 + // owner
+        8 + // amount
+        8 + // unbonding_amount
+        8 + // unbonding_ends_at
+        8 + // slashed_amount
+        8 + // rewards
+        1; // bump
+
+    /// Move `amount` from the stake into unbonding, restarting the unbonding period
+    pub fn start_unbonding(&mut self, amount: u64, now: i64, unbonding_period: i64) -> Result<()> {
+        require!(amount > 0 && amount <= self.amount, ErrorCode::InvalidStakeAmount);
+
+        self.amount -= amount;
+        self.unbonding_amount = self
+            .unbonding_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.unbonding_ends_at = now
+            .checked_add(unbonding_period)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// Take `slash_bps` of everything at stake, staked lamports first
+    /// Returns the slashed lamports
+    pub fn slash(&mut self, slash_bps: u16) -> Result<u64> {
+        let at_stake = self
+            .amount
+            .checked_add(self.unbonding_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let slashed = (at_stake as u128 * slash_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+
+        let from_stake = slashed.min(self.amount);
+        self.amount -= from_stake;
+        self.unbonding_amount -= slashed - from_stake;
+        self.slashed_amount = self
+            .slashed_amount
+            .checked_add(slashed)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(slashed)
+    }
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
 use crate::constants::*;
 use crate::error::ErrorCode;
+use crate::state::Ledger;
 
 /// Vote record for a prover on a UTXO
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
@@ -25,8 +27,27 @@ impl ProverVote {
     }
 }
 
+/// Lifecycle of a UTXO
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum UtxoStatus {
+    /// Waiting for attestations
+    #[default]
+    Pending,
+    /// Reached the attestation threshold and is on the ledger
+    Finalized,
+    /// Reached the rejection threshold with as many invalid votes as it takes to finalize,
+    /// valid votes on it are slashable. Its rent can be reclaimed by the payer
+    Rejected,
+    /// Can no longer be finalized but no quorum found it invalid: its ledger tip or epoch went
+    /// stale, or the rejection threshold was reached by fewer provers than it takes to finalize.
+    /// No vote on it is slashable, its rent can be reclaimed by the payer
+    Expired,
+}
+
 /// UTXO account structure
 /// Used for both regular UTXOs (with voting) and airdrop UTXOs (without voting)
+/// Fields added after the first release are appended after `bump`, accounts created before
+/// them are brought up to date by migrate_utxo
 #[account]
 pub struct Utxo {
     /// Epoch number
@@ -35,7 +56,7 @@ pub struct Utxo {
     /// Hash of this UTXO
     pub utxo_hash: [u8; 32],
     
-    /// Hash of previous UTXO
+    /// Ledger tip the proof linked this UTXO to, any recent tip at creation time
     pub prev_utxo_hash: [u8; 32],
     
     /// SHA256 commitment of ciphertext+payload+nonce
@@ -58,11 +79,73 @@ pub struct Utxo {
     
     /// Bump seed for PDA
     pub bump: u8,
+
+    /// SHA256(kyber_ciphertext || nonce || encrypted_payload), the guest commits the same hash
+    pub output_hash: [u8; 32],
+
+    /// Nullifiers of the inputs spent by this UTXO (empty for airdrop UTXOs)
+    pub nullifiers: Vec<[u8; 32]>,
+
+    /// Change UTXO of the same transfer, finalized together with this one
+    pub change_utxo: Option<Pubkey>,
+
+    /// Account that paid the rent, refunded if the UTXO is rejected
+    pub payer: Pubkey,
+
+    /// Pending until the attestation or rejection threshold is reached
+    pub status: UtxoStatus,
+
+    /// Escrowed lamports paid to the deciding provers, 0 once paid or for unvoted UTXOs
+    pub attestation_fee: u64,
+
+    /// UTXO finalized right before this one, zeros until finalized or for the first UTXO
+    pub ledger_prev_utxo_hash: [u8; 32],
+
+    /// Ledger tip right before this UTXO was appended, zeros until finalized
+    pub ledger_prev_tip: [u8; 32],
+
+    /// Position on the ledger (0-based), locates the UTXO in the ledger index pages
+    /// 0 until finalized, check `status` before trusting it
+    pub ledger_index: u64,
+
+    /// Set for each vote slot once the prover was slashed for that vote
+    /// Kept out of ProverVote so the votes keep the layout existing accounts were written with
+    pub slashed_votes: [bool; MAX_VOTES_ALLOWED],
+}
+
+/// Utxo fields as the first release wrote them, ending at `bump`
+/// Followed by 128 bytes of padding (64 for airdrop UTXOs), too few for the appended fields
+#[derive(AnchorDeserialize)]
+pub struct LegacyUtxo {
+    pub epoch: u32,
+    pub utxo_hash: [u8; 32],
+    pub prev_utxo_hash: [u8; 32],
+    pub ciphertext_commitment: [u8; 32],
+    pub nonce: [u8; NONCE_SIZE],
+    pub encrypted_payload: Vec<u8>,
+    pub kyber_ciphertext: [u8; KYBER_CIPHERTEXT_SIZE],
+    pub zk_proof_pubkey: Option<Pubkey>,
+    pub votes: Option<[ProverVote; MAX_VOTES_ALLOWED]>,
+    pub bump: u8,
 }
 
 impl Utxo {
-    /// Calculate size based on payload length
-    pub fn size(payload_len: usize) -> usize {
+    /// Size of the fields appended after `bump`
+    fn appended_size(nullifier_count: usize, has_change: bool) -> usize {
+        32 + // output_hash
+        4 + (32 * nullifier_count) + // nullifiers (Vec prefix + data)
+        1 + if has_change { 32 } else { 0 } + // change_utxo (Option tag + Pubkey)
+        32 + // payer
+        1 + // status
+        8 + // attestation_fee
+        32 + // ledger_prev_utxo_hash
+        32 + // ledger_prev_tip
+        8 + // ledger_index
+        MAX_VOTES_ALLOWED // slashed_votes
+    }
+
+    /// Calculate size based on payload length and number of nullifiers
+    pub fn size(payload_len: usize, nullifier_count: usize) -> usize {
         8 + // discriminator
         4 + // epoch
         32 + // utxo_hash
@@ -74,6 +157,7 @@ impl Utxo {
         1 + 32 + // zk_proof_pubkey (Option tag + Pubkey)
         1 + (ProverVote::SIZE * MAX_VOTES_ALLOWED) + // votes (Option tag + array)
         1 + // bump
+        Self::appended_size(nullifier_count, true) +
         128 // padding
     }
 
@@ -90,9 +174,54 @@ impl Utxo {
         1 + // zk_proof_pubkey (Option tag, None = 1 byte)
         1 + // votes (Option tag, None = 1 byte)
         1 + // bump
+        Self::appended_size(0, false) +
         64 // padding
     }
 
+    /// Size of an account the first release created for `legacy`
+    pub fn legacy_size(legacy: &LegacyUtxo) -> usize {
+        if legacy.votes.is_some() {
+            Self::size(legacy.encrypted_payload.len(), 0) - Self::appended_size(0, true)
+        } else {
+            Self::size_airdrop(legacy.encrypted_payload.len()) - Self::appended_size(0, false)
+        }
+    }
+
+    /// Rebuild a UTXO from its first release fields
+    /// Airdrops and UTXOs that reached MIN_ATTESTATIONS_REQUIRED were finalized then, any other
+    /// can never finalize now and is Expired. Those accounts never recorded who paid for them,
+    /// `payer` takes that place.
+    pub fn from_legacy(legacy: LegacyUtxo, payer: Pubkey) -> Self {
+        let mut utxo = Self {
+            epoch: legacy.epoch,
+            utxo_hash: legacy.utxo_hash,
+            prev_utxo_hash: legacy.prev_utxo_hash,
+            ciphertext_commitment: legacy.ciphertext_commitment,
+            nonce: legacy.nonce,
+            output_hash: Self::compute_output_hash(&legacy.kyber_ciphertext, &legacy.nonce, &legacy.encrypted_payload),
+            encrypted_payload: legacy.encrypted_payload,
+            kyber_ciphertext: legacy.kyber_ciphertext,
+            zk_proof_pubkey: legacy.zk_proof_pubkey,
+            votes: legacy.votes,
+            bump: legacy.bump,
+            nullifiers: Vec::new(),
+            change_utxo: None,
+            payer,
+            status: UtxoStatus::Pending,
+            attestation_fee: 0,
+            ledger_prev_utxo_hash: [0u8; 32],
+            ledger_prev_tip: [0u8; 32],
+            ledger_index: 0,
+            slashed_votes: [false; MAX_VOTES_ALLOWED],
+        };
+        utxo.status = if !utxo.requires_voting() || utxo.threshold_met(MIN_ATTESTATIONS_REQUIRED) {
+            UtxoStatus::Finalized
+        } else {
+            UtxoStatus::Expired
+        };
+        utxo
+    }
+
     /// Initialize a new UTXO with voting (regular create_utxo flow)
     pub fn initialize(
         &mut self,
@@ -104,6 +233,8 @@ impl Utxo {
         encrypted_payload: Vec<u8>,
         kyber_ciphertext: [u8; KYBER_CIPHERTEXT_SIZE],
         zk_proof_pubkey: Pubkey,
+        nullifiers: Vec<[u8; 32]>,
+        payer: Pubkey,
         bump: u8,
     ) {
         self.epoch = epoch;
@@ -111,10 +242,20 @@ impl Utxo {
         self.prev_utxo_hash = prev_utxo_hash;
         self.ciphertext_commitment = ciphertext_commitment;
         self.nonce = nonce;
+        self.output_hash = Self::compute_output_hash(&kyber_ciphertext, &nonce, &encrypted_payload);
         self.encrypted_payload = encrypted_payload;
         self.kyber_ciphertext = kyber_ciphertext;
         self.zk_proof_pubkey = Some(zk_proof_pubkey);
         self.votes = Some([ProverVote::default(); MAX_VOTES_ALLOWED]);
+        self.nullifiers = nullifiers;
+        self.change_utxo = None;
+        self.payer = payer;
+        self.status = UtxoStatus::Pending;
+        self.attestation_fee = 0;
+        self.ledger_prev_utxo_hash = [0u8; 32];
+        self.ledger_prev_tip = [0u8; 32];
+        self.ledger_index = 0;
+        self.slashed_votes = [false; MAX_VOTES_ALLOWED];
         self.bump = bump;
     }
 
@@ -128,6 +269,7 @@ impl Utxo {
         nonce: [u8; NONCE_SIZE],
         encrypted_payload: Vec<u8>,
         kyber_ciphertext: [u8; KYBER_CIPHERTEXT_SIZE],
+        payer: Pubkey,
         bump: u8,
     ) {
         self.epoch = epoch;
@@ -135,13 +277,68 @@ impl Utxo {
         self.prev_utxo_hash = prev_utxo_hash;
         self.ciphertext_commitment = ciphertext_commitment;
         self.nonce = nonce;
+        self.output_hash = Self::compute_output_hash(&kyber_ciphertext, &nonce, &encrypted_payload);
         self.encrypted_payload = encrypted_payload;
         self.kyber_ciphertext = kyber_ciphertext;
         self.zk_proof_pubkey = None;
         self.votes = None;
+        self.nullifiers = Vec::new();
+        self.change_utxo = None;
+        self.payer = payer;
+        self.status = UtxoStatus::Finalized;
+        self.attestation_fee = 0;
+        self.ledger_prev_utxo_hash = [0u8; 32];
+        self.ledger_prev_tip = [0u8; 32];
+        self.ledger_index = 0;
+        self.slashed_votes = [false; MAX_VOTES_ALLOWED];
         self.bump = bump;
     }
 
+    /// Initialize the change UTXO of a transfer (no voting - finalized with its receiver UTXO)
+    pub fn initialize_change(
+        &mut self,
+        epoch: u32,
+        utxo_hash: [u8; 32],
+        prev_utxo_hash: [u8; 32],
+        ciphertext_commitment: [u8; 32],
+        nonce: [u8; NONCE_SIZE],
+        encrypted_payload: Vec<u8>,
+        kyber_ciphertext: [u8; KYBER_CIPHERTEXT_SIZE],
+        zk_proof_pubkey: Pubkey,
+        payer: Pubkey,
+        bump: u8,
+    ) {
+        self.epoch = epoch;
+        self.utxo_hash = utxo_hash;
+        self.prev_utxo_hash = prev_utxo_hash;
+        self.ciphertext_commitment = ciphertext_commitment;
+        self.output_hash = Self::compute_output_hash(&kyber_ciphertext, &nonce, &encrypted_payload);
+        self.nonce = nonce;
+        self.encrypted_payload = encrypted_payload;
+        self.kyber_ciphertext = kyber_ciphertext;
+        self.zk_proof_pubkey = Some(zk_proof_pubkey);
+        self.votes = None;
+        self.nullifiers = Vec::new();
+        self.change_utxo = None;
+        self.payer = payer;
+        self.status = UtxoStatus::Pending;
+        self.attestation_fee = 0;
+        self.ledger_prev_utxo_hash = [0u8; 32];
+        self.ledger_prev_tip = [0u8; 32];
+        self.ledger_index = 0;
+        self.slashed_votes = [false; MAX_VOTES_ALLOWED];
+        self.bump = bump;
+    }
+
+    /// Hash of the output exactly as stored on chain
+    pub fn compute_output_hash(
+        kyber_ciphertext: &[u8; KYBER_CIPHERTEXT_SIZE],
+        nonce: &[u8; NONCE_SIZE],
+        encrypted_payload: &[u8],
+    ) -> [u8; 32] {
+        hashv(&[kyber_ciphertext, nonce, encrypted_payload]).to_bytes()
+    }
+
     /// Check if this UTXO requires voting (not an airdrop)
     pub fn requires_voting(&self) -> bool {
         self.votes.is_some()
@@ -206,5 +403,96 @@ impl Utxo {
     pub fn threshold_met(&self, min_attestations: u16) -> bool {
         self.get_valid_votes() >= min_attestations
     }
+
+    /// Checks if the rejection threshold is met
+    pub fn rejection_met(&self, min_rejections: u16) -> bool {
+        self.get_invalid_votes() >= min_rejections
+    }
+
+    /// Outcome of a UTXO that can no longer be finalized
+    /// Valid voters are only held to a rejection backed by as many provers as finalizing takes,
+    /// and never to one caused by the UTXO going stale after they voted
+    pub fn rejection_status(&self, stale: bool, min_attestations: u16) -> UtxoStatus {
+        if !stale && self.get_invalid_votes() >= min_attestations {
+            UtxoStatus::Rejected
+        } else {
+            UtxoStatus::Expired
+        }
+    }
+
+    /// Checks if the payer can close the UTXO and reclaim its rent
+    pub fn is_reclaimable(&self) -> bool {
+        matches!(self.status, UtxoStatus::Rejected | UtxoStatus::Expired)
+    }
+
+    /// Append this UTXO to the ledger, recording where it landed
+    pub fn append_to_ledger(&mut self, ledger: &mut Ledger) {
+        self.ledger_index = ledger.count;
+        let (prev_utxo_hash, prev_tip) = ledger.update_tip(self.utxo_hash);
+        self.ledger_prev_utxo_hash = prev_utxo_hash;
+        self.ledger_prev_tip = prev_tip;
+    }
+
+    /// Unique IDs of the provers whose votes decided the UTXO's final outcome, in vote order
+    pub fn deciding_voters(&self) -> Vec<u64> {
+        let outcome = match self.status {
+            UtxoStatus::Pending => return Vec::new(),
+            UtxoStatus::Finalized => true,
+            UtxoStatus::Rejected | UtxoStatus::Expired => false,
+        };
+        self.votes
+            .as_ref()
+            .map(|votes| {
+                votes
+                    .iter()
+                    .filter(|vote| vote.is_used() && vote.is_valid == outcome)
+                    .map(|vote| vote.prover_id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Checks if a vote went against the UTXO's final outcome, expired UTXOs have no wrong votes
+    pub fn is_wrong_vote(&self, vote: &ProverVote) -> bool {
+        match self.status {
+            UtxoStatus::Pending | UtxoStatus::Expired => false,
+            UtxoStatus::Finalized => vote.is_used() && !vote.is_valid,
+            UtxoStatus::Rejected => vote.is_used() && vote.is_valid,
+        }
+    }
+
+    /// Checks if any vote against the final outcome has not been slashed yet
+    pub fn has_unslashed_wrong_votes(&self) -> bool {
+        self.votes
+            .as_ref()
+            .map(|votes| {
+                votes
+                    .iter()
+                    .zip(self.slashed_votes.iter())
+                    .any(|(vote, slashed)| self.is_wrong_vote(vote) && !slashed)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Marks a prover's wrong vote as slashed
+    pub fn mark_slashed(&mut self, prover_unique_id: u64) -> Result<()> {
+        let index = self
+            .votes
+            .as_ref()
+            .and_then(|votes| {
+                votes.iter().zip(self.slashed_votes.iter()).position(|(vote, slashed)| {
+                    vote.prover_id == prover_unique_id && self.is_wrong_vote(vote) && !slashed
+                })
+            })
+            .ok_or(ErrorCode::NoSlashableVote)?;
+
+        self.slashed_votes[index] = true;
+        Ok(())
+    }
+
+    /// Check if this UTXO is still waiting for votes
+    pub fn is_pending(&self) -> bool {
+        self.status == UtxoStatus::Pending
+    }
 }
 
@@ -39,6 +39,136 @@ describe("qcash_programs", () => {
   let utxoHash: Buffer;
   let utxoHash2: Buffer;
 
+  // updateConfig argument that leaves every field unchanged
+  const noConfigChange = {
+    minAttestations: null,
+    minRejections: null,
+    faucetAuthority: null,
+    maxAirdropsPerVault: null,
+    maxAirdropsPerEpoch: null,
+    minProverStake: null,
+    slashBps: null,
+    unbondingPeriod: null,
+    attestationFee: null,
+  };
+
+  // The program's default minimum prover stake, 0.1 SOL
+  const proverStakeAmount = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10);
+
+  const proverStakePda = (uniqueId: number): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("prover_stake"), new anchor.BN(uniqueId).toArrayLike(Buffer, "le", 8)],
+      program.programId,
+    )[0];
+
+  const randomBytes = (len: number): Buffer => {
+    const bytes = Buffer.alloc(len);
+    for (let i = 0; i < len; i++) {
+      bytes[i] = Math.floor(Math.random() * 256);
+    }
+    return bytes;
+  };
+
+  // output_hash of a UTXO as create_utxo recomputes it: SHA256(kyber_ciphertext || nonce || encrypted_payload)
+  const outputHash = (ciphertext: Buffer, nonce: Buffer, encryptedPayload: Buffer): number[] =>
+    sha256.array(Buffer.concat([ciphertext, nonce, encryptedPayload]));
+
+  // Creates a loader owned by the admin and fills it with a Kyber ciphertext, random unless given
+  const uploadRandomLoader = async (ciphertext: Buffer = randomBytes(1088)): Promise<PublicKey> => {
+    const loader = Keypair.generate();
+
+    await program.methods
+      .initLoader()
+      .accounts({
+        signer: admin.publicKey,
+        loader: loader.publicKey,
+      })
+      .signers([loader])
+      .rpc();
+
+    const CHUNK_SIZE = 800;
+    for (let offset = 0; offset < ciphertext.length; offset += CHUNK_SIZE) {
+      await program.methods
+        .writeLoader(offset, ciphertext.slice(offset, offset + CHUNK_SIZE))
+        .accounts({ authority: admin.publicKey, loader: loader.publicKey })
+        .rpc();
+    }
+
+    return loader.publicKey;
+  };
+
+  // Creates and initializes a proof account in one transaction, then fills it with random bytes as the admin
+  const uploadRandomProof = async (size: number): Promise<PublicKey> => {
+    const proof = Keypair.generate();
+    const space = 8 + 4 + 4 + 32 + 32 + size;
+
+    await program.methods
+      .initZkProof(size)
+      .accounts({ signer: admin.publicKey, zkProof: proof.publicKey })
+      .preInstructions([
+        SystemProgram.createAccount({
+          fromPubkey: admin.publicKey,
+          newAccountPubkey: proof.publicKey,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+          space,
+          programId: program.programId,
+        }),
+      ])
+      .signers([proof])
+      .rpc();
+
+    const proofData = randomBytes(size);
+    const CHUNK_SIZE = 800;
+    for (let offset = 0; offset < proofData.length; offset += CHUNK_SIZE) {
+      await program.methods
+        .writeZkProof(offset, proofData.slice(offset, offset + CHUNK_SIZE))
+        .accounts({ authority: admin.publicKey, zkProof: proof.publicKey })
+        .rpc();
+    }
+
+    return proof.publicKey;
+  };
+
+  // A new UTXO links to a recent ledger tip, the current one is always recent
+  const ledgerTip = async (): Promise<number[]> =>
+    Array.from((await program.account.ledger.fetch(ledgerPda)).tipHash);
+
+  const ledgerPagePda = (page: number): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("ledger_page"), new anchor.BN(page).toArrayLike(Buffer, "le", 8)],
+      program.programId,
+    )[0];
+
+  // The index page the next finalized UTXO goes to and the one after it
+  const LEDGER_PAGE_SIZE = 256;
+  const ledgerPages = async () => {
+    const count = (await program.account.ledger.fetch(ledgerPda)).count.toNumber();
+    const page = Math.floor(count / LEDGER_PAGE_SIZE);
+    return { ledgerPage: ledgerPagePda(page), nextLedgerPage: ledgerPagePda(page + 1) };
+  };
+
+  const airdropTo = (
+    signer: PublicKey,
+    loader: PublicKey,
+    prevUtxoHash: number[],
+    pages: { ledgerPage: PublicKey; nextLedgerPage: PublicKey },
+  ) =>
+    program.methods
+      .airdrop(
+        Array.from(randomBytes(32)),
+        randomBytes(100),
+        Array.from(randomBytes(12)),
+        Array.from(randomBytes(32)),
+        0,
+        prevUtxoHash,
+      )
+      .accounts({
+        signer,
+        vault: vaultPda,
+        loader,
+        ...pages,
+      });
+
   before("Setup test accounts and data", async () => {
     prover1 = Keypair.generate();
     prover2 = Keypair.generate();
@@ -151,10 +281,11 @@ describe("qcash_programs", () => {
     prover1NextKeypairHash = Buffer.from(sha256.array(prover1Current.publicKey.toBuffer()));
 
     const tx = await program.methods
-      .registerProver(new anchor.BN(uniqueId), Array.from(prover1NextKeypairHash))
+      .registerProver(new anchor.BN(uniqueId), Array.from(prover1NextKeypairHash), proverStakeAmount)
       .accounts({
         admin: admin.publicKey,
         proverPubkey: prover1.publicKey,
+        staker: admin.publicKey,
       })
       .rpc();
 
@@ -165,6 +296,10 @@ describe("qcash_programs", () => {
     );
     expect(registryAccount.proverCount).to.equal(1);
     console.log("Prover 1 registered with unique ID:", uniqueId);
+
+    const stakeAccount = await program.account.proverStake.fetch(proverStakePda(uniqueId));
+    expect(stakeAccount.owner.toString()).to.equal(admin.publicKey.toString());
+    expect(stakeAccount.amount.eq(proverStakeAmount)).to.be.true;
   });
 
   it("Registers prover 2", async () => {
@@ -178,10 +313,11 @@ describe("qcash_programs", () => {
 
     prover2NextKeypairHash = Buffer.from(sha256.array(prover2Current.publicKey.toBuffer()));
     const tx = await program.methods
-      .registerProver(new anchor.BN(uniqueId), Array.from(prover2NextKeypairHash))
+      .registerProver(new anchor.BN(uniqueId), Array.from(prover2NextKeypairHash), proverStakeAmount)
       .accounts({
         admin: admin.publicKey,
         proverPubkey: prover2.publicKey,
+        staker: admin.publicKey,
       })
       .rpc();
 
@@ -194,6 +330,93 @@ describe("qcash_programs", () => {
     console.log("Prover 2 registered with unique ID:", uniqueId);
   });
 
+  it("Updates the attestation threshold", async () => {
+    // 2-of-2 quorum
+    await program.methods
+      .updateConfig({ ...noConfigChange, minAttestations: 2 })
+      .accounts({
+        admin: admin.publicKey,
+      })
+      .rpc();
+
+    let configAccount = await program.account.programConfig.fetch(
+      programConfigPda,
+    );
+    expect(configAccount.minAttestations).to.equal(2);
+
+    // Back to a single attestation for the rest of the flow
+    await program.methods
+      .updateConfig({ ...noConfigChange, minAttestations: 1 })
+      .accounts({
+        admin: admin.publicKey,
+      })
+      .rpc();
+
+    configAccount = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAccount.minAttestations).to.equal(1);
+  });
+
+  it("Rejects a threshold above the active prover count", async () => {
+    try {
+      await program.methods
+        .updateConfig({ ...noConfigChange, minAttestations: 3 })
+        .accounts({
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for unreachable threshold");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidMinAttestations");
+    }
+  });
+
+  it("Rejects a zero rejection threshold", async () => {
+    try {
+      await program.methods
+        .updateConfig({ ...noConfigChange, minRejections: 0 })
+        .accounts({
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for zero rejection threshold");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidMinRejections");
+    }
+  });
+
+  it("Rejects a rejection threshold a minority could reach", async () => {
+    // With 2 active provers and a 1-vote quorum, one invalid vote leaves the other able to finalize
+    try {
+      await program.methods
+        .updateConfig({ ...noConfigChange, minRejections: 1 })
+        .accounts({
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for a rejection threshold below the safe minimum");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidMinRejections");
+    }
+  });
+
+  it("Rejects migrating a config that already has the current layout", async () => {
+    try {
+      await program.methods
+        .migrateProgramConfig()
+        .accounts({
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for migrating a current config");
+    } catch (error) {
+      expect(error.toString()).to.include("AlreadyMigrated");
+    }
+  });
+
   it("Initializes the ledger", async () => {
     [ledgerPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("ledger")],
@@ -221,6 +444,21 @@ describe("qcash_programs", () => {
     console.log("Ledger initialized with genesis hash");
   });
 
+  it("Rejects migrating a ledger that already has the current layout", async () => {
+    try {
+      await program.methods
+        .migrateLedger(new anchor.BN(0))
+        .accounts({
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for migrating a current ledger");
+    } catch (error) {
+      expect(error.toString()).to.include("AlreadyMigrated");
+    }
+  });
+
   it("Initializes a vault (part 1 of 2)", async () => {
     const kyberKeyPart1 = kyberPubkey.slice(0, 800);
 
@@ -283,6 +521,7 @@ describe("qcash_programs", () => {
       const tx = await program.methods
         .writeLoader(offset, Buffer.from(chunk))
         .accounts({
+          authority: admin.publicKey,
           loader: loaderKeypair.publicKey,
         })
         .rpc();
@@ -294,25 +533,165 @@ describe("qcash_programs", () => {
     console.log("Kyber ciphertext written to loader");
   });
 
+  it("Rejects a loader write from another account", async () => {
+    const intruder = Keypair.generate();
+
+    try {
+      await program.methods
+        .writeLoader(0, Buffer.alloc(32))
+        .accounts({
+          authority: intruder.publicKey,
+          loader: loaderKeypair.publicKey,
+        })
+        .signers([intruder])
+        .rpc();
+
+      expect.fail("Should have thrown error for a third party loader write");
+    } catch (error) {
+      expect(error.toString()).to.include("UnauthorizedWriter");
+    }
+
+    const loader = await program.account.loader.fetch(loaderKeypair.publicKey);
+    expect(Buffer.from(loader.ciphertext).equals(kyberCiphertext)).to.be.true;
+  });
+
+  it("Rejects an airdrop from a non-faucet signer", async () => {
+    const intruder = Keypair.generate();
+
+    try {
+      await airdropTo(intruder.publicKey, loaderKeypair.publicKey, await ledgerTip(), await ledgerPages())
+        .signers([intruder])
+        .rpc();
+
+      expect.fail("Should have thrown error for a non-faucet airdrop");
+    } catch (error) {
+      expect(error.toString()).to.include("UnauthorizedFaucet");
+    }
+  });
+
+  it("Rate limits airdrops per vault", async () => {
+    await program.methods
+      .updateConfig({ ...noConfigChange, maxAirdropsPerVault: 1 })
+      .accounts({
+        admin: admin.publicKey,
+      })
+      .rpc();
+
+    // The admin is the faucet authority until update_config changes it
+    await airdropTo(admin.publicKey, await uploadRandomLoader(), await ledgerTip(), await ledgerPages()).rpc();
+
+    try {
+      await airdropTo(admin.publicKey, await uploadRandomLoader(), await ledgerTip(), await ledgerPages()).rpc();
+
+      expect.fail("Should have thrown error for a second airdrop this epoch");
+    } catch (error) {
+      expect(error.toString()).to.include("AirdropVaultLimitReached");
+    }
+
+    await program.methods
+      .updateConfig({ ...noConfigChange, maxAirdropsPerVault: 5 })
+      .accounts({
+        admin: admin.publicKey,
+      })
+      .rpc();
+  });
+
   it("Initializes ZK proof account", async () => {
     const proofSize = 500;
 
     console.log("ZK Proof Keypair:", zkProofKeypair.publicKey.toString());
-    console.log("Prover 1 Keypair:", prover1.publicKey.toString());
 
+    // Created and initialized in one transaction, signed by the proof account itself
+    // The admin uploads it, only the uploader can create a UTXO with it
+    const space = 8 + 4 + 4 + 32 + 32 + proofSize;
     const tx = await program.methods
       .initZkProof(proofSize)
       .accounts({
-        signer: prover1.publicKey,
+        signer: admin.publicKey,
         zkProof: zkProofKeypair.publicKey,
       })
-      .signers([prover1])
+      .preInstructions([
+        SystemProgram.createAccount({
+          fromPubkey: admin.publicKey,
+          newAccountPubkey: zkProofKeypair.publicKey,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+          space,
+          programId: program.programId,
+        }),
+      ])
+      .signers([zkProofKeypair])
       .rpc();
 
     console.log("Init ZK proof transaction:", tx);
     console.log("ZK proof account initialized");
   });
 
+  it("Rejects initializing someone else's pre-created ZK proof account", async () => {
+    const victim = Keypair.generate();
+    const intruder = Keypair.generate();
+    const space = 8 + 4 + 4 + 32 + 32 + 500;
+
+    // The owner creates the account in its own transaction, leaving it uninitialized on chain
+    const createTx = new anchor.web3.Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: admin.publicKey,
+        newAccountPubkey: victim.publicKey,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+        space,
+        programId: program.programId,
+      }),
+    );
+    await provider.sendAndConfirm(createTx, [victim]);
+
+    const airdropSig = await provider.connection.requestAirdrop(
+      intruder.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await provider.connection.confirmTransaction(airdropSig);
+
+    // Without the proof account's signature the intruder can't become its payer
+    try {
+      const ix = await program.methods
+        .initZkProof(500)
+        .accounts({
+          signer: intruder.publicKey,
+          zkProof: victim.publicKey,
+        })
+        .instruction();
+      const tx = new anchor.web3.Transaction().add(ix);
+      tx.feePayer = intruder.publicKey;
+      tx.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+      tx.partialSign(intruder);
+      await provider.connection.sendRawTransaction(tx.serialize({ requireAllSignatures: false }));
+
+      expect.fail("Should have thrown error for an unsigned proof account init");
+    } catch (error) {
+      expect(error.toString()).to.include("signature verification failure");
+    }
+
+    const account = await provider.connection.getAccountInfo(victim.publicKey);
+    expect(account.data.every((byte) => byte === 0)).to.be.true;
+  });
+
+  it("Rejects a ZK proof write from another account", async () => {
+    const intruder = Keypair.generate();
+
+    try {
+      await program.methods
+        .writeZkProof(0, Buffer.alloc(200))
+        .accounts({
+          authority: intruder.publicKey,
+          zkProof: zkProofKeypair.publicKey,
+        })
+        .signers([intruder])
+        .rpc();
+
+      expect.fail("Should have thrown error for a third party proof write");
+    } catch (error) {
+      expect(error.toString()).to.include("UnauthorizedWriter");
+    }
+  });
+
   it("Writes ZK proof data in chunks", async () => {
     const proofData = Buffer.alloc(500);
     for (let i = 0; i < proofData.length; i++) {
@@ -328,6 +707,7 @@ describe("qcash_programs", () => {
       const tx = await program.methods
         .writeZkProof(offset, Buffer.from(chunk))
         .accounts({
+          authority: admin.publicKey,
           zkProof: zkProofKeypair.publicKey,
         })
         .rpc();
@@ -352,21 +732,17 @@ describe("qcash_programs", () => {
     }
 
     const ledgerAccount = await program.account.ledger.fetch(ledgerPda);
-    const prevUtxoHash = Buffer.from(ledgerAccount.lastValidUtxoHash);
+    const prevUtxoHash = Buffer.from(ledgerAccount.tipHash);
 
-    const commitmentData = Buffer.concat([
-      kyberCiphertext,
-      nonce,
-      encryptedPayload,
-    ]);
-    const ciphertextCommitment = Buffer.from(sha256.array(commitmentData));
+    // Stands in for the payload commitment, only the guest can check it
+    const ciphertextCommitment = randomBytes(32);
 
-    // Compute UTXO hash: SHA256(prev_utxo_hash || ciphertext_commitment || epoch)
+    // Compute UTXO hash: SHA256(ciphertext_commitment || prev_utxo_hash || epoch)
     const epochBytes = Buffer.alloc(4);
     epochBytes.writeUInt32LE(epoch, 0);
     const headerData = Buffer.concat([
-      prevUtxoHash,
       ciphertextCommitment,
+      prevUtxoHash,
       epochBytes,
     ]);
     utxoHash = Buffer.from(sha256.array(headerData));
@@ -386,8 +762,11 @@ describe("qcash_programs", () => {
         Array.from(utxoHash),
         Buffer.from(encryptedPayload),
         Array.from(nonce),
-        Array.from(utxoHash),
+        Array.from(ciphertextCommitment),
         epoch,
+        [],
+        outputHash(kyberCiphertext, nonce, encryptedPayload),
+        Array.from(prevUtxoHash),
       )
       .accounts({
         signer: admin.publicKey,
@@ -425,6 +804,9 @@ describe("qcash_programs", () => {
       .accounts({
         proverOld: prover1.publicKey, // Current prover (signs and pays)
         prover: prover1Current.publicKey, // New prover (same for first attestation)
+        changeUtxo: null, // Created by createUtxo, so no change UTXO
+        proverStake: proverStakePda(1),
+        ...(await ledgerPages()),
         // ledger: ledgerPda,
         // utxo: utxoPda,
         // proverRegistry: proverRegistryPda,
@@ -447,81 +829,337 @@ describe("qcash_programs", () => {
     expect(validVotes).to.equal(1);
 
     // Verify ledger was updated (since MIN_ATTESTATIONS_REQUIRED = 1)
+    // Nothing finalized since the UTXO was created, so it was appended right after the tip it links to
     const ledgerAccount = await program.account.ledger.fetch(ledgerPda);
     expect(Buffer.from(ledgerAccount.lastValidUtxoHash)).to.deep.equal(
       utxoHash,
     );
-    expect(ledgerAccount.count.toString()).to.equal("1");
+    expect(Buffer.from(utxoAccount.ledgerPrevTip)).to.deep.equal(
+      Buffer.from(utxoAccount.prevUtxoHash),
+    );
+    const expectedTip = Buffer.from(
+      sha256.array(Buffer.concat([Buffer.from(utxoAccount.prevUtxoHash), utxoHash])),
+    );
+    expect(Buffer.from(ledgerAccount.tipHash)).to.deep.equal(expectedTip);
     console.log("✓ Attestation submitted and ledger updated");
   });
 
-  it("Creates a second UTXO (chained)", async () => {
-    const loader2Keypair = Keypair.generate();
+  it("Rejects migrating a UTXO that already has the current layout", async () => {
+    try {
+      await program.methods
+        .migrateUtxo(Array.from(utxoHash))
+        .accounts({
+          admin: admin.publicKey,
+          ledgerPage: (await ledgerPages()).ledgerPage,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for migrating a current UTXO");
+    } catch (error) {
+      expect(error.toString()).to.include("AlreadyMigrated");
+    }
+  });
+
+  it("Indexes finalized UTXOs in ledger pages", async () => {
+    const [utxoPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("utxo"), utxoHash],
+      program.programId,
+    );
+    const utxoAccount = await program.account.utxo.fetch(utxoPda);
+    const ledgerAccount = await program.account.ledger.fetch(ledgerPda);
+
+    // The UTXO was the last one appended to the ledger
+    const ledgerIndex = utxoAccount.ledgerIndex.toNumber();
+    expect(ledgerIndex).to.equal(ledgerAccount.count.toNumber() - 1);
+
+    // Page layout: discriminator, page number (u64), len (u32), then the hashes
+    const page = Math.floor(ledgerIndex / LEDGER_PAGE_SIZE);
+    const slot = ledgerIndex % LEDGER_PAGE_SIZE;
+    const pageAccount = await provider.connection.getAccountInfo(ledgerPagePda(page));
+    expect(pageAccount.owner.equals(program.programId)).to.be.true;
+
+    const data = Buffer.from(pageAccount.data);
+    expect(data.readBigUInt64LE(8)).to.equal(BigInt(page));
+    expect(data.readUInt32LE(16)).to.equal(slot + 1);
+    expect(data.subarray(20 + slot * 32, 20 + (slot + 1) * 32)).to.deep.equal(utxoHash);
+  });
+
+  it("Pays the escrowed attestation fee to the deciding prover", async () => {
+    const [utxoPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("utxo"), utxoHash],
+      program.programId,
+    );
+    const config = await program.account.programConfig.fetch(programConfigPda);
 
+    // Prover 1 alone decided the UTXO, so it earned the whole fee
+    const utxoAccount = await program.account.utxo.fetch(utxoPda);
+    expect(utxoAccount.attestationFee.toString()).to.equal("0");
+    let stakeAccount = await program.account.proverStake.fetch(proverStakePda(1));
+    expect(stakeAccount.rewards.eq(config.attestationFee)).to.be.true;
+
+    const balanceBefore = await provider.connection.getBalance(admin.publicKey);
     await program.methods
-      .initLoader()
+      .claimRewards(new anchor.BN(1))
+      .accounts({ owner: admin.publicKey })
+      .rpc();
+
+    stakeAccount = await program.account.proverStake.fetch(proverStakePda(1));
+    expect(stakeAccount.rewards.toString()).to.equal("0");
+    expect(stakeAccount.amount.eq(proverStakeAmount)).to.be.true;
+
+    // The claim transaction fee is smaller than the attestation fee
+    const balanceAfter = await provider.connection.getBalance(admin.publicKey);
+    expect(balanceAfter).to.be.greaterThan(balanceBefore);
+  });
+
+  it("Finalizes a transfer's receiver and change UTXOs together, in order", async () => {
+    const epoch = 0;
+    const epochBytes = Buffer.alloc(4);
+    epochBytes.writeUInt32LE(epoch, 0);
+
+    const receiverCiphertext = randomBytes(1088);
+    const changeCiphertext = randomBytes(1088);
+    const receiverLoader = await uploadRandomLoader(receiverCiphertext);
+    const changeLoader = await uploadRandomLoader(changeCiphertext);
+    const zkProof = await uploadRandomProof(500);
+
+    // The receiver links to the ledger tip, the change links to the receiver
+    const prevTip = Buffer.from(await ledgerTip());
+    const transferOutput = (ciphertext: Buffer, prevUtxoHash: Buffer) => {
+      const nonce = randomBytes(12);
+      const encryptedPayload = randomBytes(256);
+      const ciphertextCommitment = randomBytes(32);
+      const hash = Buffer.from(
+        sha256.array(Buffer.concat([ciphertextCommitment, prevUtxoHash, epochBytes])),
+      );
+      return {
+        utxoHash: Array.from(hash),
+        encryptedPayload,
+        nonce: Array.from(nonce),
+        ciphertextCommitment: Array.from(ciphertextCommitment),
+        epoch,
+        outputHash: outputHash(ciphertext, nonce, encryptedPayload),
+      };
+    };
+    const receiver = transferOutput(receiverCiphertext, prevTip);
+    const change = transferOutput(changeCiphertext, Buffer.from(receiver.utxoHash));
+    const receiverHash = Buffer.from(receiver.utxoHash);
+    const changeHash = Buffer.from(change.utxoHash);
+
+    const [receiverPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("utxo"), receiverHash],
+      program.programId,
+    );
+    const [changePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("utxo"), changeHash],
+      program.programId,
+    );
+
+    await program.methods
+      .transfer(receiver, change, [], Array.from(prevTip))
       .accounts({
         signer: admin.publicKey,
-        loader: loader2Keypair.publicKey,
+        receiverLoader,
+        changeLoader,
+        zkProof,
       })
-      .signers([loader2Keypair])
       .rpc();
 
-    const newCiphertext = Buffer.alloc(1088);
-    for (let i = 0; i < 1088; i++) {
-      newCiphertext[i] = Math.floor(Math.random() * 256);
-    }
-
-    let offset = 0;
-    const CHUNK_SIZE = 800;
-    while (offset < newCiphertext.length) {
-      const chunk = newCiphertext.slice(offset, offset + CHUNK_SIZE);
-      await program.methods
-        .writeLoader(offset, Buffer.from(chunk))
-        .accounts({ loader: loader2Keypair.publicKey })
-        .rpc();
-      offset += chunk.length;
-    }
-
+    // The change waits for the receiver's vote, it is never voted on itself
+    let receiverAccount = await program.account.utxo.fetch(receiverPda);
+    let changeAccount = await program.account.utxo.fetch(changePda);
+    expect(receiverAccount.changeUtxo.equals(changePda)).to.be.true;
+    expect(Buffer.from(changeAccount.prevUtxoHash)).to.deep.equal(receiverHash);
+    expect(changeAccount.status).to.deep.equal({ pending: {} });
 
-    const zkProof2Keypair = Keypair.generate();
-     console.log("ZK Proof Keypair:", zkProof2Keypair.publicKey.toString());
-    console.log("Prover 1 Keypair:", prover1.publicKey.toString());
+    prover1NextKeypairHash = Buffer.from(sha256.array(prover1Next.publicKey.toBuffer()));
     await program.methods
-      .initZkProof(500)
+      .submitAttestation(receiver.utxoHash, true, Array.from(prover1NextKeypairHash))
       .accounts({
-        signer: prover1.publicKey,
-        zkProof: zkProof2Keypair.publicKey,
+        proverOld: prover1Old.publicKey,
+        prover: prover1Current.publicKey,
+        changeUtxo: changePda,
+        proverStake: proverStakePda(1),
+        ...(await ledgerPages()),
       })
-      .signers([prover1])
+      .signers([prover1Old, prover1Current])
       .rpc();
 
-    const epoch = 0;
-    const nonce = Buffer.alloc(12);
-    for (let i = 0; i < 12; i++) {
-      nonce[i] = Math.floor(Math.random() * 256);
-    }
+    prover1Old = prover1Current;
+    prover1Current = prover1Next;
+    prover1Next = Keypair.generate();
 
-    const encryptedPayload = Buffer.alloc(256);
-    for (let i = 0; i < encryptedPayload.length; i++) {
-      encryptedPayload[i] = Math.floor(Math.random() * 256);
-    }
+    // Both are finalized and appended back to back, receiver first
+    receiverAccount = await program.account.utxo.fetch(receiverPda);
+    changeAccount = await program.account.utxo.fetch(changePda);
+    expect(receiverAccount.status).to.deep.equal({ finalized: {} });
+    expect(changeAccount.status).to.deep.equal({ finalized: {} });
+    expect(changeAccount.ledgerIndex.toNumber()).to.equal(receiverAccount.ledgerIndex.toNumber() + 1);
+    expect(Buffer.from(changeAccount.ledgerPrevUtxoHash)).to.deep.equal(receiverHash);
+    expect(Buffer.from(changeAccount.ledgerPrevTip)).to.deep.equal(
+      Buffer.from(sha256.array(Buffer.concat([prevTip, receiverHash]))),
+    );
 
     const ledgerAccount = await program.account.ledger.fetch(ledgerPda);
-    const prevUtxoHash = Buffer.from(ledgerAccount.lastValidUtxoHash);
+    expect(ledgerAccount.count.toNumber()).to.equal(changeAccount.ledgerIndex.toNumber() + 1);
+    expect(Buffer.from(ledgerAccount.lastValidUtxoHash)).to.deep.equal(changeHash);
+    const expectedTip = sha256.array(
+      Buffer.concat([Buffer.from(sha256.array(Buffer.concat([prevTip, receiverHash]))), changeHash]),
+    );
+    expect(Buffer.from(ledgerAccount.tipHash)).to.deep.equal(Buffer.from(expectedTip));
+  });
 
-    const commitmentData = Buffer.concat([
-      newCiphertext,
-      nonce,
-      encryptedPayload,
-    ]);
-    const ciphertextCommitment = Buffer.from(sha256.array(commitmentData));
+  it("Splits the attestation fee between the provers that decide a UTXO", async () => {
+    // A 2-of-2 quorum and an odd fee, so the deciding prover also gets the remainder
+    const config = await program.account.programConfig.fetch(programConfigPda);
+    const fee = new anchor.BN(1001);
+    await program.methods
+      .updateConfig({ ...noConfigChange, minAttestations: 2, attestationFee: fee })
+      .accounts({ admin: admin.publicKey })
+      .rpc();
+
+    const epoch = 0;
+    const epochBytes = Buffer.alloc(4);
+    epochBytes.writeUInt32LE(epoch, 0);
+    const ciphertext = randomBytes(1088);
+    const loader = await uploadRandomLoader(ciphertext);
+    const zkProof = await uploadRandomProof(500);
+    const nonce = randomBytes(12);
+    const encryptedPayload = randomBytes(256);
+    const ciphertextCommitment = randomBytes(32);
+    const prevUtxoHash = Buffer.from(await ledgerTip());
+    const hash = Buffer.from(
+      sha256.array(Buffer.concat([ciphertextCommitment, prevUtxoHash, epochBytes])),
+    );
+    const [utxoPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("utxo"), hash],
+      program.programId,
+    );
+
+    await program.methods
+      .createUtxo(
+        Array.from(hash),
+        encryptedPayload,
+        Array.from(nonce),
+        Array.from(ciphertextCommitment),
+        epoch,
+        [],
+        outputHash(ciphertext, nonce, encryptedPayload),
+        Array.from(prevUtxoHash),
+      )
+      .accounts({ signer: admin.publicKey, loader, zkProof })
+      .rpc();
+
+    const rewards = async (uniqueId: number) =>
+      (await program.account.proverStake.fetch(proverStakePda(uniqueId))).rewards;
+    const prover1Before = await rewards(1);
+    const prover2Before = await rewards(2);
+
+    // Prover 1 votes first, one valid vote doesn't decide the UTXO yet
+    prover1NextKeypairHash = Buffer.from(sha256.array(prover1Next.publicKey.toBuffer()));
+    await program.methods
+      .submitAttestation(Array.from(hash), true, Array.from(prover1NextKeypairHash))
+      .accounts({
+        proverOld: prover1Old.publicKey,
+        prover: prover1Current.publicKey,
+        changeUtxo: null,
+        proverStake: proverStakePda(1),
+        ...(await ledgerPages()),
+      })
+      .signers([prover1Old, prover1Current])
+      .rpc();
+
+    prover1Old = prover1Current;
+    prover1Current = prover1Next;
+    prover1Next = Keypair.generate();
+
+    let utxoAccount = await program.account.utxo.fetch(utxoPda);
+    expect(utxoAccount.status).to.deep.equal({ pending: {} });
+
+    // Prover 2 decides it and passes prover 1's stake along to share the fee
+    prover2NextKeypairHash = Buffer.from(sha256.array(prover2Next.publicKey.toBuffer()));
+    await program.methods
+      .submitAttestation(Array.from(hash), true, Array.from(prover2NextKeypairHash))
+      .accounts({
+        proverOld: prover2Old.publicKey,
+        prover: prover2Current.publicKey,
+        changeUtxo: null,
+        proverStake: proverStakePda(2),
+        ...(await ledgerPages()),
+      })
+      .remainingAccounts([{ pubkey: proverStakePda(1), isSigner: false, isWritable: true }])
+      .signers([prover2Old, prover2Current])
+      .rpc();
+
+    prover2Old = prover2Current;
+    prover2Current = prover2Next;
+    prover2Next = Keypair.generate();
+
+    utxoAccount = await program.account.utxo.fetch(utxoPda);
+    expect(utxoAccount.status).to.deep.equal({ finalized: {} });
+    expect(utxoAccount.attestationFee.toString()).to.equal("0");
+    expect((await rewards(1)).sub(prover1Before).toNumber()).to.equal(500);
+    expect((await rewards(2)).sub(prover2Before).toNumber()).to.equal(501);
+
+    await program.methods
+      .updateConfig({ ...noConfigChange, minAttestations: 1, attestationFee: config.attestationFee })
+      .accounts({ admin: admin.publicKey })
+      .rpc();
+  });
+
+  it("Creates a second UTXO (chained)", async () => {
+    const loader2Keypair = Keypair.generate();
+
+    await program.methods
+      .initLoader()
+      .accounts({
+        signer: admin.publicKey,
+        loader: loader2Keypair.publicKey,
+      })
+      .signers([loader2Keypair])
+      .rpc();
+
+    const newCiphertext = Buffer.alloc(1088);
+    for (let i = 0; i < 1088; i++) {
+      newCiphertext[i] = Math.floor(Math.random() * 256);
+    }
+
+    let offset = 0;
+    const CHUNK_SIZE = 800;
+    while (offset < newCiphertext.length) {
+      const chunk = newCiphertext.slice(offset, offset + CHUNK_SIZE);
+      await program.methods
+        .writeLoader(offset, Buffer.from(chunk))
+        .accounts({ authority: admin.publicKey, loader: loader2Keypair.publicKey })
+        .rpc();
+      offset += chunk.length;
+    }
+
+
+    const zkProof2 = await uploadRandomProof(500);
+    console.log("ZK Proof:", zkProof2.toString());
+
+    const epoch = 0;
+    const nonce = Buffer.alloc(12);
+    for (let i = 0; i < 12; i++) {
+      nonce[i] = Math.floor(Math.random() * 256);
+    }
+
+    const encryptedPayload = Buffer.alloc(256);
+    for (let i = 0; i < encryptedPayload.length; i++) {
+      encryptedPayload[i] = Math.floor(Math.random() * 256);
+    }
+
+    const ledgerAccount = await program.account.ledger.fetch(ledgerPda);
+    const prevUtxoHash = Buffer.from(ledgerAccount.tipHash);
+
+    const ciphertextCommitment = randomBytes(32);
 
     const epochBytes = Buffer.alloc(4);
     epochBytes.writeUInt32LE(epoch, 0);
     const headerData = Buffer.concat([
-      prevUtxoHash,
       ciphertextCommitment,
+      prevUtxoHash,
       epochBytes,
     ]);
     utxoHash2 = Buffer.from(sha256.array(headerData));
@@ -536,21 +1174,197 @@ describe("qcash_programs", () => {
         Array.from(utxoHash2),
         Buffer.from(encryptedPayload),
         Array.from(nonce),
-        Array.from(utxoHash),
+        Array.from(ciphertextCommitment),
         epoch,
+        [],
+        outputHash(newCiphertext, nonce, encryptedPayload),
+        Array.from(prevUtxoHash),
       )
       .accounts({
         signer: admin.publicKey,
         loader: loader2Keypair.publicKey,
-        zkProof: zkProof2Keypair.publicKey,
+        zkProof: zkProof2,
       })
       .rpc();
 
     console.log("Create second UTXO transaction:", tx);
 
     const utxo2Account = await program.account.utxo.fetch(utxo2Pda);
-    expect(Buffer.from(utxo2Account.prevUtxoHash)).to.deep.equal(utxoHash);
-    console.log("Second UTXO created and chained to first UTXO");
+    expect(Buffer.from(utxo2Account.prevUtxoHash)).to.deep.equal(prevUtxoHash);
+    console.log("Second UTXO created and linked to the tip after the first UTXO");
+  });
+
+  it("Rejects a UTXO linked to a ledger tip that is not recent", async () => {
+    const loader = await uploadRandomLoader();
+
+    try {
+      await program.methods
+        .createUtxo(
+          Array.from(randomBytes(32)),
+          randomBytes(100),
+          Array.from(randomBytes(12)),
+          Array.from(randomBytes(32)),
+          0,
+          [],
+          Array.from(randomBytes(32)),
+          Array.from(randomBytes(32)),
+        )
+        .accounts({
+          signer: admin.publicKey,
+          loader,
+          zkProof: Keypair.generate().publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for a stale ledger tip");
+    } catch (error) {
+      expect(error.toString()).to.include("StaleLedgerTip");
+    }
+  });
+
+  it("Rejects a UTXO declaring an epoch that hasn't started", async () => {
+    const loader = await uploadRandomLoader();
+
+    // The ledger was initialized during this run, so it is still in epoch 0
+    const ledgerAccount = await program.account.ledger.fetch(ledgerPda);
+    expect(ledgerAccount.genesisTimestamp.toNumber()).to.be.greaterThan(0);
+
+    try {
+      await program.methods
+        .createUtxo(
+          Array.from(randomBytes(32)),
+          randomBytes(100),
+          Array.from(randomBytes(12)),
+          Array.from(randomBytes(32)),
+          1,
+          [],
+          Array.from(randomBytes(32)),
+          await ledgerTip(),
+        )
+        .accounts({
+          signer: admin.publicKey,
+          loader,
+          zkProof: Keypair.generate().publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for a future epoch");
+    } catch (error) {
+      expect(error.toString()).to.include("StaleEpoch");
+    }
+  });
+
+  it("Rejects a UTXO built on a ZK proof another account uploaded", async () => {
+    const ciphertext = randomBytes(1088);
+    const loader = await uploadRandomLoader(ciphertext);
+
+    // Someone else uploads a complete proof, the admin tries to use it before they do
+    const uploader = Keypair.generate();
+    const airdropSig = await provider.connection.requestAirdrop(
+      uploader.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await provider.connection.confirmTransaction(airdropSig);
+
+    const proof = Keypair.generate();
+    const proofSize = 100;
+    const space = 8 + 4 + 4 + 32 + 32 + proofSize;
+    await program.methods
+      .initZkProof(proofSize)
+      .accounts({ signer: uploader.publicKey, zkProof: proof.publicKey })
+      .preInstructions([
+        SystemProgram.createAccount({
+          fromPubkey: uploader.publicKey,
+          newAccountPubkey: proof.publicKey,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+          space,
+          programId: program.programId,
+        }),
+      ])
+      .signers([uploader, proof])
+      .rpc();
+    await program.methods
+      .writeZkProof(0, randomBytes(proofSize))
+      .accounts({ authority: uploader.publicKey, zkProof: proof.publicKey })
+      .signers([uploader])
+      .rpc();
+
+    const nonce = randomBytes(12);
+    const encryptedPayload = randomBytes(100);
+    try {
+      await program.methods
+        .createUtxo(
+          Array.from(randomBytes(32)),
+          encryptedPayload,
+          Array.from(nonce),
+          Array.from(randomBytes(32)),
+          0,
+          [],
+          outputHash(ciphertext, nonce, encryptedPayload),
+          await ledgerTip(),
+        )
+        .accounts({
+          signer: admin.publicKey,
+          loader,
+          zkProof: proof.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for a proof uploaded by another account");
+    } catch (error) {
+      expect(error.toString()).to.include("UnauthorizedWriter");
+    }
+  });
+
+  it("Rejects a UTXO created from a loader another account uploaded", async () => {
+    // Someone else uploads their ciphertext, the admin tries to claim it and its rent
+    const uploader = Keypair.generate();
+    const airdropSig = await provider.connection.requestAirdrop(
+      uploader.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await provider.connection.confirmTransaction(airdropSig);
+
+    const loader = Keypair.generate();
+    const ciphertext = randomBytes(1088);
+    await program.methods
+      .initLoader()
+      .accounts({ signer: uploader.publicKey, loader: loader.publicKey })
+      .signers([uploader, loader])
+      .rpc();
+    for (let offset = 0; offset < ciphertext.length; offset += 800) {
+      await program.methods
+        .writeLoader(offset, ciphertext.slice(offset, offset + 800))
+        .accounts({ authority: uploader.publicKey, loader: loader.publicKey })
+        .signers([uploader])
+        .rpc();
+    }
+
+    const nonce = randomBytes(12);
+    const encryptedPayload = randomBytes(100);
+    try {
+      await program.methods
+        .createUtxo(
+          Array.from(randomBytes(32)),
+          encryptedPayload,
+          Array.from(nonce),
+          Array.from(randomBytes(32)),
+          0,
+          [],
+          outputHash(ciphertext, nonce, encryptedPayload),
+          await ledgerTip(),
+        )
+        .accounts({
+          signer: admin.publicKey,
+          loader: loader.publicKey,
+          zkProof: await uploadRandomProof(100),
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for a loader uploaded by another account");
+    } catch (error) {
+      expect(error.toString()).to.include("UnauthorizedWriter");
+    }
   });
 
   it("Deactivates prover 2", async () => {
@@ -584,6 +1398,9 @@ describe("qcash_programs", () => {
         .accounts({
           proverOld: prover2.publicKey,
           prover: prover2Current.publicKey,
+          changeUtxo: null,
+          proverStake: proverStakePda(2),
+          ...(await ledgerPages()),
         })
         .signers([prover2, prover2])
         .rpc();
@@ -596,4 +1413,340 @@ describe("qcash_programs", () => {
       console.log("Deactivated prover correctly rejected");
     }
   });
+
+  it("Rejects accept_admin from a key that was not proposed", async () => {
+    const proposed = Keypair.generate();
+    const intruder = Keypair.generate();
+
+    await program.methods
+      .proposeAdmin(proposed.publicKey)
+      .accounts({ admin: admin.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .acceptAdmin()
+        .accounts({ newAdmin: intruder.publicKey })
+        .signers([intruder])
+        .rpc();
+
+      expect.fail("Should have thrown error for an unproposed admin");
+    } catch (error) {
+      expect(error.toString()).to.include("UnauthorizedPendingAdmin");
+    }
+
+    await program.methods
+      .proposeAdmin(null)
+      .accounts({ admin: admin.publicKey })
+      .rpc();
+  });
+
+  it("Hands the admin role over with propose/accept", async () => {
+    const newAdmin = Keypair.generate();
+
+    await program.methods
+      .proposeAdmin(newAdmin.publicKey)
+      .accounts({ admin: admin.publicKey })
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: newAdmin.publicKey })
+      .signers([newAdmin])
+      .rpc();
+
+    let config = await program.account.programConfig.fetch(programConfigPda);
+    expect(config.adminAuthority.toString()).to.equal(newAdmin.publicKey.toString());
+    expect(config.pendingAdmin).to.be.null;
+
+    // The old admin has no rights left
+    try {
+      await program.methods
+        .updateConfig(noConfigChange)
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+
+      expect.fail("Should have thrown error for the previous admin");
+    } catch (error) {
+      expect(error.toString()).to.include("UnauthorizedAdmin");
+    }
+
+    // Hand it back for the remaining tests
+    await program.methods
+      .proposeAdmin(admin.publicKey)
+      .accounts({ admin: newAdmin.publicKey })
+      .signers([newAdmin])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: admin.publicKey })
+      .rpc();
+
+    config = await program.account.programConfig.fetch(programConfigPda);
+    expect(config.adminAuthority.toString()).to.equal(admin.publicKey.toString());
+  });
+
+  it("Requires M-of-N admin signers once a signer set is configured", async () => {
+    const cosigner1 = Keypair.generate();
+    const cosigner2 = Keypair.generate();
+
+    await program.methods
+      .setAdminSigners([admin.publicKey, cosigner1.publicKey, cosigner2.publicKey], 2)
+      .accounts({ admin: admin.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .updateConfig(noConfigChange)
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+
+      expect.fail("Should have thrown error for a single admin signature");
+    } catch (error) {
+      expect(error.toString()).to.include("InsufficientAdminSignatures");
+    }
+
+    const cosigner1Signature = {
+      pubkey: cosigner1.publicKey,
+      isSigner: true,
+      isWritable: false,
+    };
+
+    await program.methods
+      .updateConfig(noConfigChange)
+      .accounts({ admin: admin.publicKey })
+      .remainingAccounts([cosigner1Signature])
+      .signers([cosigner1])
+      .rpc();
+
+    // Two signers go back to a single admin
+    await program.methods
+      .proposeAdmin(admin.publicKey)
+      .accounts({ admin: admin.publicKey })
+      .remainingAccounts([cosigner1Signature])
+      .signers([cosigner1])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: admin.publicKey })
+      .rpc();
+
+    const config = await program.account.programConfig.fetch(programConfigPda);
+    expect(config.adminSigners).to.have.length(0);
+    expect(config.adminThreshold).to.equal(0);
+  });
+
+  const findProver = async (uniqueId: number) => {
+    const registry = await program.account.proverRegistry.fetch(proverRegistryPda);
+    return registry.provers.find((p: any) => p.uniqueId.toNumber() === uniqueId);
+  };
+
+  it("Reactivates prover 2", async () => {
+    await program.methods
+      .reactivateProver(new anchor.BN(2))
+      .accounts({ admin: admin.publicKey })
+      .rpc();
+
+    const prover2Info = await findProver(2);
+    expect(prover2Info.isActive).to.equal(true);
+  });
+
+  it("Rejects removing an active prover", async () => {
+    try {
+      await program.methods
+        .removeProver(new anchor.BN(2))
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+
+      expect.fail("Should have thrown error for removing an active prover");
+    } catch (error) {
+      expect(error.toString()).to.include("ProverStillActive");
+    }
+  });
+
+  it("Force-resets the key of a prover that lost its rotation keys", async () => {
+    const replacementKey = Keypair.generate();
+    const replacementHash = Buffer.from(sha256.array(replacementKey.publicKey.toBuffer()));
+
+    await program.methods
+      .resetProverKey(new anchor.BN(2), Array.from(replacementHash))
+      .accounts({ admin: admin.publicKey })
+      .rpc();
+
+    const prover2Info = await findProver(2);
+    expect(Buffer.from(prover2Info.pubkeyHash).equals(replacementHash)).to.be.true;
+
+    // The replacement key is what the registry now knows the prover by
+    await program.methods
+      .deactivateProver()
+      .accounts({
+        admin: admin.publicKey,
+        proverPubkey: replacementKey.publicKey,
+      })
+      .rpc();
+
+    expect((await findProver(2)).isActive).to.equal(false);
+  });
+
+  it("Removes a deactivated prover and frees its slot", async () => {
+    await program.methods
+      .removeProver(new anchor.BN(2))
+      .accounts({ admin: admin.publicKey })
+      .rpc();
+
+    let registryAccount = await program.account.proverRegistry.fetch(proverRegistryPda);
+    expect(registryAccount.proverCount).to.equal(1);
+    expect(await findProver(2)).to.be.undefined;
+
+    // The unique ID and slot can be registered again, the stake left under it still counts
+    const newProver = Keypair.generate();
+    await program.methods
+      .registerProver(
+        new anchor.BN(2),
+        Array.from(Buffer.from(sha256.array(newProver.publicKey.toBuffer()))),
+        new anchor.BN(0),
+      )
+      .accounts({
+        admin: admin.publicKey,
+        proverPubkey: newProver.publicKey,
+        staker: admin.publicKey,
+      })
+      .rpc();
+
+    registryAccount = await program.account.proverRegistry.fetch(proverRegistryPda);
+    expect(registryAccount.proverCount).to.equal(2);
+  });
+
+  it("Rejects registering a prover below the minimum stake", async () => {
+    const newProver = Keypair.generate();
+    try {
+      await program.methods
+        .registerProver(
+          new anchor.BN(3),
+          Array.from(Buffer.from(sha256.array(newProver.publicKey.toBuffer()))),
+          new anchor.BN(1000),
+        )
+        .accounts({
+          admin: admin.publicKey,
+          proverPubkey: newProver.publicKey,
+          staker: admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for an insufficient stake");
+    } catch (error) {
+      expect(error.toString()).to.include("InsufficientStake");
+    }
+  });
+
+  it("Rejects slashing a vote that agreed with the outcome", async () => {
+    // Prover 1 voted valid on the first UTXO, which was finalized
+    const [utxoPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("utxo"), utxoHash],
+      program.programId,
+    );
+
+    try {
+      await program.methods
+        .slashProver(new anchor.BN(1))
+        .accounts({
+          authority: admin.publicKey,
+          utxo: utxoPda,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown error for slashing a correct vote");
+    } catch (error) {
+      expect(error.toString()).to.include("NoSlashableVote");
+    }
+  });
+
+  it("Sweeps lamports held by the program config above its rent to the admin's destination", async () => {
+    const destination = Keypair.generate();
+
+    // Nothing was slashed, so only the config's rent is there
+    try {
+      await program.methods
+        .sweepSlashed()
+        .accounts({ admin: admin.publicKey, destination: destination.publicKey })
+        .rpc();
+
+      expect.fail("Should have thrown error for an empty sweep");
+    } catch (error) {
+      expect(error.toString()).to.include("NothingToSweep");
+    }
+
+    // Stands in for slashed stake
+    const slashed = 5_000_000;
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: programConfigPda,
+          lamports: slashed,
+        }),
+      ),
+    );
+
+    const intruder = Keypair.generate();
+    try {
+      await program.methods
+        .sweepSlashed()
+        .accounts({ admin: intruder.publicKey, destination: intruder.publicKey })
+        .signers([intruder])
+        .rpc();
+
+      expect.fail("Should have thrown error for a non-admin sweep");
+    } catch (error) {
+      expect(error.toString()).to.include("UnauthorizedAdmin");
+    }
+
+    await program.methods
+      .sweepSlashed()
+      .accounts({ admin: admin.publicKey, destination: destination.publicKey })
+      .rpc();
+
+    expect(await provider.connection.getBalance(destination.publicKey)).to.equal(slashed);
+    const configInfo = await provider.connection.getAccountInfo(programConfigPda);
+    expect(configInfo.lamports).to.equal(
+      await provider.connection.getMinimumBalanceForRentExemption(configInfo.data.length),
+    );
+  });
+
+  it("Unbonds stake and holds it until the unbonding period ends", async () => {
+    const unstakeAmount = new anchor.BN(1000);
+
+    // Only the stake owner can unstake
+    const stranger = Keypair.generate();
+    try {
+      await program.methods
+        .requestUnstake(new anchor.BN(2), unstakeAmount)
+        .accounts({ owner: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+
+      expect.fail("Should have thrown error for unstaking someone else's stake");
+    } catch (error) {
+      expect(error.toString()).to.include("UnauthorizedStakeOwner");
+    }
+
+    await program.methods
+      .requestUnstake(new anchor.BN(2), unstakeAmount)
+      .accounts({ owner: admin.publicKey })
+      .rpc();
+
+    const stakeAccount = await program.account.proverStake.fetch(proverStakePda(2));
+    expect(stakeAccount.amount.eq(proverStakeAmount.sub(unstakeAmount))).to.be.true;
+    expect(stakeAccount.unbondingAmount.eq(unstakeAmount)).to.be.true;
+
+    try {
+      await program.methods
+        .withdrawStake(new anchor.BN(2))
+        .accounts({ owner: admin.publicKey })
+        .rpc();
+
+      expect.fail("Should have thrown error for withdrawing before unbonding ends");
+    } catch (error) {
+      expect(error.toString()).to.include("UnbondingNotEnded");
+    }
+  });
 });
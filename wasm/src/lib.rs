@@ -6,14 +6,14 @@
 // Use of this software is governed by the LICENSE file.
 
 use qcash_core::wallet::WalletKeys;
-use qcash_core::{KYBER_PUBKEY_SIZE,KYBER_CIPHERTEXT_SIZE};
+use qcash_core::{KYBER_PUBKEY_SIZE,KYBER_CIPHERTEXT_SIZE,RngVersion};
 use serde::Serialize;
 use wasm_bindgen::prelude::*;
 use pqc_kyber::{encapsulate as pqc_encapsulate,decapsulate as pqc_decapsulate}; 
 use rand::rngs::OsRng;
 use rand::RngCore;
 use chacha20poly1305::{ChaCha20Poly1305, Key, KeyInit, Nonce, aead::{Aead}};
-use qcash_core::UTXOEncryptedPayload;
+use qcash_core::{encrypt_output, fold_history, UTXOCommitmentHeader, UTXOEncryptedPayload, VersionedPayload, PAYLOAD_VERSION};
 use sha2::{Sha256, Digest};
 
 #[wasm_bindgen]
@@ -90,8 +90,8 @@ pub fn encrypt_payload(
         is_return,
         receiver_vault: receiver_array,
         randomness: payload_randomness,
-        utxo_spent_list: vec![],
-        version: 1,
+        history_root: [0u8; 32],
+        version: PAYLOAD_VERSION,
     };
 
     let payload_bytes = bincode::serialize(&utxo_payload)
@@ -123,8 +123,10 @@ pub struct DecryptUtxo{
     pub is_return:bool,
     pub index:u32, // helps frontend track position
     #[wasm_bindgen(getter_with_clone)]
-    pub utxo_spent_list: Vec<u8>, // Flattened array,
-    pub spent_list_len:usize, // to help reconstruct chunks of 32 bytes
+    pub history_root: Vec<u8>, // 32 bytes, needed to spend this UTXO
+    pub version:u8, // payload version, the guest verifies version 1 inputs against utxo_spent_list
+    #[wasm_bindgen(getter_with_clone)]
+    pub utxo_spent_list: Vec<u8>, // 32 bytes per hash, empty unless version is 1
 }
 
 #[wasm_bindgen]
@@ -157,22 +159,23 @@ pub fn try_decrypt_utxo(
         Err(_)=> return Err("Not our UTXO(Decryption Failed)".into())
     };
 
-    // Deserialize the payload 
-    let payload:UTXOEncryptedPayload = bincode::deserialize(&decrypted_bytes)
-        .map_err(|_| "Deserializtaion Failed".to_string())?;
-
-    let mut flat_spent_list = Vec::new();
-    for hash in &payload.utxo_spent_list{
-        flat_spent_list.extend_from_slice(hash);
-    }
+    // Deserialize the payload, version 1 payloads are still on chain
+    let versioned = VersionedPayload::decode(&decrypted_bytes)
+        .map_err(|e| e.to_string())?;
+    let utxo_spent_list = match &versioned {
+        VersionedPayload::V1(legacy) => legacy.utxo_spent_list.concat(),
+        VersionedPayload::V2(_) => Vec::new(),
+    };
+    let payload = versioned.to_current::<Sha256>();
 
     Ok(DecryptUtxo { 
         amount: payload.amount, 
         randomness: payload.randomness.to_vec(), 
         is_return:  payload.is_return,
         index,
-        utxo_spent_list: flat_spent_list,
-        spent_list_len: payload.utxo_spent_list.len(),
+        history_root: payload.history_root.to_vec(),
+        version: payload.version,
+        utxo_spent_list,
     })
 
 }
@@ -184,6 +187,7 @@ pub struct WalletResult{
     pub kyber_pubkey:String,
     pub kyber_secret_key: Vec<u8>,  // For background sync
     pub secret_entropy_hex:String, // 32 bytes hex
+    pub kyber_version:u8, // RngVersion the Kyber key was derived with
 }
 
 #[wasm_bindgen]
@@ -196,17 +200,24 @@ pub fn generate_wallet()->JsValue{
         kyber_pubkey: bs58::encode(keys.kyber_key.public).into_string(),
         kyber_secret_key: keys.kyber_key.secret.to_vec(),
         secret_entropy_hex: hex::encode(keys.secret_entropy),
+        kyber_version: keys.kyber_version as u8,
     };
 
     // Serialize to JS Object
     serde_wasm_bindgen::to_value(&result).unwrap()
 }
 
+// kyber_version defaults to 1, the version of every wallet created before RngVersion 2
+// Wallets created since pass the kyber_version generate_wallet returned
 #[wasm_bindgen]
-pub fn restore_wallet(mnemonic:&str)->Result<JsValue,String>{
+pub fn restore_wallet(mnemonic:&str, kyber_version:Option<u8>)->Result<JsValue,String>{
+    let kyber_version = match kyber_version {
+        Some(version) => RngVersion::try_from(version).map_err(|e| e.to_string())?,
+        None => RngVersion::V1,
+    };
 
     let keys = std::panic::catch_unwind(||{
-        WalletKeys::from_mnemonic(mnemonic)
+        WalletKeys::from_mnemonic_with_version(mnemonic, kyber_version)
     }).map_err(|_| "Invalid Mnemonic Phrase".to_string())?;
 
     let result = WalletResult{
@@ -215,6 +226,7 @@ pub fn restore_wallet(mnemonic:&str)->Result<JsValue,String>{
         kyber_pubkey:bs58::encode(keys.kyber_key.public).into_string(),
         kyber_secret_key: keys.kyber_key.secret.to_vec(),
         secret_entropy_hex:hex::encode(keys.secret_entropy),
+        kyber_version: keys.kyber_version as u8,
     };
 
     Ok(serde_wasm_bindgen::to_value(&result).unwrap())
@@ -240,24 +252,31 @@ pub struct OutputResult{
     pub kyber_ciphertext:Vec<u8>,
     pub nonce:Vec<u8>,
     pub encrypted_payload:Vec<u8>,
+    pub output_hash:Vec<u8>,
 
     // For prover inputs 
     pub randomness:Vec<u8>,
     pub receiver_vault:Vec<u8>,
     pub is_return:bool,
+    pub encapsulation_seed:Vec<u8>,
 }
 
-// Helper to replicate the guest hashing logic 
-pub fn hash_payload_internal(payload:&UTXOEncryptedPayload)->[u8;32]{
-    let mut hasher = Sha256::new();
-    hasher.update(payload.amount.to_le_bytes());
-    hasher.update(&[payload.is_return as u8]);
-    hasher.update(payload.receiver_vault);
-    hasher.update(payload.randomness);
-    for h in &payload.utxo_spent_list{
-        hasher.update(h);
+// Nullifier of a spent UTXO, matches the guest: H("qcash:nullifier" || utxo_hash || randomness)
+#[wasm_bindgen]
+pub fn compute_nullifier(utxo_hash:&[u8],randomness:&[u8])->Result<Vec<u8>,String>{
+    if utxo_hash.len() != 32 {
+        return Err("Invalid UTXO Hash Size".into());
+    }
+
+    if randomness.len() != 32 {
+        return Err("Invalid Randomness Size".into());
     }
-    hasher.finalize().into()
+
+    let mut hasher = Sha256::new();
+    hasher.update(b"qcash:nullifier");
+    hasher.update(utxo_hash);
+    hasher.update(randomness);
+    Ok(hasher.finalize().to_vec())
 }
 
 #[wasm_bindgen]
@@ -268,7 +287,31 @@ pub fn prepare_output(
     prev_utxo_hash_bytes:&[u8],
     epoch:u32,
     is_return:bool,
+    spent_inputs:&[u8], // (history_root || utxo_hash) of each spent input in order, the guest folds the same
 )->Result<JsValue,String>{
+    let result = build_output(
+        receiver_pubkey_bytes,
+        receiver_vault_pda,
+        amount,
+        prev_utxo_hash_bytes,
+        epoch,
+        is_return,
+        spent_inputs,
+    )?;
+
+    Ok(serde_wasm_bindgen::to_value(&result).unwrap())
+}
+
+// prepare_output without the JS conversion, so native code and tests can call it
+pub fn build_output(
+    receiver_pubkey_bytes:&[u8],
+    receiver_vault_pda: &[u8],
+    amount:u64,
+    prev_utxo_hash_bytes:&[u8],
+    epoch:u32,
+    is_return:bool,
+    spent_inputs:&[u8],
+)->Result<OutputResult,String>{
     if receiver_pubkey_bytes.len() != KYBER_PUBKEY_SIZE {
         return Err("Invalid Pubkey Size".into());
     }
@@ -277,66 +320,70 @@ pub fn prepare_output(
         return Err("Invalid Vault PDA Size (expected 32 bytes)".into());
     }
 
+    let prev_utxo_hash: [u8; 32] = prev_utxo_hash_bytes
+        .try_into()
+        .map_err(|_| "Invalid Prev UTXO Hash Size (expected 32 bytes)".to_string())?;
+
+    if !spent_inputs.len().is_multiple_of(64) {
+        return Err("Invalid Spent Inputs Size (expected multiple of 64 bytes)".into());
+    }
+
     let mut pubkey_arr = [0u8; KYBER_PUBKEY_SIZE];
     pubkey_arr.copy_from_slice(receiver_pubkey_bytes);
 
     let mut vault_pda_arr = [0u8; 32];
     vault_pda_arr.copy_from_slice(receiver_vault_pda);
 
-    // Kyber Encapsulate
-    let mut rng = OsRng;
-    let (ciphertext, shared_secret) = match pqc_encapsulate(&pubkey_arr, &mut rng) {
-        Ok(res) => res,
-        Err(_) => return Err("Encapsulation Failed".into()),
-    };
+    let history_root = spent_inputs
+        .chunks_exact(64)
+        .fold([0u8; 32], |acc, input| {
+            let (input_history_root, input_utxo_hash) = input.split_at(32);
+            fold_history::<Sha256>(&acc, input_history_root.try_into().unwrap(), input_utxo_hash.try_into().unwrap())
+        });
 
+    let mut rng = OsRng;
     let mut payload_randomness = [0u8; 32];
     rng.try_fill_bytes(&mut payload_randomness).expect("RNG Error");
 
+    // The guest re-runs the encryption from these, so they are returned for the prover inputs
+    let mut encapsulation_seed = [0u8; 32];
+    rng.try_fill_bytes(&mut encapsulation_seed).expect("RNG Error");
+
+    let mut nonce_bytes = [0u8; 12];
+    rng.try_fill_bytes(&mut nonce_bytes).expect("RNG Error");
+
     let payload = UTXOEncryptedPayload {
         amount,
         is_return,
         receiver_vault: vault_pda_arr,
         randomness: payload_randomness,
-        utxo_spent_list: vec![], // TODO: Add history 
-        version: 1,
+        history_root,
+        version: PAYLOAD_VERSION,
     };
 
-    let c_commitment = hash_payload_internal(&payload);
+    // Same header the guest commits for this output
+    let header = UTXOCommitmentHeader::compute::<Sha256>(&payload, prev_utxo_hash, epoch);
 
-    // calculating UTXO hash 
-    let mut header_hasher = Sha256::new();
-    header_hasher.update(c_commitment);
-    header_hasher.update(prev_utxo_hash_bytes);
-    header_hasher.update(epoch.to_le_bytes());
-    let utxo_hash : [u8;32] = header_hasher.finalize().into();
-
-    // Payload encryption
-    let payload_bytes = bincode::serialize(&payload).map_err(|e| e.to_string())?;
-    let key = Key::from_slice(&shared_secret);
-    let cipher = ChaCha20Poly1305::new(key);
-
-    let mut nonce_bytes = [0u8; 12];
-    rng.try_fill_bytes(&mut nonce_bytes).expect("RNG Error");
-    let nonce = Nonce::from_slice(&nonce_bytes);
-
-    let encrypted_payload = cipher.encrypt(nonce, payload_bytes.as_ref())
-        .map_err(|_| "Encryption Failed".to_string())?;
+    // Kyber Encapsulate + Payload encryption, deterministic in (encapsulation_seed, nonce)
+    let encrypted = encrypt_output(&pubkey_arr, &payload, &encapsulation_seed, &nonce_bytes)
+        .map_err(|e| e.to_string())?;
+    let output_hash = encrypted.hash::<Sha256>();
 
     let result = OutputResult {
-        utxo_hash: utxo_hash.to_vec(),
-        prev_utxo_hash: prev_utxo_hash_bytes.to_vec(),
-        ciphertext_commitment: c_commitment.to_vec(),
+        utxo_hash: header.utxo_hash.to_vec(),
+        prev_utxo_hash: header.prev_utxo_hash.to_vec(),
+        ciphertext_commitment: header.ciphertext_commitment.to_vec(),
         epoch,
-        kyber_ciphertext: ciphertext.to_vec(),
-        nonce: nonce_bytes.to_vec(),
-        encrypted_payload,
+        kyber_ciphertext: encrypted.kyber_ciphertext.to_vec(),
+        nonce: encrypted.nonce.to_vec(),
+        encrypted_payload: encrypted.encrypted_payload,
+        output_hash: output_hash.to_vec(),
         
         randomness: payload_randomness.to_vec(),
         receiver_vault: vault_pda_arr.to_vec(),
         is_return,
+        encapsulation_seed: encapsulation_seed.to_vec(),
     };
 
-    Ok(serde_wasm_bindgen::to_value(&result).unwrap())
-    
+    Ok(result)
 }
\ No newline at end of file
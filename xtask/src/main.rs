@@ -36,6 +36,8 @@ use tracing_subscriber::FmtSubscriber;
 
 const FAUCET_WALLET: &str = include_str!("../../qcash-faucet/faucet-wallet.json");
 const FAUCET_PUBKEY: Pubkey = pubkey!("43XSgRV3LPo3gWMVJYLHYnnEayVa5sbXT12zJ2uih4j7");
+/// Stake the owner locks for every test prover, the program's default minimum
+const PROVER_STAKE: u64 = LAMPORTS_PER_SOL / 10;
 
 #[derive(Parser)]
 #[command(name = "xtask")]
@@ -860,9 +862,9 @@ async fn start_test_environment(
     // Create owner keypair
     let owner = Arc::new(Keypair::new());
 
-    // Fund the owner and the faucet accounts
+    // Fund the owner (which also stakes for every prover) and the faucet accounts
     let mut validator_accounts = vec![
-        SolanaAccount::new_with_lamports(owner.pubkey(), LAMPORTS_PER_SOL)?,
+        SolanaAccount::new_with_lamports(owner.pubkey(), 10 * LAMPORTS_PER_SOL)?,
         SolanaAccount::new_with_lamports(FAUCET_PUBKEY, LAMPORTS_PER_SOL)?,
     ];
 
@@ -963,6 +965,9 @@ async fn start_test_environment(
             solana_keys.0.keypair.pubkey(),
             next_key_hash
         );
+        let unique_id = i as u64 + 1;
+        let (prover_stake_pda, _bump) =
+            accounts::RegisterProver::prover_stake_pda(&interface::PROGRAM_ID, unique_id);
         let register_prover = interface::register_prover(
             &interface::PROGRAM_ID,
             accounts::RegisterProver {
@@ -970,15 +975,43 @@ async fn start_test_environment(
                 program_config: program_config_pda,
                 prover_registry: prover_registry_pda,
                 prover_pubkey: solana_keys.0.keypair.pubkey(),
+                staker: owner.pubkey(),
+                prover_stake: prover_stake_pda,
+                ..Default::default()
             },
             instructions::RegisterProver {
-                unique_id: i as u64 + 1,
+                unique_id,
                 next_key_hash,
+                stake_amount: PROVER_STAKE,
             },
         );
         send_transaction(&rpc_client, &owner, &[register_prover]).await?;
     }
 
+    // The program admin starts out as the faucet authority, hand it to the faucet wallet
+    let set_faucet_authority = interface::update_config(
+        &interface::PROGRAM_ID,
+        accounts::UpdateConfig {
+            admin: owner.pubkey(),
+            program_config: program_config_pda,
+            prover_registry: prover_registry_pda,
+        },
+        instructions::UpdateConfig {
+            update: interface::types::ConfigUpdate {
+                min_attestations: None,
+                min_rejections: None,
+                faucet_authority: Some(FAUCET_PUBKEY),
+                max_airdrops_per_vault: None,
+                max_airdrops_per_epoch: None,
+                min_prover_stake: None,
+                slash_bps: None,
+                unbonding_period: None,
+                attestation_fee: None,
+            },
+        },
+    );
+    send_transaction(&rpc_client, &owner, &[set_faucet_authority]).await?;
+
     let (mut faucet_backend, mut faucet_frontend) = run_faucet(false).await?;
 
     info!("Test environment is running. Press Ctrl+C to stop.");